@@ -0,0 +1,28 @@
+//! Compares `fast_int` against the standard integer formatting.
+//!
+//! Run with `cargo bench --bench fast_int`.
+
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::Instant;
+
+const N: u64 = 2_000_000;
+
+fn bench(name: &str, f: impl Fn(&mut String, u64)) {
+	let mut s = String::with_capacity(64);
+	let start = Instant::now();
+	for i in 0..N {
+		s.clear();
+		f(&mut s, black_box(i.wrapping_mul(0x9E3779B97F4A7C15)));
+		black_box(&s);
+	}
+	let elapsed = start.elapsed();
+	println!("{:<10} {:>8.2} ns/iter", name, elapsed.as_nanos() as f64 / N as f64);
+}
+
+fn main() {
+	bench("std", |s, n| write!(s, "{}", n).unwrap());
+	bench("fast_int", |s, n| write!(s, "{}", fmtools::fast_int(n)).unwrap());
+	bench("std i32", |s, n| write!(s, "{}", n as i32).unwrap());
+	bench("fast i32", |s, n| write!(s, "{}", fmtools::fast_int(n as i32)).unwrap());
+}
@@ -0,0 +1,23 @@
+//! Compares `join_str` against formatting the generic `join`.
+//!
+//! Run with `cargo bench --bench join_str`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+const N: u32 = 200_000;
+
+fn bench(name: &str, f: impl Fn(&[String]) -> String) {
+	let items: Vec<String> = (0..16).map(|i| format!("item-{}", i)).collect();
+	let start = Instant::now();
+	for _ in 0..N {
+		black_box(f(black_box(&items)));
+	}
+	let elapsed = start.elapsed();
+	println!("{:<10} {:>8.2} ns/iter", name, elapsed.as_nanos() as f64 / N as f64);
+}
+
+fn main() {
+	bench("join", |items| fmtools::join(", ", items).to_string());
+	bench("join_str", |items| fmtools::join_str(", ", items));
+}
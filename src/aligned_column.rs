@@ -0,0 +1,104 @@
+use std::fmt;
+
+/// Displays the items as rows with the keys padded to align the rest of the rows.
+///
+/// ```
+/// let deps = [("serde", "1.0"), ("unicode-width", "0.1"), ("fmtools", "0.2")];
+/// let s = fmtools::aligned_column(&deps, |&(name, version)| (name, version)).to_string();
+/// assert_eq!(s, "\
+/// serde         1.0
+/// unicode-width 0.1
+/// fmtools       0.2");
+/// ```
+///
+/// The closure returns the displayable key and rest of every item.
+/// All keys are measured first, then every row is rendered as the key padded to the widest key, the gap and the rest.
+/// Rows are separated by newlines, there is no trailing newline.
+///
+/// Only the widths of the keys are buffered, the rest is streamed.
+/// The iterator is cloned every time the object is displayed and the closure is invoked twice for every item.
+#[inline]
+pub fn aligned_column<T, F, K, R>(collection: T, f: F) -> AlignedColumn<'static, T::IntoIter, F>
+	where T: IntoIterator,
+		T::IntoIter: Clone,
+		F: Fn(T::Item) -> (K, R),
+		K: fmt::Display,
+		R: fmt::Display,
+{
+	AlignedColumn { iter: collection.into_iter(), f, gap: " " }
+}
+
+/// Displayable object returned by [`aligned_column`].
+#[derive(Copy, Clone)]
+pub struct AlignedColumn<'a, I, F> {
+	iter: I,
+	f: F,
+	gap: &'a str,
+}
+
+impl<'a, I, F> AlignedColumn<'a, I, F> {
+	/// Sets the gap between the padded key and the rest, defaults to a single space.
+	///
+	/// ```
+	/// let env = [("PATH", "/usr/bin"), ("HOME", "/root"), ("LANG", "C")];
+	/// let s = fmtools::aligned_column(env, |(key, value)| (key, value)).gap(" = ").to_string();
+	/// assert_eq!(s, "PATH = /usr/bin\nHOME = /root\nLANG = C");
+	/// ```
+	#[inline]
+	pub fn gap<'b>(self, gap: &'b str) -> AlignedColumn<'b, I, F> {
+		AlignedColumn { iter: self.iter, f: self.f, gap }
+	}
+}
+
+impl<I, F, K, R> fmt::Display for AlignedColumn<'_, I, F>
+	where I: Iterator + Clone,
+		F: Fn(I::Item) -> (K, R),
+		K: fmt::Display,
+		R: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let widths = self.iter.clone()
+			.map(|item| crate::pad::width_of(&(self.f)(item).0))
+			.collect::<Result<Vec<usize>, fmt::Error>>()?;
+		let max_width = widths.iter().copied().max().unwrap_or(0);
+
+		for (i, (item, width)) in self.iter.clone().zip(widths).enumerate() {
+			if i > 0 {
+				f.write_str("\n")?;
+			}
+			let (key, rest) = (self.f)(item);
+			write!(f, "{}", key)?;
+			crate::pad::write_fill(f, ' ', max_width.saturating_sub(width))?;
+			f.write_str(self.gap)?;
+			write!(f, "{}", rest)?;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([I, F, K, R] AlignedColumn<'_, I, F>
+	where I: Iterator + Clone,
+		F: Fn(I::Item) -> (K, R),
+		K: fmt::Display,
+		R: fmt::Display,
+);
+
+#[test]
+fn tests() {
+	// Varying key widths, multi-byte and wide keys
+	let rows = [("a", 1), ("héllo", 22), ("€€", 333), ("日本", 4)];
+	let s = aligned_column(&rows, |&(key, value)| (key, value)).to_string();
+	#[cfg(not(feature = "unicode-width"))]
+	assert_eq!(s, "a     1\nhéllo 22\n€€    333\n日本    4");
+	#[cfg(feature = "unicode-width")]
+	assert_eq!(s, "a     1\nhéllo 22\n€€    333\n日本  4");
+	let s = aligned_column(&rows[..3], |&(key, value)| (key, value)).gap(": ").to_string();
+	assert_eq!(s, "a    : 1\nhéllo: 22\n€€   : 333");
+
+	// Keys and rest with formatting
+	let s = aligned_column(1..=3, |n| (crate::fmt!(move "n="{n * 500}), crate::fmt!(move {n:>3}))).to_string();
+	assert_eq!(s, "n=500    1\nn=1000   2\nn=1500   3");
+
+	// Empty iterator and empty keys
+	assert_eq!(aligned_column(&[] as &[(&str, i32)], |&(k, v)| (k, v)).to_string(), "");
+	assert_eq!(aligned_column(["", ""], |s| (s, "x")).to_string(), " x\n x");
+}
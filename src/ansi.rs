@@ -0,0 +1,475 @@
+/*!
+Adapters aware of ANSI escape sequences.
+
+Colored output contains escape sequences which take no space on the terminal.
+The adapters in this module skip over CSI sequences (eg. `\x1b[31m`) and OSC sequences (eg. hyperlinks) when measuring the width while still writing them.
+
+```
+use fmtools::{ansi, Align};
+
+let status = "\x1b[32mok\x1b[0m";
+let s = fmtools::format!("|"{ansi::pad(6, Align::Left, status)}"|");
+assert_eq!(s, "|\x1b[32mok\x1b[0m    |");
+assert_eq!(ansi::strip(s).to_string(), "|ok    |");
+```
+
+The width is measured in chars, with the `unicode-width` feature in display columns.
+*/
+
+use core::fmt;
+use crate::Align;
+
+/// Removes the escape sequences from the output.
+///
+/// ```
+/// let s = fmtools::ansi::strip("\x1b[1;31merror\x1b[0m: \x1b]8;;https://example.com\x07link\x1b]8;;\x07").to_string();
+/// assert_eq!(s, "error: link");
+/// ```
+#[inline]
+pub fn strip<T: fmt::Display>(value: T) -> Strip<T> {
+	Strip { value }
+}
+
+/// Displayable object returned by [`strip`].
+#[derive(Copy, Clone)]
+pub struct Strip<T> {
+	value: T,
+}
+impl<T: fmt::Display> fmt::Display for Strip<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		struct StripWriter<'a, 'f> {
+			f: &'a mut fmt::Formatter<'f>,
+			parser: Parser,
+		}
+		impl fmt::Write for StripWriter<'_, '_> {
+			fn write_str(&mut self, mut s: &str) -> fmt::Result {
+				while let Some((escape, chunk)) = self.parser.split(&mut s) {
+					if !escape {
+						self.f.write_str(chunk)?;
+					}
+				}
+				Ok(())
+			}
+		}
+		fmt::write(&mut StripWriter { f, parser: Parser::new() }, format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display] Strip<T>);
+
+/// Pads the value with spaces to the given width, ignoring escape sequences.
+///
+/// ```
+/// use fmtools::{ansi, Align};
+///
+/// let s = ansi::pad(5, Align::Right, "\x1b[31m42\x1b[0m").to_string();
+/// assert_eq!(s, "   \x1b[31m42\x1b[0m");
+/// ```
+///
+/// The ANSI-aware version of [`pad`](crate::pad).
+#[inline]
+pub fn pad<T: fmt::Display>(width: usize, align: Align, value: T) -> Pad<T> {
+	Pad { value, width, align }
+}
+
+/// Displayable object returned by [`pad`].
+#[derive(Copy, Clone)]
+pub struct Pad<T> {
+	value: T,
+	width: usize,
+	align: Align,
+}
+impl<T: fmt::Display> fmt::Display for Pad<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let padding = self.width.saturating_sub(width_of(&self.value)?);
+		crate::pad::write_padded(f, &self.value, padding, self.align)
+	}
+}
+debug_as_display!([T: fmt::Display] Pad<T>);
+
+/// Pads or truncates the value to exactly the given width, ignoring escape sequences.
+///
+/// ```
+/// use fmtools::{ansi, Align};
+///
+/// let s = ansi::fit(6, Align::Left, "\x1b[33mconfiguration\x1b[0m").to_string();
+/// assert_eq!(s, "\x1b[33mconfi…\x1b[0m");
+/// ```
+///
+/// Escape sequences after the cut are still written, a trailing reset is not lost.
+///
+/// The ANSI-aware version of [`fit`](crate::fit).
+#[inline]
+pub fn fit<T: fmt::Display>(width: usize, align: Align, value: T) -> Fit<'static, T> {
+	Fit { value, width, align, suffix: "…" }
+}
+
+/// Displayable object returned by [`fit`].
+#[derive(Copy, Clone)]
+pub struct Fit<'a, T> {
+	value: T,
+	width: usize,
+	align: Align,
+	suffix: &'a str,
+}
+impl<T> Fit<'_, T> {
+	/// Sets the suffix written when the output is truncated.
+	///
+	/// The suffix is omitted if it is wider than the width.
+	#[inline]
+	pub fn suffix(self, suffix: &str) -> Fit<'_, T> {
+		Fit { value: self.value, width: self.width, align: self.align, suffix }
+	}
+}
+impl<T: fmt::Display> fmt::Display for Fit<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let width = width_of(&self.value)?;
+		if width <= self.width {
+			return crate::pad::write_padded(f, &self.value, self.width - width, self.align);
+		}
+		let mut suffix = self.suffix;
+		let mut suffix_width = crate::width::str_width(suffix);
+		if suffix_width > self.width {
+			suffix = "";
+			suffix_width = 0;
+		}
+		let mut writer = FitWriter { f, parser: Parser::new(), remaining: self.width - suffix_width, suffix: Some(suffix) };
+		fmt::write(&mut writer, format_args!("{}", self.value))?;
+		let remaining = writer.remaining;
+		crate::pad::write_fill(f, ' ', remaining)
+	}
+}
+debug_as_display!([T: fmt::Display] Fit<'_, T>);
+
+struct FitWriter<'a, 'f, 's> {
+	f: &'a mut fmt::Formatter<'f>,
+	parser: Parser,
+	remaining: usize,
+	// Written at the cut
+	suffix: Option<&'s str>,
+}
+impl fmt::Write for FitWriter<'_, '_, '_> {
+	fn write_str(&mut self, mut s: &str) -> fmt::Result {
+		while let Some((escape, chunk)) = self.parser.split(&mut s) {
+			if escape {
+				self.f.write_str(chunk)?;
+				continue;
+			}
+			let Some(suffix) = self.suffix else { continue };
+			let mut end = chunk.len();
+			for (i, chr) in chunk.char_indices() {
+				let width = crate::width::char_width(chr);
+				if width > self.remaining {
+					end = i;
+					break;
+				}
+				self.remaining -= width;
+			}
+			self.f.write_str(&chunk[..end])?;
+			if end < chunk.len() {
+				self.f.write_str(suffix)?;
+				self.suffix = None;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Wraps lines longer than the width, ignoring escape sequences.
+///
+/// ```
+/// let s = fmtools::ansi::wrap(4, "\x1b[31mabcdef\x1b[0m").to_string();
+/// assert_eq!(s, "\x1b[31mabcd\x1b[0m\n\x1b[31mef\x1b[0m");
+/// ```
+///
+/// Lines are broken at the width, every line has at least one char.
+///
+/// The active colors and styles (SGR parameters) are tracked and reset at the end of every broken line and restored on the continuation line.
+/// This keeps the colors from bleeding into whatever is written next to the line, like the gutter of a table.
+#[inline]
+pub fn wrap<T: fmt::Display>(width: usize, value: T) -> Wrap<T> {
+	Wrap { value, width }
+}
+
+/// Displayable object returned by [`wrap`].
+#[derive(Copy, Clone)]
+pub struct Wrap<T> {
+	value: T,
+	width: usize,
+}
+impl<T: fmt::Display> fmt::Display for Wrap<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut writer = WrapWriter { f, parser: Parser::new(), width: self.width, column: 0 };
+		fmt::write(&mut writer, format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display] Wrap<T>);
+
+struct WrapWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	parser: Parser,
+	width: usize,
+	column: usize,
+}
+impl fmt::Write for WrapWriter<'_, '_> {
+	fn write_str(&mut self, mut s: &str) -> fmt::Result {
+		while let Some((escape, chunk)) = self.parser.split(&mut s) {
+			if escape {
+				self.f.write_str(chunk)?;
+				continue;
+			}
+			let mut start = 0;
+			for (i, chr) in chunk.char_indices() {
+				if chr == '\n' {
+					self.column = 0;
+					continue;
+				}
+				let width = crate::width::char_width(chr);
+				if self.column + width > self.width && self.column > 0 {
+					self.f.write_str(&chunk[start..i])?;
+					start = i;
+					let sgr = self.parser.sgr();
+					if !sgr.is_empty() {
+						self.f.write_str("\x1b[0m")?;
+					}
+					self.f.write_str("\n")?;
+					if !sgr.is_empty() {
+						write!(self.f, "\x1b[{}m", sgr)?;
+					}
+					self.column = 0;
+				}
+				self.column += width;
+			}
+			self.f.write_str(&chunk[start..])?;
+		}
+		Ok(())
+	}
+}
+
+// Measures the visible width of the output.
+fn width_of<T: fmt::Display + ?Sized>(value: &T) -> Result<usize, fmt::Error> {
+	struct Measure {
+		parser: Parser,
+		width: usize,
+	}
+	impl fmt::Write for Measure {
+		fn write_str(&mut self, mut s: &str) -> fmt::Result {
+			while let Some((escape, chunk)) = self.parser.split(&mut s) {
+				if !escape {
+					self.width += crate::width::str_width(chunk);
+				}
+			}
+			Ok(())
+		}
+	}
+	let mut measure = Measure { parser: Parser::new(), width: 0 };
+	fmt::write(&mut measure, format_args!("{}", value))?;
+	Ok(measure.width)
+}
+
+const ESC: u8 = 0x1b;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum State {
+	Ground,
+	Escape,
+	Csi,
+	Osc,
+	OscEscape,
+}
+
+// Splits the output in text and escape sequences, escape sequences may be split across writes.
+// Tracks the active SGR parameters.
+pub(crate) struct Parser {
+	state: State,
+	params: Buf<32>,
+	sgr: Buf<64>,
+	reset: bool,
+}
+impl Parser {
+	pub(crate) const fn new() -> Parser {
+		Parser { state: State::Ground, params: Buf::new(), sgr: Buf::new(), reset: false }
+	}
+
+	// Returns if an SGR reset sequence was completed since the last call.
+	pub(crate) fn take_reset(&mut self) -> bool {
+		core::mem::replace(&mut self.reset, false)
+	}
+
+	// Returns the active SGR parameters.
+	fn sgr(&self) -> &str {
+		self.sgr.as_str()
+	}
+
+	// Splits off the next chunk of text or escape sequence, returns true if it is an escape sequence.
+	pub(crate) fn split<'s>(&mut self, s: &mut &'s str) -> Option<(bool, &'s str)> {
+		let bytes = s.as_bytes();
+		let first = *bytes.first()?;
+		let escape = self.state != State::Ground || first == ESC;
+		let mut end = 0;
+		if escape {
+			while end < bytes.len() && self.step(bytes[end]) {
+				end += 1;
+				if self.state == State::Ground {
+					break;
+				}
+			}
+			// Malformed sequence, the byte is text
+			if end == 0 {
+				end = bytes.iter().position(|&b| b == ESC).unwrap_or(bytes.len());
+				let (chunk, rest) = s.split_at(end);
+				*s = rest;
+				return Some((false, chunk));
+			}
+		}
+		else {
+			end = bytes.iter().position(|&b| b == ESC).unwrap_or(bytes.len());
+		}
+		let (chunk, rest) = s.split_at(end);
+		*s = rest;
+		Some((escape, chunk))
+	}
+
+	// Advances the state machine, returns false if the byte is not part of the escape sequence.
+	fn step(&mut self, byte: u8) -> bool {
+		match self.state {
+			State::Ground => {
+				self.state = State::Escape;
+			},
+			State::Escape => match byte {
+				b'[' => {
+					self.params.clear();
+					self.state = State::Csi;
+				},
+				b']' => self.state = State::Osc,
+				0x20..=0x7e => self.state = State::Ground,
+				_ => {
+					self.state = State::Ground;
+					return false;
+				},
+			},
+			State::Csi => match byte {
+				0x30..=0x3f => self.params.push(byte),
+				0x20..=0x2f => (),
+				0x40..=0x7e => {
+					if byte == b'm' {
+						self.apply_sgr();
+					}
+					self.state = State::Ground;
+				},
+				_ => {
+					self.state = State::Ground;
+					return false;
+				},
+			},
+			State::Osc => match byte {
+				0x07 => self.state = State::Ground,
+				ESC => self.state = State::OscEscape,
+				_ => (),
+			},
+			State::OscEscape => {
+				self.state = if byte == b'\\' { State::Ground } else { State::Osc };
+			},
+		}
+		true
+	}
+
+	fn apply_sgr(&mut self) {
+		let params = self.params.as_str();
+		if self.params.overflow {
+			return;
+		}
+		if params.is_empty() || params == "0" {
+			self.sgr.clear();
+			self.reset = true;
+		}
+		else if let Some(rest) = params.strip_prefix("0;") {
+			self.sgr.clear();
+			self.sgr.push_str(rest);
+		}
+		else {
+			if !self.sgr.is_empty() {
+				self.sgr.push(b';');
+			}
+			self.sgr.push_str(params);
+			// Too many parameters to track, keep the most recent ones
+			if self.sgr.overflow {
+				self.sgr.clear();
+				self.sgr.push_str(params);
+			}
+		}
+	}
+}
+
+// Small buffer of ASCII bytes.
+struct Buf<const N: usize> {
+	buf: [u8; N],
+	len: usize,
+	overflow: bool,
+}
+impl<const N: usize> Buf<N> {
+	const fn new() -> Buf<N> {
+		Buf { buf: [0; N], len: 0, overflow: false }
+	}
+	fn clear(&mut self) {
+		self.len = 0;
+		self.overflow = false;
+	}
+	fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+	fn push(&mut self, byte: u8) {
+		if self.len < N {
+			self.buf[self.len] = byte;
+			self.len += 1;
+		}
+		else {
+			self.overflow = true;
+		}
+	}
+	fn push_str(&mut self, s: &str) {
+		for &byte in s.as_bytes() {
+			self.push(byte);
+		}
+	}
+	fn as_str(&self) -> &str {
+		// Only ASCII bytes are ever pushed
+		core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+	}
+}
+
+#[test]
+fn tests() {
+	const RED: &str = "\x1b[31m";
+	const BOLD: &str = "\x1b[1m";
+	const RESET: &str = "\x1b[0m";
+	const LINK: &str = "\x1b]8;;https://example.com/€\x1b\\";
+
+	// Strip
+	assert_eq!(strip(crate::fmt!({RED}"red"{RESET}" "{LINK}"link"{LINK})).to_string(), "red link");
+	assert_eq!(strip(crate::fmt!({"\x1b["}{"31"}{"mred\x1b"}{"[0m"})).to_string(), "red");
+	assert_eq!(strip("\x1b€").to_string(), "€");
+	assert_eq!(strip("plain").to_string(), "plain");
+
+	// Padded into a table column
+	let cells = [crate::format!({RED}"err"{RESET}), crate::format!({BOLD}{RED}"warning"{RESET}), "ok".to_string()];
+	let column: Vec<String> = cells.iter().map(|cell| crate::format!("|"{pad(8, Align::Left, cell)}"|")).collect();
+	for row in &column {
+		assert_eq!(strip(row).to_string().len(), 10, "{:?}", row);
+	}
+	assert_eq!(column[0], "|\x1b[31merr\x1b[0m     |");
+	assert_eq!(pad(5, Align::Center, crate::fmt!({RED}"a"{RESET})).to_string(), "  \x1b[31ma\x1b[0m  ");
+
+	// Fit
+	assert_eq!(fit(5, Align::Right, crate::fmt!({RED}"ab"{RESET})).to_string(), "   \x1b[31mab\x1b[0m");
+	assert_eq!(fit(4, Align::Left, crate::fmt!({RED}"abcdef"{RESET})).to_string(), "\x1b[31mabc…\x1b[0m");
+	assert_eq!(fit(4, Align::Left, crate::fmt!({RED}"ab"{RESET}"cdef")).suffix("").to_string(), "\x1b[31mab\x1b[0mcd");
+	assert_eq!(fit(3, Align::Left, crate::fmt!({"abcd"}{LINK}{"e"})).suffix("!").to_string(), "ab!\x1b]8;;https://example.com/€\x1b\\");
+
+	// Colored content wrapped across lines
+	assert_eq!(wrap(3, crate::fmt!({RED}"abcdefg"{RESET})).to_string(), "\x1b[31mabc\x1b[0m\n\x1b[31mdef\x1b[0m\n\x1b[31mg\x1b[0m");
+	assert_eq!(wrap(2, crate::fmt!({BOLD}"a"{RED}"bc"{RESET}"de")).to_string(), "\x1b[1ma\x1b[31mb\x1b[0m\n\x1b[1;31mc\x1b[0md\ne");
+	assert_eq!(wrap(3, "ab\ncdef").to_string(), "ab\ncde\nf");
+	assert_eq!(wrap(2, crate::fmt!({"\x1b[3"}{"2mab"}{"cd"})).to_string(), "\x1b[32mab\x1b[0m\n\x1b[32mcd");
+	assert_eq!(wrap(2, crate::fmt!({RED}"ab"{"\x1b[m"}"cd")).to_string(), "\x1b[31mab\x1b[m\ncd");
+	assert_eq!(wrap(2, crate::fmt!({RED}"a"{"\x1b[0;1m"}"bc")).to_string(), "\x1b[31ma\x1b[0;1mb\x1b[0m\n\x1b[1mc");
+}
@@ -0,0 +1,121 @@
+use std::{fmt, io};
+
+/// Exposes the output of the value as a reader.
+///
+/// ```
+/// use std::io::Read;
+///
+/// let body = fmtools::fmt!("{\"id\":"{42}"}");
+/// let mut reader = fmtools::as_read(body);
+/// let mut bytes = Vec::new();
+/// reader.read_to_end(&mut bytes).unwrap();
+/// assert_eq!(bytes, b"{\"id\":42}");
+/// ```
+///
+/// The output is rendered once into a buffer on the first read, the reads are then served from the buffer.
+///
+/// An error returned by the value is reported as an `io::Error` with kind `Other`.
+#[inline]
+pub fn as_read<T: fmt::Display>(value: T) -> AsRead<T> {
+	AsRead { value, buf: None, pos: 0 }
+}
+
+/// Reader returned by [`as_read`].
+pub struct AsRead<T> {
+	value: T,
+	// The rendered output once read from
+	buf: Option<Vec<u8>>,
+	// Bytes already read from the output
+	pos: usize,
+}
+
+impl<T: fmt::Display> io::Read for AsRead<T> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		if buf.is_empty() {
+			return Ok(0);
+		}
+		let output = match &mut self.buf {
+			Some(output) => output,
+			buf => {
+				let mut s = String::new();
+				fmt::write(&mut s, format_args!("{}", self.value)).map_err(|_| io::Error::other("formatter error"))?;
+				buf.insert(s.into_bytes())
+			},
+		};
+		let bytes = &output[self.pos..];
+		let n = usize::min(bytes.len(), buf.len());
+		buf[..n].copy_from_slice(&bytes[..n]);
+		self.pos += n;
+		Ok(n)
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for AsRead<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("AsRead").field("value", &self.value).field("offset", &self.pos).finish()
+	}
+}
+
+#[test]
+fn tests() {
+	use std::io::Read;
+
+	fn read_all<R: Read>(mut reader: R, step: usize) -> io::Result<Vec<u8>> {
+		let mut data = Vec::new();
+		let mut buf = [0u8; 7];
+		loop {
+			// Zero-length reads do not advance the reader
+			assert_eq!(reader.read(&mut buf[..0])?, 0);
+			let n = reader.read(&mut buf[..step])?;
+			if n == 0 {
+				return Ok(data);
+			}
+			assert!(n <= step);
+			data.extend_from_slice(&buf[..n]);
+		}
+	}
+
+	// Small output through a small buffer
+	let small = crate::fmt!("id="{42}", name="{"héllo"});
+	assert_eq!(read_all(as_read(small), 7).unwrap(), small.to_string().as_bytes());
+	assert_eq!(read_all(as_read(small), 1).unwrap(), small.to_string().as_bytes());
+
+	// Output spanning several chunks
+	let large = crate::fmt!(for i in 0..2000 { {i}"," });
+	assert_eq!(read_all(as_read(large), 7).unwrap(), large.to_string().as_bytes());
+
+	// Large output read at once
+	let exact = "x".repeat(8192);
+	assert_eq!(read_all(as_read(&exact), 7).unwrap(), exact.as_bytes());
+	let mut bytes = Vec::new();
+	as_read(&exact).read_to_end(&mut bytes).unwrap();
+	assert_eq!(bytes, exact.as_bytes());
+
+	// Empty output
+	let mut reader = as_read("");
+	assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+	assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+
+	// Reads after the end
+	let mut reader = as_read("ab");
+	assert_eq!(reader.read(&mut [0; 4]).unwrap(), 2);
+	assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+	assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+
+	// Rendered once even when the output changes between displays
+	let renders = std::cell::Cell::new(0);
+	let changing = crate::fmt(|f| {
+		renders.set(renders.get() + 1);
+		for _ in 0..5000 {
+			write!(f, "{}", renders.get())?;
+		}
+		Ok(())
+	});
+	assert_eq!(read_all(as_read(changing), 7).unwrap(), "1".repeat(5000).as_bytes());
+	assert_eq!(renders.get(), 1);
+
+	// Formatter errors
+	let fails = crate::fmt(|_| Err(fmt::Error));
+	let err = as_read(fails).read(&mut [0; 4]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::Other);
+}
@@ -0,0 +1,142 @@
+use std::{fmt, future, io};
+use std::pin::Pin;
+use futures_io::AsyncWrite;
+use crate::buf::Window;
+
+const CHUNK: usize = 4096;
+
+/// Renders the value into an async writer.
+///
+/// Requires the `std` and `futures-io` features.
+///
+/// ```
+/// # async fn example(mut socket: impl futures_io::AsyncWrite + Unpin) -> std::io::Result<()> {
+/// let id = 42;
+/// fmtools::write_async(&mut socket, &fmtools::fmt!("id="{id}"\n")).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The output is rendered in chunks of 4 KiB into a buffer allocated once, every chunk is written before the next one is rendered.
+/// The value is rendered again for every chunk, skipping the output written before.
+/// Large outputs are never held in memory at once in exchange for rendering the value once per chunk.
+/// The value must render the same output every time it is displayed.
+///
+/// The writer is not flushed.
+/// An error returned by the value is reported as an `io::Error` with kind `Other`.
+pub async fn write_async<W, T>(w: &mut W, value: &T) -> io::Result<()>
+	where W: AsyncWrite + Unpin + ?Sized,
+		T: fmt::Display + ?Sized
+{
+	let mut buf = vec![0; CHUNK].into_boxed_slice();
+	let mut skip = 0;
+	loop {
+		let window = Window::render(buf, value, skip).ok_or_else(|| io::Error::other("formatter error"))?;
+		write_all(w, window.as_bytes()).await?;
+		if !window.is_full() {
+			return Ok(());
+		}
+		buf = window.into_buf();
+		skip += CHUNK;
+	}
+}
+
+async fn write_all<W: AsyncWrite + Unpin + ?Sized>(w: &mut W, mut buf: &[u8]) -> io::Result<()> {
+	while !buf.is_empty() {
+		let n = future::poll_fn(|cx| Pin::new(&mut *w).poll_write(cx, buf)).await?;
+		if n == 0 {
+			return Err(io::ErrorKind::WriteZero.into());
+		}
+		buf = &buf[n..];
+	}
+	Ok(())
+}
+
+#[test]
+fn tests() {
+	use std::task::{Context, Poll, Waker};
+
+	// Accepts a few bytes at a time and is pending every other poll
+	struct Slow {
+		data: Vec<u8>,
+		pending: bool,
+	}
+	impl AsyncWrite for Slow {
+		fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+			self.pending = !self.pending;
+			if self.pending {
+				cx.waker().wake_by_ref();
+				return Poll::Pending;
+			}
+			let n = usize::min(buf.len(), 3);
+			self.data.extend_from_slice(&buf[..n]);
+			Poll::Ready(Ok(n))
+		}
+		fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+			Poll::Ready(Ok(()))
+		}
+		fn poll_close(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+			Poll::Ready(Ok(()))
+		}
+	}
+
+	fn block_on<F: future::Future>(future: F) -> F::Output {
+		let mut future = std::pin::pin!(future);
+		let mut cx = Context::from_waker(Waker::noop());
+		loop {
+			if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+				return output;
+			}
+		}
+	}
+
+	// Small output
+	let mut w = Slow { data: Vec::new(), pending: false };
+	block_on(write_async(&mut w, &crate::fmt!("id="{42}))).unwrap();
+	assert_eq!(w.data, b"id=42");
+
+	// Output spanning several chunks
+	let large = crate::fmt!(for i in 0..2000 { {i}"," });
+	let mut w = Slow { data: Vec::new(), pending: false };
+	block_on(write_async(&mut w, &large)).unwrap();
+	assert_eq!(w.data, large.to_string().as_bytes());
+
+	// Output exactly filling and just overflowing a chunk
+	for len in [CHUNK, CHUNK + 1, CHUNK * 2] {
+		let exact = crate::fmt!({"x".repeat(len - 1)}{'y'});
+		let mut w = Slow { data: Vec::new(), pending: false };
+		block_on(write_async(&mut w, &exact)).unwrap();
+		assert_eq!(w.data, exact.to_string().as_bytes());
+	}
+
+	// Rendered once per chunk with the chunks written in between
+	let renders = std::cell::Cell::new(0);
+	let counted = crate::fmt(|f| {
+		renders.set(renders.get() + 1);
+		for _ in 0..CHUNK * 2 + 1 {
+			f.write_str("z")?;
+		}
+		Ok(())
+	});
+	let mut w = Slow { data: Vec::new(), pending: false };
+	block_on(write_async(&mut w, &counted)).unwrap();
+	assert_eq!(w.data, "z".repeat(CHUNK * 2 + 1).as_bytes());
+	assert_eq!(renders.get(), 3);
+
+	// The future does not hold the chunk
+	let mut w = Slow { data: Vec::new(), pending: false };
+	assert!(std::mem::size_of_val(&write_async(&mut w, &counted)) < 256);
+
+	// Formatter errors, also after the first chunk
+	let fails = crate::fmt(|f| { f.write_str(&"x".repeat(CHUNK + 1))?; Err(fmt::Error) });
+	let mut w = Slow { data: Vec::new(), pending: false };
+	let err = block_on(write_async(&mut w, &fails)).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::Other);
+	assert_eq!(w.data.len(), CHUNK);
+
+	// Formatter errors
+	let fails = crate::fmt(|_| Err(fmt::Error));
+	let mut w = Slow { data: Vec::new(), pending: false };
+	let err = block_on(write_async(&mut w, &fails)).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::Other);
+}
@@ -0,0 +1,145 @@
+use core::{fmt, str};
+
+/// Renders the value into the buffer without allocation.
+///
+/// Returns the rendered string on success.
+///
+/// ```
+/// let mut buf = [0u8; 16];
+/// let id = 42;
+/// let s = fmtools::write_buf(&mut buf, fmtools::fmt!("id="{id})).unwrap();
+/// assert_eq!(s, "id=42");
+/// ```
+///
+/// When the buffer is too small the output is truncated at a char boundary, the error reports how many bytes were written.
+/// Rendering is aborted as soon as the buffer is full.
+///
+/// ```
+/// let mut buf = [0u8; 4];
+/// let err = fmtools::write_buf(&mut buf, "hé€").unwrap_err();
+/// assert_eq!(err.written(), 3);
+/// assert_eq!(&buf[..err.written()], "hé".as_bytes());
+/// ```
+///
+/// An error returned by the value itself is also reported as truncation at the number of bytes written so far.
+pub fn write_buf<T: fmt::Display>(buf: &mut [u8], value: T) -> Result<&str, Truncated> {
+	let mut writer = BufWriter { buf, len: 0 };
+	let result = fmt::write(&mut writer, format_args!("{}", value));
+	let len = writer.len;
+	if result.is_err() {
+		return Err(Truncated { written: len });
+	}
+	// Only complete chars are ever written to the buffer
+	Ok(str::from_utf8(&buf[..len]).unwrap_or_default())
+}
+
+/// Error returned by [`write_buf`] when the buffer is too small.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Truncated {
+	written: usize,
+}
+impl Truncated {
+	/// Returns the number of bytes written.
+	///
+	/// This is always at a valid UTF-8 boundary.
+	#[inline]
+	pub fn written(&self) -> usize {
+		self.written
+	}
+}
+impl fmt::Display for Truncated {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "output truncated after {} bytes", self.written)
+	}
+}
+#[cfg(feature = "std")]
+impl std::error::Error for Truncated {}
+
+struct BufWriter<'a> {
+	buf: &'a mut [u8],
+	len: usize,
+}
+impl fmt::Write for BufWriter<'_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let n = copy_truncated(&mut self.buf[self.len..], s);
+		self.len += n;
+		if n < s.len() { Err(fmt::Error) } else { Ok(()) }
+	}
+}
+
+/// Copies as much of the string as fits without splitting a char.
+///
+/// Returns the number of bytes copied.
+pub(crate) fn copy_truncated(dst: &mut [u8], s: &str) -> usize {
+	let mut n = usize::min(s.len(), dst.len());
+	while !s.is_char_boundary(n) {
+		n -= 1;
+	}
+	dst[..n].copy_from_slice(&s.as_bytes()[..n]);
+	n
+}
+
+// Captures the output bytes in the range `skip..skip + buf.len()`.
+pub(crate) struct Window<B> {
+	buf: B,
+	skip: usize,
+	len: usize,
+}
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Window<B> {
+	/// Renders the window into the buffer, returns `None` if the value returns an error.
+	pub(crate) fn render<T: fmt::Display + ?Sized>(buf: B, value: &T, skip: usize) -> Option<Window<B>> {
+		let mut window = Window { buf, skip, len: 0 };
+		let result = fmt::write(&mut window, format_args!("{}", value));
+		// Rendering is aborted when the window is full
+		if result.is_err() && !window.is_full() {
+			return None;
+		}
+		Some(window)
+	}
+	pub(crate) fn len(&self) -> usize {
+		self.len
+	}
+	pub(crate) fn is_full(&self) -> bool {
+		self.len == self.buf.as_ref().len()
+	}
+	pub(crate) fn as_bytes(&self) -> &[u8] {
+		&self.buf.as_ref()[..self.len]
+	}
+	#[cfg(all(feature = "std", feature = "futures-io"))]
+	pub(crate) fn into_buf(self) -> B {
+		self.buf
+	}
+}
+impl<B: AsRef<[u8]> + AsMut<[u8]>> fmt::Write for Window<B> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let mut bytes = s.as_bytes();
+		let skip = usize::min(self.skip, bytes.len());
+		self.skip -= skip;
+		bytes = &bytes[skip..];
+		let buf = &mut self.buf.as_mut()[self.len..];
+		let n = usize::min(bytes.len(), buf.len());
+		buf[..n].copy_from_slice(&bytes[..n]);
+		self.len += n;
+		if self.is_full() { Err(fmt::Error) } else { Ok(()) }
+	}
+}
+
+#[test]
+fn tests() {
+	// Exact fit
+	let mut buf = [0u8; 5];
+	assert_eq!(write_buf(&mut buf, crate::fmt!("ab"{123})), Ok("ab123"));
+
+	// Truncation in the middle of a multi-byte char
+	let mut buf = [0u8; 5];
+	assert_eq!(write_buf(&mut buf, "ab€"), Ok("ab€"));
+	assert_eq!(write_buf(&mut buf, "abc€"), Err(Truncated { written: 3 }));
+	assert_eq!(write_buf(&mut buf, "ab😀"), Err(Truncated { written: 2 }));
+	assert_eq!(&buf[..2], b"ab");
+
+	// Empty buffer
+	let mut buf = [0u8; 0];
+	assert_eq!(write_buf(&mut buf, ""), Ok(""));
+	assert_eq!(write_buf(&mut buf, "a"), Err(Truncated { written: 0 }));
+	assert_eq!(Truncated { written: 0 }.to_string(), "output truncated after 0 bytes");
+}
@@ -0,0 +1,122 @@
+use std::borrow::Cow;
+use std::fmt;
+
+/// Renders the value to a scratch string and passes the complete text to the closure to transform and write.
+///
+/// For layouts which need the whole rendered text before deciding on the output.
+///
+/// ```
+/// let upper_if_short = |s: &str, f: &mut std::fmt::Formatter| {
+/// 	if s.len() <= 8 {
+/// 		f.write_str(&s.to_uppercase())
+/// 	}
+/// 	else {
+/// 		f.write_str(s)
+/// 	}
+/// };
+///
+/// let s = fmtools::buffered(fmtools::fmt!("short"), upper_if_short).to_string();
+/// assert_eq!(s, "SHORT");
+/// let s = fmtools::buffered(fmtools::fmt!("longer "{"text"}), upper_if_short).to_string();
+/// assert_eq!(s, "longer text");
+/// ```
+///
+/// The value is rendered without any formatting options, the closure receives the formatter and decides which options to respect.
+///
+/// A new string is allocated on every render.
+/// Prefer the streaming adapters when the transformation does not need to see the whole text.
+#[inline]
+pub fn buffered<T: fmt::Display, F: Fn(&str, &mut fmt::Formatter) -> fmt::Result>(value: T, post: F) -> Buffered<T, F> {
+	Buffered { value, post }
+}
+
+/// Displayable object returned by [`buffered`].
+#[derive(Copy, Clone)]
+pub struct Buffered<T, F> {
+	value: T,
+	post: F,
+}
+impl<T: fmt::Display, F: Fn(&str, &mut fmt::Formatter) -> fmt::Result> fmt::Display for Buffered<T, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut s = String::new();
+		fmt::write(&mut s, format_args!("{}", self.value))?;
+		(self.post)(&s, f)
+	}
+}
+debug_as_display!([T: fmt::Display, F: Fn(&str, &mut fmt::Formatter) -> fmt::Result] Buffered<T, F>);
+
+/// Renders the value to a scratch string and displays the text returned by the closure.
+///
+/// ```
+/// let sentence = fmtools::fmt!("GP"{"GGA"});
+/// let s = fmtools::map_output(sentence, |s| {
+/// 	let sum = s.bytes().fold(0u8, u8::wrapping_add);
+/// 	format!("{}*{:02X}", s, sum).into()
+/// }).to_string();
+/// assert_eq!(s, "GPGGA*66");
+/// ```
+///
+/// The value is rendered without any formatting options.
+/// The returned text is formatted as a `str`, respecting width, fill, alignment and precision.
+///
+/// Return [`Cow::Borrowed`] to pass the text through unchanged without another allocation.
+#[inline]
+pub fn map_output<T: fmt::Display, F: Fn(&str) -> Cow<'_, str>>(value: T, f: F) -> MapOutput<T, F> {
+	MapOutput { value, f }
+}
+
+/// Displayable object returned by [`map_output`].
+#[derive(Copy, Clone)]
+pub struct MapOutput<T, F> {
+	value: T,
+	f: F,
+}
+impl<T: fmt::Display, F: Fn(&str) -> Cow<'_, str>> fmt::Display for MapOutput<T, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut s = String::new();
+		fmt::write(&mut s, format_args!("{}", self.value))?;
+		f.pad(&(self.f)(&s))
+	}
+}
+debug_as_display!([T: fmt::Display, F: Fn(&str) -> Cow<'_, str>] MapOutput<T, F>);
+
+#[test]
+fn tests() {
+	// Align the `=` signs across lines
+	fn align_eq(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
+		let column = s.lines().filter_map(|line| line.find('=')).max().unwrap_or(0);
+		for (i, line) in s.lines().enumerate() {
+			if i > 0 {
+				f.write_str("\n")?;
+			}
+			match line.split_once('=') {
+				Some((key, value)) => write!(f, "{:<2$}={}", key, value, column)?,
+				None => f.write_str(line)?,
+			}
+		}
+		Ok(())
+	}
+	let settings = [("width", 80), ("tab_size", 4), ("x", 0)];
+	let template = crate::fmt!(
+		for &(key, value) in &settings {
+			{key}" = "{value}"\n"
+		}
+		"# end"
+	);
+	assert_eq!(buffered(template, align_eq).to_string(), "width    = 80\ntab_size = 4\nx        = 0\n# end");
+	assert_eq!(buffered("", align_eq).to_string(), "");
+
+	// Formatting options are left to the closure
+	let s = format!("{:>8}", buffered("abc", |s, f| f.pad(s)));
+	assert_eq!(s, "     abc");
+
+	// No-op pass-through
+	let text = crate::fmt!({"chunked "}{"output "}{42});
+	assert_eq!(map_output(text, |s| Cow::Borrowed(s)).to_string(), "chunked output 42");
+	assert_eq!(format!("[{:^6.3}]", map_output("abcd", |s| Cow::Borrowed(s))), "[ abc  ]");
+	assert_eq!(map_output(text, |s| Cow::Owned(s.replace(' ', "_"))).to_string(), "chunked_output_42");
+
+	// Errors from the value are propagated
+	let failing = crate::fmt(|_| Err(fmt::Error));
+	assert!(fmt::write(&mut String::new(), format_args!("{}", map_output(failing, |s| Cow::Borrowed(s)))).is_err());
+}
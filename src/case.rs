@@ -0,0 +1,349 @@
+use core::fmt::{self, Write};
+
+/// Displayable object which converts the output to uppercase.
+///
+/// Returned by [`DisplayExt::uppercased`](crate::DisplayExt::uppercased).
+#[derive(Copy, Clone)]
+pub struct Uppercase<T> {
+	value: T,
+}
+impl<T: fmt::Display> Uppercase<T> {
+	#[inline]
+	pub(crate) fn new(value: T) -> Uppercase<T> {
+		Uppercase { value }
+	}
+}
+impl<T: fmt::Display> fmt::Display for Uppercase<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		map_chars(f, &self.value, char::to_uppercase)
+	}
+}
+debug_as_display!([T: fmt::Display] Uppercase<T>);
+
+/// Uppercases the first letter of the output.
+///
+/// ```
+/// let level = "warning";
+/// let s = fmtools::format!({fmtools::capitalize(level)}": disk almost full");
+/// assert_eq!(s, "Warning: disk almost full");
+/// ```
+///
+/// Leading punctuation is skipped, the first letter or digit ends the search: `"'quoted'"` becomes `"'Quoted'"` while `"3d"` is unchanged.
+/// The rest of the output is unchanged.
+/// Uppercasing is Unicode-aware and may expand into multiple chars, `"ß"` becomes `"SS"`.
+#[inline]
+pub fn capitalize<T: fmt::Display>(value: T) -> Capitalize<T> {
+	Capitalize { value, title: false }
+}
+
+/// Uppercases the first letter of every word and lowercases the rest.
+///
+/// ```
+/// let s = fmtools::title_case("the QUICK brown-fox").to_string();
+/// assert_eq!(s, "The Quick Brown-fox");
+/// ```
+///
+/// Words are separated by whitespace, the first letter of every word follows the rules of [`capitalize`].
+#[inline]
+pub fn title_case<T: fmt::Display>(value: T) -> Capitalize<T> {
+	Capitalize { value, title: true }
+}
+
+/// Displayable object returned by [`capitalize`] and [`title_case`].
+#[derive(Copy, Clone)]
+pub struct Capitalize<T> {
+	value: T,
+	title: bool,
+}
+impl<T: fmt::Display> fmt::Display for Capitalize<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut writer = CapitalizeWriter { f, title: self.title, word_start: true, done: false };
+		fmt::write(&mut writer, format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display] Capitalize<T>);
+
+struct CapitalizeWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	title: bool,
+	// Looking for the first letter of the word
+	word_start: bool,
+	// Capitalized the first letter, the rest is passed through
+	done: bool,
+}
+impl fmt::Write for CapitalizeWriter<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if self.done {
+			return self.f.write_str(s);
+		}
+		for (i, chr) in s.char_indices() {
+			if self.title && chr.is_whitespace() {
+				self.word_start = true;
+				self.f.write_char(chr)?;
+			}
+			else if self.word_start && chr.is_alphanumeric() {
+				self.word_start = false;
+				for chr in chr.to_uppercase() {
+					self.f.write_char(chr)?;
+				}
+				if !self.title {
+					self.done = true;
+					return self.f.write_str(&s[i + chr.len_utf8()..]);
+				}
+			}
+			else if self.title && !self.word_start {
+				for chr in chr.to_lowercase() {
+					self.f.write_char(chr)?;
+				}
+			}
+			else {
+				self.f.write_char(chr)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Converts an identifier in the output to `snake_case`.
+///
+/// ```
+/// let s = fmtools::snake_case("parseHTTPResponse").to_string();
+/// assert_eq!(s, "parse_http_response");
+/// ```
+///
+/// The output is split into words with the following rules:
+///
+/// * Chars which are not alphanumeric, like `_`, `-` and whitespace, separate words and are dropped.
+///   Consecutive separators count as a single separator, leading and trailing separators are removed.
+/// * An uppercase letter following a lowercase letter or a digit starts a new word: `parseHttp` is `parse` `Http`.
+/// * A run of uppercase letters is an acronym, its last letter starts a new word if followed by a lowercase letter: `HTTPServer` is `HTTP` `Server`.
+/// * Digits belong to the word before them: `utf8Decoder` is `utf8` `Decoder`.
+///
+/// The words are then written in the target convention.
+#[inline]
+pub fn snake_case<T: fmt::Display>(value: T) -> Convention<T> {
+	Convention { value, style: Style::Snake }
+}
+
+/// Converts an identifier in the output to `kebab-case`.
+///
+/// ```
+/// let s = fmtools::kebab_case("XMLHttpRequest").to_string();
+/// assert_eq!(s, "xml-http-request");
+/// ```
+///
+/// See [`snake_case`] for the rules to split the words.
+#[inline]
+pub fn kebab_case<T: fmt::Display>(value: T) -> Convention<T> {
+	Convention { value, style: Style::Kebab }
+}
+
+/// Converts an identifier in the output to `camelCase`.
+///
+/// ```
+/// let s = fmtools::camel_case("user_id-HTTP server").to_string();
+/// assert_eq!(s, "userIdHttpServer");
+/// ```
+///
+/// See [`snake_case`] for the rules to split the words.
+#[inline]
+pub fn camel_case<T: fmt::Display>(value: T) -> Convention<T> {
+	Convention { value, style: Style::Camel }
+}
+
+/// Converts an identifier in the output to `PascalCase`.
+///
+/// ```
+/// let s = fmtools::pascal_case("parse_http_response").to_string();
+/// assert_eq!(s, "ParseHttpResponse");
+/// ```
+///
+/// See [`snake_case`] for the rules to split the words.
+#[inline]
+pub fn pascal_case<T: fmt::Display>(value: T) -> Convention<T> {
+	Convention { value, style: Style::Pascal }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Style {
+	Snake,
+	Kebab,
+	Camel,
+	Pascal,
+}
+
+/// Displayable object returned by [`snake_case`], [`kebab_case`], [`camel_case`] and [`pascal_case`].
+#[derive(Copy, Clone)]
+pub struct Convention<T> {
+	value: T,
+	style: Style,
+}
+impl<T: fmt::Display> fmt::Display for Convention<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut writer = ConventionWriter { f, style: self.style, words: 0, prev: Class::Sep, pending: None };
+		fmt::write(&mut writer, format_args!("{}", self.value))?;
+		writer.flush()
+	}
+}
+debug_as_display!([T: fmt::Display] Convention<T>);
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Class {
+	Sep,
+	Upper,
+	Lower,
+	Digit,
+}
+impl Class {
+	fn of(chr: char) -> Class {
+		if chr.is_uppercase() { Class::Upper }
+		else if chr.is_numeric() { Class::Digit }
+		// Letters without case are treated as lowercase
+		else if chr.is_alphanumeric() { Class::Lower }
+		else { Class::Sep }
+	}
+}
+
+struct ConventionWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	style: Style,
+	// Number of words started
+	words: usize,
+	prev: Class,
+	// Uppercase letter following an uppercase letter, held back until it is known whether it starts a new word
+	pending: Option<char>,
+}
+impl ConventionWriter<'_, '_> {
+	fn start_word(&mut self, chr: char) -> fmt::Result {
+		let upper = match self.style {
+			Style::Snake => { if self.words > 0 { self.f.write_char('_')?; } false },
+			Style::Kebab => { if self.words > 0 { self.f.write_char('-')?; } false },
+			Style::Camel => self.words > 0,
+			Style::Pascal => true,
+		};
+		self.words += 1;
+		if upper { self.write_upper(chr) } else { self.write_lower(chr) }
+	}
+	fn write_upper(&mut self, chr: char) -> fmt::Result {
+		for chr in chr.to_uppercase() {
+			self.f.write_char(chr)?;
+		}
+		Ok(())
+	}
+	fn write_lower(&mut self, chr: char) -> fmt::Result {
+		for chr in chr.to_lowercase() {
+			self.f.write_char(chr)?;
+		}
+		Ok(())
+	}
+	fn flush(&mut self) -> fmt::Result {
+		match self.pending.take() {
+			Some(chr) => self.write_lower(chr),
+			None => Ok(()),
+		}
+	}
+}
+impl fmt::Write for ConventionWriter<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for chr in s.chars() {
+			let class = Class::of(chr);
+			if let Some(pending) = self.pending.take() {
+				// The last letter of an acronym starts a new word if followed by a lowercase letter
+				if class == Class::Lower {
+					self.start_word(pending)?;
+				}
+				else {
+					self.write_lower(pending)?;
+				}
+			}
+			match (self.prev, class) {
+				(_, Class::Sep) => (),
+				(Class::Sep, _) => self.start_word(chr)?,
+				(Class::Lower | Class::Digit, Class::Upper) => self.start_word(chr)?,
+				(Class::Upper, Class::Upper) => self.pending = Some(chr),
+				_ => self.write_lower(chr)?,
+			}
+			self.prev = class;
+		}
+		Ok(())
+	}
+}
+
+/// Streams the value's output through a char mapping.
+fn map_chars<T: fmt::Display + ?Sized, I: Iterator<Item = char>>(f: &mut fmt::Formatter, value: &T, map: fn(char) -> I) -> fmt::Result {
+	struct MapChars<'a, 'f, I> {
+		f: &'a mut fmt::Formatter<'f>,
+		map: fn(char) -> I,
+	}
+	impl<I: Iterator<Item = char>> fmt::Write for MapChars<'_, '_, I> {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			for chr in s.chars() {
+				for chr in (self.map)(chr) {
+					self.f.write_char(chr)?;
+				}
+			}
+			Ok(())
+		}
+	}
+	fmt::write(&mut MapChars { f, map }, format_args!("{}", value))
+}
+
+#[test]
+fn tests() {
+	assert_eq!(Uppercase::new("Hello, wörld ß").to_string(), "HELLO, WÖRLD SS");
+	assert_eq!(Uppercase::new(crate::fmt!("a"{1}"b")).to_string(), "A1B");
+
+	// Capitalize
+	assert_eq!(capitalize("hello world").to_string(), "Hello world");
+	assert_eq!(capitalize("Hello World").to_string(), "Hello World");
+	assert_eq!(capitalize("hELLO").to_string(), "HELLO");
+	assert_eq!(capitalize("'quoted' text").to_string(), "'Quoted' text");
+	assert_eq!(capitalize("3d model").to_string(), "3d model");
+	assert_eq!(capitalize("ßtraße").to_string(), "SStraße");
+	assert_eq!(capitalize("ǆ").to_string(), "Ǆ");
+	assert_eq!(capitalize("日本語 text").to_string(), "日本語 text");
+	assert_eq!(capitalize(crate::fmt!({""}{" "}{"("}{"éa"}{"b"})).to_string(), " (Éab");
+	assert_eq!(capitalize("").to_string(), "");
+
+	// Title case
+	assert_eq!(title_case("hello world").to_string(), "Hello World");
+	assert_eq!(title_case("HELLO  WORLD\tAGAIN").to_string(), "Hello  World\tAgain");
+	assert_eq!(title_case("Already Capitalized").to_string(), "Already Capitalized");
+	assert_eq!(title_case("(nested) 2nd place").to_string(), "(Nested) 2nd Place");
+	assert_eq!(title_case("über ΣΟΦΙΑ").to_string(), "Über Σοφια");
+	assert_eq!(title_case("漢字 かな").to_string(), "漢字 かな");
+	assert_eq!(title_case(crate::fmt!({"he"}{"LLO w"}{"ORLD"})).to_string(), "Hello World");
+
+	// Case conventions
+	const CONVENTIONS: &[(&str, &str, &str, &str, &str)] = &[
+		("", "", "", "", ""),
+		("parseHTTPResponse", "parse_http_response", "parse-http-response", "parseHttpResponse", "ParseHttpResponse"),
+		("HTTPServer", "http_server", "http-server", "httpServer", "HttpServer"),
+		("XMLHttpRequest", "xml_http_request", "xml-http-request", "xmlHttpRequest", "XmlHttpRequest"),
+		("getURL", "get_url", "get-url", "getUrl", "GetUrl"),
+		("ID", "id", "id", "id", "Id"),
+		("snake_case_value", "snake_case_value", "snake-case-value", "snakeCaseValue", "SnakeCaseValue"),
+		("SCREAMING_SNAKE", "screaming_snake", "screaming-snake", "screamingSnake", "ScreamingSnake"),
+		("kebab-case", "kebab_case", "kebab-case", "kebabCase", "KebabCase"),
+		("Title Case Words", "title_case_words", "title-case-words", "titleCaseWords", "TitleCaseWords"),
+		("__foo--bar  baz__", "foo_bar_baz", "foo-bar-baz", "fooBarBaz", "FooBarBaz"),
+		("utf8Decoder", "utf8_decoder", "utf8-decoder", "utf8Decoder", "Utf8Decoder"),
+		("HTTP2Server", "http2_server", "http2-server", "http2Server", "Http2Server"),
+		("version 2", "version_2", "version-2", "version2", "Version2"),
+		("123abc", "123abc", "123abc", "123abc", "123abc"),
+		("a.b/c", "a_b_c", "a-b-c", "aBC", "ABC"),
+		("ÜberGröße", "über_größe", "über-größe", "überGröße", "ÜberGröße"),
+	];
+	for &(input, snake, kebab, camel, pascal) in CONVENTIONS {
+		assert_eq!(snake_case(input).to_string(), snake, "{:?}", input);
+		assert_eq!(kebab_case(input).to_string(), kebab, "{:?}", input);
+		assert_eq!(camel_case(input).to_string(), camel, "{:?}", input);
+		assert_eq!(pascal_case(input).to_string(), pascal, "{:?}", input);
+	}
+
+	// Boundaries across chunked writes
+	assert_eq!(snake_case(crate::fmt!({"parseHT"}{"TPR"}{"esponse"})).to_string(), "parse_http_response");
+	assert_eq!(snake_case(crate::fmt!({"HTTP"}{"Server"})).to_string(), "http_server");
+	assert_eq!(snake_case(crate::fmt!({"HTTP"}{"_"}{"_server"})).to_string(), "http_server");
+	assert_eq!(pascal_case(crate::fmt!({"get"}{"U"}{"RL"})).to_string(), "GetUrl");
+}
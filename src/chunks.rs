@@ -0,0 +1,139 @@
+use core::{fmt, str};
+use core::ops::ControlFlow;
+
+/// Invokes the callback for every string written by the value.
+///
+/// ```
+/// use std::ops::ControlFlow;
+///
+/// let mut chunks = Vec::new();
+/// fmtools::for_each_chunk(&fmtools::fmt!("a = "{42}), |chunk| {
+/// 	chunks.push(chunk.to_string());
+/// 	ControlFlow::Continue(())
+/// }).unwrap();
+/// assert_eq!(chunks, ["a = ", "42"]);
+/// ```
+///
+/// Returning `ControlFlow::Break` stops rendering the value, this is not an error.
+pub fn for_each_chunk<T, F>(value: &T, f: F) -> fmt::Result
+	where T: fmt::Display + ?Sized,
+		F: FnMut(&str) -> ControlFlow<()>
+{
+	let mut writer = ChunkWriter { f, done: false };
+	match fmt::write(&mut writer, format_args!("{}", value)) {
+		Err(_) if writer.done => Ok(()),
+		result => result,
+	}
+}
+
+/// Invokes the callback for chunks of at most `N` bytes written by the value.
+///
+/// Like [`for_each_chunk`] but tiny writes are coalesced in a buffer on the stack.
+/// Writes which do not fit in the buffer are passed to the callback directly and may be larger than `N` bytes.
+///
+/// ```
+/// use std::ops::ControlFlow;
+///
+/// let mut chunks = Vec::new();
+/// fmtools::for_each_chunk_buffered::<8, _, _>(&fmtools::fmt!("a = "{42}" b = "{13}), |chunk| {
+/// 	chunks.push(chunk.to_string());
+/// 	ControlFlow::Continue(())
+/// }).unwrap();
+/// assert_eq!(chunks, ["a = 42", " b = 13"]);
+/// ```
+pub fn for_each_chunk_buffered<const N: usize, T, F>(value: &T, f: F) -> fmt::Result
+	where T: fmt::Display + ?Sized,
+		F: FnMut(&str) -> ControlFlow<()>
+{
+	let mut writer = BufferedWriter { inner: ChunkWriter { f, done: false }, buf: [0; N], len: 0 };
+	let result = fmt::write(&mut writer, format_args!("{}", value)).and_then(|_| writer.flush());
+	match result {
+		Err(_) if writer.inner.done => Ok(()),
+		result => result,
+	}
+}
+
+struct ChunkWriter<F> {
+	f: F,
+	done: bool,
+}
+impl<F: FnMut(&str) -> ControlFlow<()>> fmt::Write for ChunkWriter<F> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if s.is_empty() {
+			return Ok(());
+		}
+		match (self.f)(s) {
+			ControlFlow::Continue(()) => Ok(()),
+			ControlFlow::Break(()) => {
+				self.done = true;
+				Err(fmt::Error)
+			},
+		}
+	}
+}
+
+struct BufferedWriter<F, const N: usize> {
+	inner: ChunkWriter<F>,
+	buf: [u8; N],
+	len: usize,
+}
+impl<F: FnMut(&str) -> ControlFlow<()>, const N: usize> BufferedWriter<F, N> {
+	fn flush(&mut self) -> fmt::Result {
+		let len = self.len;
+		self.len = 0;
+		// Only complete strings are ever written to the buffer
+		let s = str::from_utf8(&self.buf[..len]).unwrap_or_default();
+		fmt::Write::write_str(&mut self.inner, s)
+	}
+}
+impl<F: FnMut(&str) -> ControlFlow<()>, const N: usize> fmt::Write for BufferedWriter<F, N> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if self.len + s.len() > N {
+			self.flush()?;
+			if s.len() > N {
+				return self.inner.write_str(s);
+			}
+		}
+		self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+		self.len += s.len();
+		Ok(())
+	}
+}
+
+#[test]
+fn tests() {
+	fn collect(value: &dyn fmt::Display, limit: usize) -> (fmt::Result, Vec<String>) {
+		let mut chunks = Vec::new();
+		let result = for_each_chunk(value, |chunk| {
+			chunks.push(chunk.to_string());
+			if chunks.len() >= limit { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+		});
+		(result, chunks)
+	}
+	fn collect_buffered(value: &dyn fmt::Display) -> Vec<String> {
+		let mut chunks = Vec::new();
+		for_each_chunk_buffered::<4, _, _>(value, |chunk| {
+			chunks.push(chunk.to_string());
+			ControlFlow::Continue(())
+		}).unwrap();
+		chunks
+	}
+
+	let value = crate::fmt!("a"{1}"bcdef"{2});
+	assert_eq!(collect(&value, 100), (Ok(()), vec!["a".into(), "1".into(), "bcdef".into(), "2".into()]));
+
+	// Early break
+	assert_eq!(collect(&value, 2), (Ok(()), vec!["a".into(), "1".into()]));
+
+	// Empty output
+	assert_eq!(collect(&"", 100), (Ok(()), vec![]));
+	assert_eq!(collect_buffered(&""), Vec::<String>::new());
+
+	// Coalescing
+	assert_eq!(collect_buffered(&value), ["a1", "bcdef", "2"]);
+	assert_eq!(collect_buffered(&crate::fmt!("ab"{"cd"}{"e"})), ["abcd", "e"]);
+
+	// Errors are passed through
+	let fails = crate::fmt(|f| { f.write_str("abc")?; Err(fmt::Error) });
+	assert_eq!(collect(&fails, 100), (Err(fmt::Error), vec!["abc".into()]));
+}
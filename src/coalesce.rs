@@ -0,0 +1,153 @@
+use core::fmt;
+
+/// Displays the first value which produces non-empty output.
+///
+/// ```
+/// let (config, env, default) = ("", "from env", "default");
+/// let s = fmtools::format!("value: "{fmtools::coalesce((config, env, default))});
+/// assert_eq!(s, "value: from env");
+/// ```
+///
+/// The values are displayable objects (not `Option`s) which may legitimately render nothing, eg. empty strings or empty joins.
+/// Each value is rendered once to find out if it is empty, aborting at its first output, and rendered again to write it.
+/// The values after the first non-empty one are not rendered.
+///
+/// Accepts tuples of up to 8 displayable objects, and arrays or slices of displayable objects such as `&dyn Display`.
+/// If all values are empty nothing is written, see [`coalesce_or`] to write a fallback instead.
+#[inline]
+pub fn coalesce<T: Coalesce>(values: T) -> CoalesceDisplay<T, &'static str> {
+	CoalesceDisplay { values, fallback: "" }
+}
+
+/// Displays the first value which produces non-empty output or the fallback.
+///
+/// ```
+/// let values: [&dyn std::fmt::Display; 2] = [&"", &fmtools::join(", ", &[] as &[i32])];
+/// let s = fmtools::coalesce_or(values, "<unset>").to_string();
+/// assert_eq!(s, "<unset>");
+/// ```
+#[inline]
+pub fn coalesce_or<T: Coalesce, U: fmt::Display>(values: T, fallback: U) -> CoalesceDisplay<T, U> {
+	CoalesceDisplay { values, fallback }
+}
+
+/// Collections of displayable objects supported by [`coalesce`].
+pub trait Coalesce {
+	/// Writes the first value with non-empty output, returns `false` if all values are empty.
+	fn fmt_first(&self, f: &mut fmt::Formatter) -> Result<bool, fmt::Error>;
+}
+
+macro_rules! impl_coalesce_tuple {
+	($($ty:ident),+) => {
+		impl<$($ty: fmt::Display),+> Coalesce for ($($ty,)+) {
+			#[allow(non_snake_case)]
+			fn fmt_first(&self, f: &mut fmt::Formatter) -> Result<bool, fmt::Error> {
+				let ($($ty,)+) = self;
+				$(
+					if !is_empty($ty)? {
+						fmt::Display::fmt($ty, f)?;
+						return Ok(true);
+					}
+				)+
+				Ok(false)
+			}
+		}
+	};
+}
+impl_coalesce_tuple!(A);
+impl_coalesce_tuple!(A, B);
+impl_coalesce_tuple!(A, B, C);
+impl_coalesce_tuple!(A, B, C, D);
+impl_coalesce_tuple!(A, B, C, D, E);
+impl_coalesce_tuple!(A, B, C, D, E, F);
+impl_coalesce_tuple!(A, B, C, D, E, F, G);
+impl_coalesce_tuple!(A, B, C, D, E, F, G, H);
+
+impl<T: fmt::Display> Coalesce for [T] {
+	fn fmt_first(&self, f: &mut fmt::Formatter) -> Result<bool, fmt::Error> {
+		for value in self {
+			if !is_empty(value)? {
+				fmt::Display::fmt(value, f)?;
+				return Ok(true);
+			}
+		}
+		Ok(false)
+	}
+}
+impl<T: fmt::Display, const N: usize> Coalesce for [T; N] {
+	#[inline]
+	fn fmt_first(&self, f: &mut fmt::Formatter) -> Result<bool, fmt::Error> {
+		self[..].fmt_first(f)
+	}
+}
+impl<T: Coalesce + ?Sized> Coalesce for &T {
+	#[inline]
+	fn fmt_first(&self, f: &mut fmt::Formatter) -> Result<bool, fmt::Error> {
+		(**self).fmt_first(f)
+	}
+}
+
+/// Displayable object returned by [`coalesce`] and [`coalesce_or`].
+#[derive(Copy, Clone)]
+pub struct CoalesceDisplay<T, U> {
+	values: T,
+	fallback: U,
+}
+impl<T: Coalesce, U: fmt::Display> fmt::Display for CoalesceDisplay<T, U> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if !self.values.fmt_first(f)? {
+			fmt::Display::fmt(&self.fallback, f)?;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([T: Coalesce, U: fmt::Display] CoalesceDisplay<T, U>);
+
+/// Returns if the value produces no output.
+///
+/// Rendering is aborted at the first output.
+pub(crate) fn is_empty<T: fmt::Display + ?Sized>(value: &T) -> Result<bool, fmt::Error> {
+	struct Detect(bool);
+	impl fmt::Write for Detect {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			if s.is_empty() {
+				return Ok(());
+			}
+			self.0 = true;
+			Err(fmt::Error)
+		}
+	}
+	let mut detect = Detect(false);
+	match fmt::write(&mut detect, format_args!("{}", value)) {
+		Err(_) if detect.0 => Ok(false),
+		Err(err) => Err(err),
+		Ok(()) => Ok(true),
+	}
+}
+
+#[test]
+fn tests() {
+	use std::cell::Cell;
+
+	// First empty, second not
+	assert_eq!(coalesce(("", "b", "c")).to_string(), "b");
+	assert_eq!(coalesce((crate::fmt!(), 42)).to_string(), "42");
+	assert_eq!(coalesce(["a", "b"]).to_string(), "a");
+
+	// All empty
+	assert_eq!(coalesce(("", crate::fmt!(""))).to_string(), "");
+	assert_eq!(coalesce_or(("", ""), "<unset>").to_string(), "<unset>");
+	assert_eq!(coalesce_or(&[] as &[&str], "<unset>").to_string(), "<unset>");
+
+	// Later values are not rendered
+	let renders = Cell::new(0);
+	let counted = crate::fmt(|f| { renders.set(renders.get() + 1); f.write_str("x") });
+	assert_eq!(coalesce(("a", &counted)).to_string(), "a");
+	assert_eq!(renders.get(), 0);
+	assert_eq!(coalesce(("", &counted, &counted)).to_string(), "x");
+	assert_eq!(renders.get(), 2);
+
+	// Errors propagate
+	let error = crate::fmt(|_| Err(fmt::Error));
+	assert!(fmt::write(&mut String::new(), format_args!("{}", coalesce((error, "a")))).is_err());
+}
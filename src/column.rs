@@ -0,0 +1,216 @@
+use core::fmt;
+
+/// Writer adapter tracking the current column of the output.
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut w = fmtools::ColumnWriter::new(String::new());
+/// write!(w, "héllo\nwörld").unwrap();
+/// assert_eq!(w.column(), 5);
+/// w.write_str("\t|").unwrap();
+/// assert_eq!(w.column(), 9);
+/// assert_eq!(w.into_inner(), "héllo\nwörld\t|");
+/// ```
+///
+/// The column is the width of the output since the last newline, measured in chars, with the `unicode-width` feature in display columns.
+/// A carriage return resets the column as well.
+/// A tab advances the column to the next tab stop, every 8 columns by default, see [`tab_width`](ColumnWriter::tab_width).
+/// Escape sequences are measured as text unless enabled with [`ansi`](ColumnWriter::ansi).
+///
+/// Wrap the formatter in an escape hatch of [fmt!](crate::fmt!) to make layout decisions mid-template.
+/// The column is only known from where the formatter is wrapped, carry it over between escape hatches with [`with_column`](ColumnWriter::with_column):
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let words = ["lorem", "ipsum", "dolor", "sit", "amet"];
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	let mut col = 0;
+/// 	"> "
+/// 	|_| col = 2;
+/// 	for word in &words {
+/// 		|f| {
+/// 			let mut w = fmtools::ColumnWriter::with_column(&mut *f, col);
+/// 			if w.column() + word.len() > 14 {
+/// 				w.write_str("\n> ")?;
+/// 			}
+/// 			write!(w, "{} ", word)?;
+/// 			col = w.column();
+/// 		}
+/// 	}
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "> lorem ipsum \n> dolor sit \n> amet ");
+/// ```
+///
+/// The resulting string is:
+///
+/// ```text
+/// > lorem ipsum
+/// > dolor sit
+/// > amet
+/// ```
+pub struct ColumnWriter<W> {
+	inner: W,
+	column: usize,
+	tab_width: usize,
+	parser: Option<crate::ansi::Parser>,
+}
+
+impl<W: fmt::Write> ColumnWriter<W> {
+	/// Wraps the writer, starting at column zero.
+	#[inline]
+	pub const fn new(inner: W) -> ColumnWriter<W> {
+		ColumnWriter::with_column(inner, 0)
+	}
+	/// Wraps the writer, starting at the given column.
+	#[inline]
+	pub const fn with_column(inner: W, column: usize) -> ColumnWriter<W> {
+		ColumnWriter { inner, column, tab_width: 8, parser: None }
+	}
+}
+
+impl<W> ColumnWriter<W> {
+	/// Sets the distance between tab stops, zero width tabs do not advance the column.
+	///
+	/// ```
+	/// use std::fmt::Write;
+	///
+	/// let mut w = fmtools::ColumnWriter::new(String::new()).tab_width(4);
+	/// w.write_str("ab\tc").unwrap();
+	/// assert_eq!(w.column(), 5);
+	/// ```
+	#[inline]
+	pub fn tab_width(mut self, tab_width: usize) -> Self {
+		self.tab_width = tab_width;
+		self
+	}
+	/// Skips over ANSI escape sequences when measuring, see [`ansi`](crate::ansi).
+	///
+	/// ```
+	/// use std::fmt::Write;
+	///
+	/// let mut w = fmtools::ColumnWriter::new(String::new()).ansi();
+	/// w.write_str("\x1b[31mred\x1b[0m").unwrap();
+	/// assert_eq!(w.column(), 3);
+	/// ```
+	#[inline]
+	pub fn ansi(mut self) -> Self {
+		self.parser = Some(crate::ansi::Parser::new());
+		self
+	}
+	/// Returns the current column.
+	#[inline]
+	pub fn column(&self) -> usize {
+		self.column
+	}
+	/// Returns a reference to the inner writer.
+	#[inline]
+	pub fn get_ref(&self) -> &W {
+		&self.inner
+	}
+	/// Returns a mutable reference to the inner writer.
+	///
+	/// Output written directly to the inner writer is not tracked.
+	#[inline]
+	pub fn get_mut(&mut self) -> &mut W {
+		&mut self.inner
+	}
+	/// Unwraps the inner writer.
+	#[inline]
+	pub fn into_inner(self) -> W {
+		self.inner
+	}
+
+	fn advance(&mut self, s: &str) {
+		let s = match s.rfind(['\n', '\r']) {
+			Some(i) => {
+				self.column = 0;
+				&s[i + 1..]
+			},
+			None => s,
+		};
+		for chr in s.chars() {
+			if chr == '\t' {
+				if let Some(stops) = self.column.checked_div(self.tab_width) {
+					self.column = (stops + 1) * self.tab_width;
+				}
+			}
+			else {
+				self.column += crate::width::char_width(chr);
+			}
+		}
+	}
+}
+
+impl<W: fmt::Write> fmt::Write for ColumnWriter<W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.inner.write_str(s)?;
+		match self.parser.take() {
+			Some(mut parser) => {
+				let mut s = s;
+				while let Some((escape, chunk)) = parser.split(&mut s) {
+					if !escape {
+						self.advance(chunk);
+					}
+				}
+				self.parser = Some(parser);
+			},
+			None => self.advance(s),
+		}
+		Ok(())
+	}
+}
+
+#[test]
+fn tests() {
+	use fmt::Write;
+
+	// Multi-byte chars and newlines across chunked writes
+	let mut w = ColumnWriter::new(String::new());
+	for chunk in ["h", "é", "llo", " wö", "rld\nf", "oo", "\r", "€€", "", "\n"] {
+		w.write_str(chunk).unwrap();
+		assert_eq!(w.column(), w.get_ref().rsplit(['\n', '\r']).next().unwrap().chars().count());
+	}
+	assert_eq!(w.column(), 0);
+	write!(w, "{:>4}|", 7).unwrap();
+	assert_eq!(w.column(), 5);
+
+	// Tab stops
+	let tabs = |tab_width, s: &str| {
+		let mut w = ColumnWriter::new(String::new()).tab_width(tab_width);
+		w.write_str(s).unwrap();
+		w.column()
+	};
+	assert_eq!(tabs(8, "\t"), 8);
+	assert_eq!(tabs(8, "abcdefg\t"), 8);
+	assert_eq!(tabs(8, "abcdefgh\t"), 16);
+	assert_eq!(tabs(4, "a\tb\t"), 8);
+	assert_eq!(tabs(4, "a\n\t"), 4);
+	assert_eq!(tabs(0, "a\tb"), 2);
+
+	// Escape sequences split across writes
+	let mut w = ColumnWriter::with_column(String::new(), 3).ansi();
+	for chunk in ["\x1b[3", "1mre", "d\x1b", "[0m", "!"] {
+		w.write_str(chunk).unwrap();
+	}
+	assert_eq!(w.column(), 7);
+	let mut w = ColumnWriter::new(String::new());
+	w.write_str("\x1b[31mred").unwrap();
+	assert_eq!(w.column(), crate::width::str_width("\x1b[31mred"));
+	assert!(w.column() > 3);
+
+	// Failed writes are not tracked
+	struct Full;
+	impl fmt::Write for Full {
+		fn write_str(&mut self, _s: &str) -> fmt::Result {
+			Err(fmt::Error)
+		}
+	}
+	let mut w = ColumnWriter::with_column(Full, 2);
+	assert!(w.write_str("abc").is_err());
+	assert_eq!(w.column(), 2);
+}
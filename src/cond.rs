@@ -0,0 +1,76 @@
+use core::fmt;
+
+/// Displays the value only if the flag is true.
+///
+/// ```
+/// let (count, truncated) = (10, true);
+/// let s = fmtools::format!({count}" items"{fmtools::cond(truncated, " (truncated)")});
+/// assert_eq!(s, "10 items (truncated)");
+/// ```
+#[inline]
+pub fn cond<T: fmt::Display>(flag: bool, value: T) -> Cond<T> {
+	Cond { flag, value }
+}
+
+/// Displays the value returned by the closure only if the flag is true.
+///
+/// The closure is not invoked when the flag is false.
+///
+/// ```
+/// let verbose = false;
+/// let s = fmtools::format!("done"{fmtools::cond_with(verbose, || expensive_report())});
+/// assert_eq!(s, "done");
+/// # fn expensive_report() -> String { unreachable!() }
+/// ```
+#[inline]
+pub fn cond_with<T: fmt::Display, F: Fn() -> T>(flag: bool, f: F) -> CondWith<F> {
+	CondWith { flag, f }
+}
+
+/// Displayable object returned by [`cond`].
+#[derive(Copy, Clone)]
+pub struct Cond<T> {
+	flag: bool,
+	value: T,
+}
+impl<T: fmt::Display> fmt::Display for Cond<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.flag { self.value.fmt(f) } else { Ok(()) }
+	}
+}
+debug_as_display!([T: fmt::Display] Cond<T>);
+
+/// Displayable object returned by [`cond_with`].
+#[derive(Copy, Clone)]
+pub struct CondWith<F> {
+	flag: bool,
+	f: F,
+}
+impl<T: fmt::Display, F: Fn() -> T> fmt::Display for CondWith<F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.flag { (self.f)().fmt(f) } else { Ok(()) }
+	}
+}
+debug_as_display!([T: fmt::Display, F: Fn() -> T] CondWith<F>);
+
+#[test]
+fn tests() {
+	use std::cell::Cell;
+
+	assert_eq!(crate::format!("a"{cond(true, "b")}"c"), "abc");
+	assert_eq!(crate::format!("a"{cond(false, "b")}"c"), "ac");
+	assert_eq!(crate::format!({cond(true, 42):>4}), "  42");
+
+	// Copy when the contents are
+	let value = cond(true, 1);
+	let copy = value;
+	assert_eq!(format!("{}{:?}", value, copy), "11");
+
+	// Laziness
+	let calls = Cell::new(0);
+	let value = || { calls.set(calls.get() + 1); "x" };
+	assert_eq!(cond_with(false, value).to_string(), "");
+	assert_eq!(calls.get(), 0);
+	assert_eq!(cond_with(true, value).to_string(), "x");
+	assert_eq!(calls.get(), 1);
+}
@@ -0,0 +1,138 @@
+use core::borrow::Borrow;
+use core::cell::Cell;
+use core::fmt;
+
+/// Number of bytes and chars written by a [`Counted`] display.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Counts {
+	/// Number of bytes written.
+	pub bytes: usize,
+	/// Number of chars written.
+	pub chars: usize,
+}
+
+/// Wraps the value to count how much it writes.
+///
+/// Returns the displayable object and a cheap cloneable handle to read the counts after it was rendered.
+///
+/// ```
+/// let (body, count) = fmtools::counted(fmtools::fmt!("héllo "{42}));
+/// let body = body.to_string();
+/// assert_eq!(count.bytes(), 9);
+/// assert_eq!(count.chars(), 8);
+/// let record = fmtools::format!({count.bytes()}":"{body});
+/// assert_eq!(record, "9:héllo 42");
+/// ```
+///
+/// Every render resets the counts, the handle reports the most recent render.
+/// If the render fails the counts reflect the output written before the error.
+#[cfg(feature = "std")]
+#[inline]
+pub fn counted<T: fmt::Display>(value: T) -> (Counted<T, Count>, Count) {
+	let count = Count::default();
+	(Counted { value, counts: count.clone() }, count)
+}
+
+/// Wraps the value to count how much it writes into the given cell.
+///
+/// Like [`counted`] without allocation.
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// let counts = Cell::new(fmtools::Counts::default());
+/// let s = fmtools::counted_with(fmtools::fmt!("id="{42}), &counts).to_string();
+/// assert_eq!(s, "id=42");
+/// assert_eq!(counts.get().bytes, 5);
+/// ```
+#[inline]
+pub fn counted_with<T: fmt::Display>(value: T, counts: &Cell<Counts>) -> Counted<T, &Cell<Counts>> {
+	Counted { value, counts }
+}
+
+/// Shared handle to the counts of a [`counted`] display.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct Count(std::rc::Rc<Cell<Counts>>);
+
+#[cfg(feature = "std")]
+impl Count {
+	/// Returns the counts of the most recent render.
+	#[inline]
+	pub fn get(&self) -> Counts {
+		self.0.get()
+	}
+	/// Returns the number of bytes written by the most recent render.
+	#[inline]
+	pub fn bytes(&self) -> usize {
+		self.0.get().bytes
+	}
+	/// Returns the number of chars written by the most recent render.
+	#[inline]
+	pub fn chars(&self) -> usize {
+		self.0.get().chars
+	}
+}
+#[cfg(feature = "std")]
+impl Borrow<Cell<Counts>> for Count {
+	#[inline]
+	fn borrow(&self) -> &Cell<Counts> {
+		&self.0
+	}
+}
+
+/// Displayable object returned by [`counted`] and [`counted_with`].
+#[derive(Clone)]
+pub struct Counted<T, C> {
+	value: T,
+	counts: C,
+}
+impl<T: fmt::Display, C: Borrow<Cell<Counts>>> fmt::Display for Counted<T, C> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let counts = self.counts.borrow();
+		counts.set(Counts::default());
+		let mut writer = CountWriter { f, counts };
+		fmt::write(&mut writer, format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display, C: Borrow<Cell<Counts>>] Counted<T, C>);
+
+struct CountWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	counts: &'a Cell<Counts>,
+}
+impl fmt::Write for CountWriter<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.f.write_str(s)?;
+		let counts = self.counts.get();
+		self.counts.set(Counts {
+			bytes: counts.bytes + s.len(),
+			chars: counts.chars + s.chars().count(),
+		});
+		Ok(())
+	}
+}
+
+#[test]
+fn tests() {
+	let (value, count) = counted(crate::fmt!("€"{1}"ß"));
+	assert_eq!(count.get(), Counts::default());
+	assert_eq!(value.to_string(), "€1ß");
+	assert_eq!(count.get(), Counts { bytes: 6, chars: 3 });
+
+	// Renders reset the counts
+	let (value, count) = counted(crate::fmt!("a"{"b"}));
+	assert_eq!(crate::format!({value}{value}), "abab");
+	assert_eq!((count.bytes(), count.chars()), (2, 2));
+
+	// Failed renders count the partial output
+	let counts = Cell::new(Counts::default());
+	let error = crate::fmt(|f| { f.write_str("abc")?; Err(fmt::Error) });
+	assert!(fmt::write(&mut String::new(), format_args!("{}", counted_with(error, &counts))).is_err());
+	assert_eq!(counts.get(), Counts { bytes: 3, chars: 3 });
+
+	// Empty
+	let (value, count) = counted("");
+	assert_eq!(value.to_string(), "");
+	assert_eq!(count.get(), Counts::default());
+}
@@ -0,0 +1,71 @@
+use core::fmt;
+
+/// Displays the value with its `Debug` implementation.
+///
+/// Turns `Debug`-only values into displayable objects, eg. to join them:
+///
+/// ```
+/// #[derive(Debug)]
+/// enum Level { Low, High }
+///
+/// let levels = [Level::Low, Level::High];
+/// let s = fmtools::join(", ", levels.iter().map(fmtools::dbg)).to_string();
+/// assert_eq!(s, "Low, High");
+/// ```
+///
+/// The formatting options are forwarded, the alternate flag `{:#}` pretty prints like `{:#?}`:
+///
+/// ```
+/// let point = (1, 2);
+/// assert_eq!(format!("{:#}", fmtools::dbg(point)), "(\n    1,\n    2,\n)");
+/// assert_eq!(format!("[{:>4}]", fmtools::dbg(42)), "[  42]");
+/// ```
+///
+/// Whether the width, fill and precision are respected is up to the `Debug` implementation of the value.
+///
+/// The value is held by value, pass a reference to borrow it instead: `fmtools::dbg(&value)`.
+/// Inside the braces of [fmt!](crate::fmt!) the `?` specifier does the same: `{value:?}`.
+#[inline]
+pub const fn dbg<T: fmt::Debug>(value: T) -> Dbg<T> {
+	Dbg(value)
+}
+
+/// Displayable object returned by [`dbg()`].
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct Dbg<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Display for Dbg<T> {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(&self.0, f)
+	}
+}
+impl<T: fmt::Debug> fmt::Debug for Dbg<T> {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(&self.0, f)
+	}
+}
+
+#[test]
+fn tests() {
+	use std::time::Duration;
+
+	// Debug-only items in a join
+	let items = [Some(Duration::from_millis(1500)), None];
+	assert_eq!(crate::join(", ", items.iter().map(dbg)).to_string(), "Some(1.5s), None");
+
+	// Alternate flag passthrough
+	assert_eq!(format!("{:#}", dbg(&items[0])), "Some(\n    1.5s,\n)");
+	assert_eq!(format!("{:#}", dbg(&items[0])), format!("{:#?}", items[0]));
+	assert_eq!(crate::format!({dbg((1, "a")):#}), "(\n    1,\n    \"a\",\n)");
+
+	// Other options are forwarded
+	assert_eq!(format!("{:<4}|{:.1}|{:?}", dbg(7), dbg(1.25), dbg("q")), "7   |1.2|\"q\"");
+
+	// By value
+	let owned = dbg(vec![1, 2]);
+	assert_eq!(owned.to_string(), "[1, 2]");
+	assert_eq!(owned.0.len(), 2);
+}
@@ -0,0 +1,54 @@
+use core::fmt;
+
+impl<F: Fn(&mut fmt::Formatter) -> fmt::Result> defmt::Format for crate::fmt<F> {
+	fn format(&self, f: defmt::Formatter) {
+		defmt::Format::format(&defmt::Display2Format(self), f)
+	}
+}
+
+/// Adapts any displayable object for use with [defmt](https://docs.rs/defmt).
+///
+/// Requires the `defmt` feature.
+///
+/// ```
+/// let value = 42;
+/// let msg = fmtools::defmt_display(fmtools::fmt!("value = "{value}));
+/// // defmt::info!("{}", msg);
+/// # fn is_format<T: defmt::Format>(_: &T) {}
+/// # is_format(&msg);
+/// ```
+///
+/// The [fmt!](crate::fmt!) objects implement `defmt::Format` themselves, this adapter is for other `Display` types.
+/// The output is rendered with `core::fmt` on the device, no string interning takes place.
+#[inline]
+pub fn defmt_display<T: fmt::Display>(value: T) -> DefmtDisplay<T> {
+	DefmtDisplay(value)
+}
+
+/// Displayable object adapted for defmt.
+///
+/// See [`defmt_display`] for more information.
+#[derive(Copy, Clone, Debug)]
+#[repr(transparent)]
+pub struct DefmtDisplay<T>(pub T);
+
+impl<T: fmt::Display> defmt::Format for DefmtDisplay<T> {
+	fn format(&self, f: defmt::Formatter) {
+		defmt::Format::format(&defmt::Display2Format(&self.0), f)
+	}
+}
+impl<T: fmt::Display> fmt::Display for DefmtDisplay<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+#[test]
+fn tests() {
+	fn is_format<T: defmt::Format>(_: &T) {}
+
+	let value = 42;
+	is_format(&crate::fmt!("value = "{value}));
+	is_format(&defmt_display(crate::join(", ", &[1, 2, 3])));
+	assert_eq!(defmt_display(crate::fmt!("value = "{value})).to_string(), "value = 42");
+}
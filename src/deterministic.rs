@@ -0,0 +1,189 @@
+/*!
+Helpers for reproducible output.
+
+Golden files and snapshot tests compare the output byte for byte.
+These helpers trade pretty output for output which is identical across runs and platforms.
+*/
+
+use core::fmt;
+
+/// Displays a float with a fixed number of decimals for reproducible output.
+///
+/// ```
+/// assert_eq!(fmtools::float_fixed(0.1 + 0.2, 2).to_string(), "0.30");
+/// assert_eq!(fmtools::float_fixed(0.125, 2).to_string(), "0.12");
+/// assert_eq!(fmtools::float_fixed(0.375, 2).to_string(), "0.38");
+/// assert_eq!(fmtools::float_fixed(-0.001, 2).to_string(), "0.00");
+/// ```
+///
+/// The exact value of the float is rounded to the number of decimals, ties are rounded to the even digit.
+/// Note that most decimal fractions are not exactly representable, `0.125` is a tie while `0.045` is slightly below one.
+/// The rounding is implemented in `core` without relying on the platform, the output is identical across runs and platforms.
+///
+/// Values which round to zero are written without sign, negative zero included.
+/// Not a number is written as `NaN`, infinities as `inf` and `-inf`.
+///
+/// Unlike the shortest representation which round-trips the output never exposes float noise like `0.30000000000000004`,
+/// choose the number of decimals for the precision the output needs.
+/// Pass `f32` values with `.into()`, the conversion is exact.
+///
+/// The width, fill and alignment specifiers are supported, the default alignment is right.
+#[inline]
+pub fn float_fixed(value: f64, decimals: usize) -> FloatFixed {
+	FloatFixed { value, decimals }
+}
+
+/// Displayable object returned by [`float_fixed`].
+#[derive(Copy, Clone)]
+pub struct FloatFixed {
+	value: f64,
+	decimals: usize,
+}
+impl fmt::Display for FloatFixed {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		// Measures the output and checks if any digit is not zero
+		struct Measure {
+			len: usize,
+			zero: bool,
+		}
+		impl fmt::Write for Measure {
+			fn write_str(&mut self, s: &str) -> fmt::Result {
+				self.len += s.len();
+				self.zero = self.zero && s.bytes().all(|b| b == b'0' || b == b'.');
+				Ok(())
+			}
+		}
+		// Clears the sign bit, `f64::abs` is not available in core on older compilers
+		let abs = f64::from_bits(self.value.to_bits() & !(1 << 63));
+		let mut measure = Measure { len: 0, zero: true };
+		fmt::write(&mut measure, format_args!("{:.*}", self.decimals, abs))?;
+		let negative = self.value < 0.0 && !measure.zero;
+
+		let padding = f.width().unwrap_or(0).saturating_sub(measure.len + negative as usize);
+		let (pre, post) = match f.align() {
+			Some(fmt::Alignment::Left) => (0, padding),
+			Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+			_ => (padding, 0),
+		};
+		let fill = f.fill();
+		crate::pad::write_fill(f, fill, pre)?;
+		if negative {
+			f.write_str("-")?;
+		}
+		write!(f, "{:.*}", self.decimals, abs)?;
+		crate::pad::write_fill(f, fill, post)
+	}
+}
+debug_as_display!([] FloatFixed);
+
+/// Displays the key value pairs sorted by key for reproducible output.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let mut scores = HashMap::new();
+/// scores.insert("carol", 3);
+/// scores.insert("alice", 1);
+/// scores.insert("bob", 2);
+///
+/// let s = fmtools::deterministic_map(&scores).to_string();
+/// assert_eq!(s, "{alice: 1, bob: 2, carol: 3}");
+/// ```
+///
+/// The iteration order of `HashMap` is randomized per instance and differs between runs.
+/// The pairs are collected and sorted by key once when the object is constructed, the output does not depend on the iteration order.
+/// Pairs with equal keys keep their relative order.
+///
+/// The pairs are written as `{key: value, ...}` with the keys and values displayed without formatting options.
+/// Combine with [`float_fixed`] to write float values reproducibly.
+#[cfg(feature = "std")]
+pub fn deterministic_map<T, K, V>(pairs: T) -> DeterministicMap<K, V>
+	where T: IntoIterator<Item = (K, V)>,
+		K: Ord + fmt::Display,
+		V: fmt::Display,
+{
+	let mut pairs: Vec<(K, V)> = pairs.into_iter().collect();
+	pairs.sort_by(|a, b| a.0.cmp(&b.0));
+	DeterministicMap { pairs }
+}
+
+/// Displayable object returned by [`deterministic_map`].
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct DeterministicMap<K, V> {
+	pairs: Vec<(K, V)>,
+}
+#[cfg(feature = "std")]
+impl<K: fmt::Display, V: fmt::Display> fmt::Display for DeterministicMap<K, V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("{")?;
+		for (i, (key, value)) in self.pairs.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{}: {}", key, value)?;
+		}
+		f.write_str("}")
+	}
+}
+#[cfg(feature = "std")]
+debug_as_display!([K: fmt::Display, V: fmt::Display] DeterministicMap<K, V>);
+
+#[test]
+fn tests() {
+	#[track_caller]
+	fn check(f: impl fmt::Display, s: &str) {
+		assert_eq!(f.to_string(), s);
+	}
+
+	// Float noise
+	check(float_fixed(0.1 + 0.2, 2), "0.30");
+	check(float_fixed(0.1 + 0.2, 16), "0.3000000000000000");
+	check(float_fixed(0.1 + 0.2, 17), "0.30000000000000004");
+	for _ in 0..100 {
+		check(float_fixed(0.1 + 0.2, 1), "0.3");
+	}
+
+	// Ties to even
+	check(float_fixed(0.5, 0), "0");
+	check(float_fixed(1.5, 0), "2");
+	check(float_fixed(2.5, 0), "2");
+	check(float_fixed(-2.5, 0), "-2");
+	check(float_fixed(0.125, 2), "0.12");
+	check(float_fixed(0.045, 2), "0.04");
+	check(float_fixed(0.1f32.into(), 9), "0.100000001");
+
+	// Signs and special values
+	check(float_fixed(-0.0, 2), "0.00");
+	check(float_fixed(-0.004, 2), "0.00");
+	check(float_fixed(-0.005001, 2), "-0.01");
+	check(float_fixed(-1e21, 0), "-1000000000000000000000");
+	check(float_fixed(f64::NAN, 2), "NaN");
+	check(float_fixed(f64::INFINITY, 2), "inf");
+	check(float_fixed(f64::NEG_INFINITY, 2), "-inf");
+
+	// Padding
+	check(format_args!("[{:>7}]", float_fixed(-1.5, 1)), "[   -1.5]");
+	check(format_args!("[{:<7}]", float_fixed(-0.001, 1)), "[0.0    ]");
+	check(format_args!("[{:*^8}]", float_fixed(1.23456, 2)), "[**1.23**]");
+	check(format_args!("[{:2}]", float_fixed(12.5, 1)), "[12.5]");
+
+	// Randomly seeded hash maps
+	#[cfg(feature = "std")]
+	{
+		use std::collections::{BTreeMap, HashMap};
+		let entries: Vec<(String, f64)> = (0..50).map(|i| (format!("key{:02}", i), i as f64 * 0.1)).collect();
+		let expected = deterministic_map(entries.iter().map(|(k, v)| (k, float_fixed(*v, 3)))).to_string();
+		let sorted: BTreeMap<_, _> = entries.iter().cloned().collect();
+		let btree = crate::format!("{" for (k, v) in &sorted sep ", " { {k}": "{float_fixed(*v, 3)} } "}");
+		assert_eq!(expected, btree);
+		for _ in 0..10 {
+			let map: HashMap<_, _> = entries.iter().rev().cloned().collect();
+			assert_eq!(deterministic_map(map.iter().map(|(k, v)| (k, float_fixed(*v, 3)))).to_string(), expected);
+		}
+		assert!(expected.starts_with("{key00: 0.000, key01: 0.100, key02: 0.200, key03: 0.300, "));
+
+		check(deterministic_map(HashMap::<i32, i32>::new()), "{}");
+		check(deterministic_map([(2, 'b'), (1, 'x'), (2, 'a')]), "{1: x, 2: b, 2: a}");
+	}
+}
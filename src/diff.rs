@@ -0,0 +1,180 @@
+use std::fmt;
+
+/// Renders a line based diff between two texts.
+///
+/// ```
+/// let old = "a\nb\nc\n";
+/// let new = "a\nB\nc\nd\n";
+/// let s = fmtools::diff_lines(old, new).to_string();
+/// assert_eq!(s, " a\n-b\n+B\n c\n+d\n");
+/// ```
+///
+/// Every line is prefixed with `-` if only in the old text, `+` if only in the new text and a space if in both.
+/// The lines are split the same way as [`str::lines`] and each line is terminated by a newline.
+///
+/// The diff is computed with a longest common subsequence, which takes time and memory proportional to the product of the line counts.
+/// Good enough for test failure output and configuration changes, not for large files.
+///
+/// Limit the unchanged lines around the changes with [`context`](DiffLines::context) for unified style hunks:
+///
+/// ```
+/// let old = "1\n2\n3\n4\n5\n6\n";
+/// let new = "1\n2\n3\n4\n5\nsix\n";
+/// let s = fmtools::diff_lines(old, new).context(1).to_string();
+/// assert_eq!(s, "@@ -5,2 +5,2 @@\n 5\n-6\n+six\n");
+/// ```
+#[inline]
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> DiffLines<'a> {
+	DiffLines { old, new, context: None }
+}
+
+/// Displayable object returned by [`diff_lines`].
+#[derive(Copy, Clone)]
+pub struct DiffLines<'a> {
+	old: &'a str,
+	new: &'a str,
+	context: Option<usize>,
+}
+impl<'a> DiffLines<'a> {
+	/// Collapses the unchanged lines into hunks with the given number of context lines around the changes.
+	///
+	/// Each hunk starts with a `@@ -start,len +start,len @@` header.
+	/// Identical texts render nothing.
+	#[inline]
+	pub fn context(self, lines: usize) -> DiffLines<'a> {
+		DiffLines { context: Some(lines), ..self }
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Op {
+	Equal,
+	Delete,
+	Insert,
+}
+
+fn diff<'s>(old: &[&'s str], new: &[&'s str]) -> Vec<(Op, &'s str)> {
+	// Length of the longest common subsequence of the suffixes
+	let m = new.len() + 1;
+	let mut table = vec![0u32; (old.len() + 1) * m];
+	for i in (0..old.len()).rev() {
+		for j in (0..new.len()).rev() {
+			table[i * m + j] = if old[i] == new[j] {
+				table[(i + 1) * m + j + 1] + 1
+			}
+			else {
+				u32::max(table[(i + 1) * m + j], table[i * m + j + 1])
+			};
+		}
+	}
+	let mut ops = Vec::with_capacity(old.len() + new.len());
+	let (mut i, mut j) = (0, 0);
+	while i < old.len() && j < new.len() {
+		if old[i] == new[j] {
+			ops.push((Op::Equal, old[i]));
+			i += 1;
+			j += 1;
+		}
+		else if table[(i + 1) * m + j] >= table[i * m + j + 1] {
+			ops.push((Op::Delete, old[i]));
+			i += 1;
+		}
+		else {
+			ops.push((Op::Insert, new[j]));
+			j += 1;
+		}
+	}
+	ops.extend(old[i..].iter().map(|&line| (Op::Delete, line)));
+	ops.extend(new[j..].iter().map(|&line| (Op::Insert, line)));
+	ops
+}
+
+fn write_op(f: &mut fmt::Formatter, op: Op, line: &str) -> fmt::Result {
+	let prefix = match op {
+		Op::Equal => " ",
+		Op::Delete => "-",
+		Op::Insert => "+",
+	};
+	f.write_str(prefix)?;
+	f.write_str(line)?;
+	f.write_str("\n")
+}
+
+impl fmt::Display for DiffLines<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let old: Vec<&str> = self.old.lines().collect();
+		let new: Vec<&str> = self.new.lines().collect();
+		let ops = diff(&old, &new);
+
+		let context = match self.context {
+			Some(context) => context,
+			None => {
+				for &(op, line) in &ops {
+					write_op(f, op, line)?;
+				}
+				return Ok(());
+			},
+		};
+
+		let mut start = 0;
+		while let Some(first) = ops[start..].iter().position(|&(op, _)| op != Op::Equal) {
+			let first = start + first;
+			// Extend the hunk while the next change is within reach of the context
+			let mut last = first;
+			let mut i = first;
+			while i < ops.len() {
+				if ops[i].0 != Op::Equal {
+					last = i;
+				}
+				else if i - last > 2 * context {
+					break;
+				}
+				i += 1;
+			}
+			let begin = first.saturating_sub(context).max(start);
+			let end = usize::min(last + context + 1, ops.len());
+
+			// Line numbers of the hunk in both texts
+			let count = |ops: &[(Op, &str)], skip: Op| ops.iter().filter(|&&(op, _)| op != skip).count();
+			let (old_start, new_start) = (count(&ops[..begin], Op::Insert), count(&ops[..begin], Op::Delete));
+			let (old_len, new_len) = (count(&ops[begin..end], Op::Insert), count(&ops[begin..end], Op::Delete));
+			let line = |start: usize, len: usize| if len == 0 { start } else { start + 1 };
+			writeln!(f, "@@ -{},{} +{},{} @@", line(old_start, old_len), old_len, line(new_start, new_len), new_len)?;
+			for &(op, line) in &ops[begin..end] {
+				write_op(f, op, line)?;
+			}
+			start = end;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([] DiffLines<'_>);
+
+#[test]
+fn tests() {
+	#[track_caller]
+	fn check(old: &str, new: &str, expected: &str) {
+		assert_eq!(diff_lines(old, new).to_string(), expected);
+	}
+
+	// Identical and empty
+	check("a\nb", "a\nb\n", " a\n b\n");
+	check("", "", "");
+	check("", "a\nb", "+a\n+b\n");
+	check("a\nb", "", "-a\n-b\n");
+
+	// Insertions, deletions and changes
+	check("a\nc", "a\nb\nc", " a\n+b\n c\n");
+	check("a\nb\nc", "a\nc", " a\n-b\n c\n");
+	check("a\nb\nc", "a\nx\nc", " a\n-b\n+x\n c\n");
+	check("x\na\nb", "a\nb\ny", "-x\n a\n b\n+y\n");
+
+	// Hunks
+	let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+	let new = "one\n2\n3\n4\n5\n6\n7\n8\n9\nten\n";
+	assert_eq!(diff_lines(old, new).context(2).to_string(), "@@ -1,3 +1,3 @@\n-1\n+one\n 2\n 3\n@@ -8,3 +8,3 @@\n 8\n 9\n-10\n+ten\n");
+	assert_eq!(diff_lines(old, new).context(4).to_string(), "@@ -1,10 +1,10 @@\n-1\n+one\n 2\n 3\n 4\n 5\n 6\n 7\n 8\n 9\n-10\n+ten\n");
+	assert_eq!(diff_lines(old, old).context(1).to_string(), "");
+	assert_eq!(diff_lines("a\nb\n", "a\n").context(0).to_string(), "@@ -2,1 +1,0 @@\n-b\n");
+	assert_eq!(diff_lines("", "a\n").context(3).to_string(), "@@ -0,0 +1,1 @@\n+a\n");
+}
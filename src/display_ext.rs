@@ -0,0 +1,91 @@
+use core::fmt;
+use crate::*;
+
+/// Extension methods to compose displayable objects.
+///
+/// Implemented for every type implementing `Display`.
+/// Each method wraps the value in an adapter which is itself displayable, so the adapters chain:
+///
+/// ```
+/// use fmtools::DisplayExt;
+///
+/// let title = "report";
+/// let s = fmtools::format!("["{title.uppercased().quoted().centered(12)}"]");
+/// assert_eq!(s, "[  \"REPORT\"  ]");
+/// ```
+///
+/// The methods take the value by value, use a reference to keep the original: `(&value).padded(10)`.
+///
+/// ```
+/// use fmtools::DisplayExt;
+///
+/// let name = String::from("ferris");
+/// let line = fmtools::fmt!("- "{(&name).quoted().padded(10)}"|\n");
+/// assert_eq!(line.repeated(2).to_string(), "- \"ferris\"  |\n- \"ferris\"  |\n");
+/// assert_eq!(name, "ferris");
+/// ```
+pub trait DisplayExt: fmt::Display {
+	/// Pads the output with spaces on the right to at least the given width in chars.
+	///
+	/// See [`pad`].
+	#[inline]
+	fn padded(self, width: usize) -> Padded<Self> where Self: Sized {
+		pad(width, Align::Left, self)
+	}
+
+	/// Centers the output with spaces to at least the given width in chars.
+	///
+	/// See [`center`].
+	#[inline]
+	fn centered(self, width: usize) -> Padded<Self> where Self: Sized {
+		center(width, self)
+	}
+
+	/// Truncates the output to at most the given number of chars followed by `…`.
+	///
+	/// Use [`Truncate::suffix`] to change the suffix, see [`truncate`].
+	#[inline]
+	fn truncated(self, max_chars: usize) -> Truncate<'static, Self> where Self: Sized {
+		truncate(max_chars, self)
+	}
+
+	/// Converts the output to uppercase.
+	#[inline]
+	fn uppercased(self) -> Uppercase<Self> where Self: Sized {
+		Uppercase::new(self)
+	}
+
+	/// Repeats the output the given number of times.
+	#[inline]
+	fn repeated(self, count: usize) -> Repeat<Self> where Self: Sized {
+		Repeat::new(count, self)
+	}
+
+	/// Wraps the output in double quotes, escaping backslashes and double quotes.
+	#[inline]
+	fn quoted(self) -> Quoted<Self> where Self: Sized {
+		Quoted::new(self)
+	}
+
+	/// Prefixes every line of the output.
+	///
+	/// See [`indent`].
+	#[inline]
+	fn indented(self, prefix: &str) -> Indent<'_, Self> where Self: Sized {
+		indent(prefix, self)
+	}
+}
+
+impl<T: fmt::Display + ?Sized> DisplayExt for T {}
+
+#[test]
+fn tests() {
+	let value = 42;
+	assert_eq!(value.padded(4).quoted().to_string(), "\"42  \"");
+	assert_eq!((&value).repeated(2).centered(6).to_string(), " 4242 ");
+	assert_eq!("ab\ncd".uppercased().indented("| ").to_string(), "| AB\n| CD");
+	assert_eq!("ab\ncd".uppercased().indented("| ").truncated(7).to_string(), "| AB\n| …");
+	assert_eq!("abcd".uppercased().quoted().truncated(3).to_string(), "\"AB…");
+	assert_eq!(crate::fmt!({value}).truncated(1).suffix("").to_string(), "4");
+	assert_eq!(crate::fmt!({value}).uppercased().repeated(0).padded(2).to_string(), "  ");
+}
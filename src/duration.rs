@@ -0,0 +1,212 @@
+use core::fmt;
+use core::time::Duration;
+
+/// Formats the duration in the ISO 8601 `PnDTnHnMnS` form.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let d = Duration::from_millis(3723_500);
+/// assert_eq!(fmtools::iso8601_duration(d).to_string(), "PT1H2M3.5S");
+/// ```
+///
+/// Zero components are omitted, the time designator `T` is only written if there are time components.
+/// Fractional seconds are only written when non-zero, without trailing zeros.
+/// The zero duration is written as `PT0S`.
+///
+/// Days are the largest unit, they are always 24 hours long and are not carried over into months or years.
+#[inline]
+pub fn iso8601_duration(d: Duration) -> Iso8601Duration {
+	Iso8601Duration { d }
+}
+
+/// Displayable object returned by [`iso8601_duration`].
+#[derive(Copy, Clone)]
+pub struct Iso8601Duration {
+	d: Duration,
+}
+impl fmt::Display for Iso8601Duration {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let secs = self.d.as_secs();
+		let nanos = self.d.subsec_nanos();
+		let days = secs / 86400;
+		let hours = secs / 3600 % 24;
+		let minutes = secs / 60 % 60;
+		let seconds = secs % 60;
+
+		f.write_str("P")?;
+		if days != 0 {
+			write!(f, "{}D", days)?;
+		}
+		if hours == 0 && minutes == 0 && seconds == 0 && nanos == 0 {
+			// Zero duration must still have at least one component
+			return if days == 0 { f.write_str("T0S") } else { Ok(()) };
+		}
+		f.write_str("T")?;
+		if hours != 0 {
+			write!(f, "{}H", hours)?;
+		}
+		if minutes != 0 {
+			write!(f, "{}M", minutes)?;
+		}
+		if seconds != 0 || nanos != 0 {
+			write!(f, "{}", seconds)?;
+			if nanos != 0 {
+				let mut frac = nanos;
+				let mut digits = 9;
+				while frac % 10 == 0 {
+					frac /= 10;
+					digits -= 1;
+				}
+				write!(f, ".{:01$}", frac, digits)?;
+			}
+			f.write_str("S")?;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([] Iso8601Duration);
+
+/// Formats the duration in its natural unit with 3 significant digits.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// assert_eq!(fmtools::duration_auto(Duration::from_millis(1250)).to_string(), "1.25s");
+/// assert_eq!(fmtools::duration_auto(Duration::from_micros(350_000)).to_string(), "350ms");
+/// assert_eq!(fmtools::duration_auto(Duration::from_nanos(12_400)).to_string(), "12.4µs");
+/// assert_eq!(fmtools::duration_auto(Duration::from_nanos(800)).to_string(), "800ns");
+/// ```
+///
+/// The unit is one of `ns`, `µs`, `ms` or `s`, durations of 1000 seconds and longer are written in whole seconds.
+/// Nanoseconds are written without fractional digits.
+/// Rounding which reaches the next unit moves to that unit, 999.6µs is written as `1.00ms`.
+///
+/// The formatting options for width, fill and alignment are respected, which makes it suitable for benchmark tables:
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let s = fmtools::format!("["{fmtools::duration_auto(Duration::from_nanos(999_600)).ascii():>8}"]");
+/// assert_eq!(s, "[  1.00ms]");
+/// ```
+#[inline]
+pub fn duration_auto(d: Duration) -> DurationAuto {
+	DurationAuto { d, ascii: false }
+}
+
+/// Displayable object returned by [`duration_auto`].
+#[derive(Copy, Clone)]
+pub struct DurationAuto {
+	d: Duration,
+	ascii: bool,
+}
+impl DurationAuto {
+	/// Writes microseconds as `us` instead of `µs`.
+	#[inline]
+	pub const fn ascii(self) -> DurationAuto {
+		DurationAuto { ascii: true, ..self }
+	}
+
+	fn write<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+		let nanos = self.d.as_nanos();
+		if nanos < 1000 {
+			return write!(w, "{}ns", nanos);
+		}
+		let mut unit = 0;
+		let mut scale = 1000;
+		while unit < 2 && nanos >= scale * 1000 {
+			unit += 1;
+			scale *= 1000;
+		}
+		let mut digits = match nanos / scale { 0..=9 => 2, 10..=99 => 1, _ => 0 };
+		loop {
+			let pow = 10u128.pow(digits);
+			let step = scale / pow;
+			let rounded = (nanos + step / 2) / step;
+			let int = rounded / pow;
+			// Rounding up may add an integer digit or reach the next unit
+			if digits > 0 && int >= 1000 / 10u128.pow(digits) {
+				digits -= 1;
+				continue;
+			}
+			if digits == 0 && int >= 1000 && unit < 2 {
+				unit += 1;
+				scale *= 1000;
+				digits = 2;
+				continue;
+			}
+			let suffix = match unit {
+				0 if self.ascii => "us",
+				0 => "µs",
+				1 => "ms",
+				_ => "s",
+			};
+			return if digits == 0 {
+				write!(w, "{}{}", int, suffix)
+			}
+			else {
+				write!(w, "{}.{:0width$}{}", int, rounded % pow, suffix, width = digits as usize)
+			};
+		}
+	}
+}
+impl fmt::Display for DurationAuto {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut s = crate::FixedString::<48>::new();
+		self.write(&mut s)?;
+		f.pad(&s)
+	}
+}
+debug_as_display!([] DurationAuto);
+
+#[test]
+fn tests() {
+	const TESTS: &[(u64, u32, &str)] = &[
+		(0, 0, "PT0S"),
+		(0, 1, "PT0.000000001S"),
+		(0, 500_000_000, "PT0.5S"),
+		(0, 120_000_000, "PT0.12S"),
+		(1, 0, "PT1S"),
+		(59, 999_999_999, "PT59.999999999S"),
+		(60, 0, "PT1M"),
+		(3600, 0, "PT1H"),
+		(86400, 0, "P1D"),
+		(3723, 500_000_000, "PT1H2M3.5S"),
+		(3601, 0, "PT1H1S"),
+		(2 * 86400 + 60, 0, "P2DT1M"),
+		(3 * 86400 + 4 * 3600 + 5 * 60 + 6, 7_000_000, "P3DT4H5M6.007S"),
+		(400 * 86400, 250_000_000, "P400DT0.25S"),
+	];
+	for &(secs, nanos, expected) in TESTS {
+		assert_eq!(iso8601_duration(Duration::new(secs, nanos)).to_string(), expected, "{}s {}ns", secs, nanos);
+	}
+
+	const AUTO: &[(u64, u32, &str)] = &[
+		(0, 0, "0ns"),
+		(0, 800, "800ns"),
+		(0, 999, "999ns"),
+		(0, 1_000, "1.00µs"),
+		(0, 1_004, "1.00µs"),
+		(0, 1_005, "1.01µs"),
+		(0, 9_995, "10.0µs"),
+		(0, 12_400, "12.4µs"),
+		(0, 99_950, "100µs"),
+		(0, 999_499, "999µs"),
+		(0, 999_600, "1.00ms"),
+		(0, 350_000_000, "350ms"),
+		(0, 999_500_000, "1.00s"),
+		(1, 250_000_000, "1.25s"),
+		(59, 990_000_000, "60.0s"),
+		(999, 400_000_000, "999s"),
+		(999, 600_000_000, "1000s"),
+		(123_456, 0, "123456s"),
+	];
+	for &(secs, nanos, expected) in AUTO {
+		assert_eq!(duration_auto(Duration::new(secs, nanos)).to_string(), expected, "{}s {}ns", secs, nanos);
+	}
+	assert_eq!(duration_auto(Duration::from_nanos(12_400)).ascii().to_string(), "12.4us");
+	assert_eq!(duration_auto(Duration::from_millis(5)).ascii().to_string(), "5.00ms");
+	assert_eq!(format!("[{:<7}]", duration_auto(Duration::from_nanos(1_500))), "[1.50µs ]");
+	assert_eq!(duration_auto(Duration::MAX).to_string(), "18446744073709551616s");
+}
@@ -0,0 +1,124 @@
+use core::fmt;
+
+/// One of two displayable objects.
+///
+/// Unifies branches which produce different displayable types:
+///
+/// ```
+/// use fmtools::Either;
+///
+/// fn describe(values: &[i32]) -> impl std::fmt::Display + '_ {
+/// 	if values.is_empty() {
+/// 		Either::Left(fmtools::fmt!("empty"))
+/// 	}
+/// 	else {
+/// 		Either::Right(fmtools::join(", ", values))
+/// 	}
+/// }
+///
+/// assert_eq!(describe(&[]).to_string(), "empty");
+/// assert_eq!(describe(&[1, 2]).to_string(), "1, 2");
+/// ```
+///
+/// Both `Display` and `Debug` are delegated to the active variant, including the formatting options.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Either<A, B> {
+	Left(A),
+	Right(B),
+}
+
+/// One of three displayable objects.
+///
+/// Like [`Either`] but avoids nesting for three branches.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Either3<A, B, C> {
+	A(A),
+	B(B),
+	C(C),
+}
+
+/// Returns `Left(a)` if the condition is true, otherwise `Right(b)`.
+///
+/// ```
+/// let n = 3;
+/// let s = fmtools::format!({n}" item"{fmtools::either(n == 1, "", fmtools::fmt!("s"))});
+/// assert_eq!(s, "3 items");
+/// ```
+#[inline]
+pub fn either<A, B>(cond: bool, a: A, b: B) -> Either<A, B> {
+	if cond { Either::Left(a) } else { Either::Right(b) }
+}
+
+/// Returns `Either::Left(a)`.
+#[inline]
+pub fn left<A, B>(a: A) -> Either<A, B> {
+	Either::Left(a)
+}
+
+/// Returns `Either::Right(b)`.
+#[inline]
+pub fn right<A, B>(b: B) -> Either<A, B> {
+	Either::Right(b)
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Either<A, B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Either::Left(a) => a.fmt(f),
+			Either::Right(b) => b.fmt(f),
+		}
+	}
+}
+
+impl<A: fmt::Debug, B: fmt::Debug> fmt::Debug for Either<A, B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Either::Left(a) => a.fmt(f),
+			Either::Right(b) => b.fmt(f),
+		}
+	}
+}
+
+impl<A: fmt::Display, B: fmt::Display, C: fmt::Display> fmt::Display for Either3<A, B, C> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Either3::A(a) => a.fmt(f),
+			Either3::B(b) => b.fmt(f),
+			Either3::C(c) => c.fmt(f),
+		}
+	}
+}
+impl<A: fmt::Debug, B: fmt::Debug, C: fmt::Debug> fmt::Debug for Either3<A, B, C> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Either3::A(a) => a.fmt(f),
+			Either3::B(b) => b.fmt(f),
+			Either3::C(c) => c.fmt(f),
+		}
+	}
+}
+
+#[test]
+fn tests() {
+	fn pick(n: i32) -> Either3<impl fmt::Display + fmt::Debug, impl fmt::Display + fmt::Debug, impl fmt::Display + fmt::Debug> {
+		match n {
+			0 => Either3::A("zero"),
+			1 => Either3::B(crate::fmt!("one")),
+			_ => Either3::C(crate::fmt!(move "many ("{n}")")),
+		}
+	}
+	assert_eq!(pick(0).to_string(), "zero");
+	assert_eq!(pick(1).to_string(), "one");
+	assert_eq!(pick(5).to_string(), "many (5)");
+
+	// Inside fmt! holes with formatting options
+	for &flag in &[true, false] {
+		let value: Either<i32, &str> = either(flag, 42, "x");
+		let s = crate::format!("["{value:>3}"]");
+		assert_eq!(s, if flag { "[ 42]" } else { "[  x]" });
+	}
+	assert_eq!(left::<_, &str>(1).to_string(), "1");
+	assert_eq!(right::<i32, _>("b").to_string(), "b");
+	assert_eq!(format!("{:?}", left::<_, i32>("a")), "\"a\"");
+	assert_eq!(format!("{:?}", pick(1)), "one");
+}
@@ -0,0 +1,126 @@
+use core::fmt;
+use crate::buf::Window;
+
+const WINDOW: usize = 256;
+
+/// Compares the rendered output of two displayable objects.
+///
+/// ```
+/// let a = fmtools::fmt!("value = "{42});
+/// let b = fmtools::join("", &["val", "ue = ", "42"]);
+/// assert!(fmtools::display_eq(&a, &b));
+/// assert!(!fmtools::display_eq(&a, &"value = 43"));
+/// ```
+///
+/// The output of `b` is compared as it is written against the output of `a` rendered in windows of 256 bytes on the stack.
+/// Writing `b` is aborted as soon as the first difference is found, nothing is allocated.
+///
+/// Every window renders `a` again, long outputs of `a` are rendered as many times as they have windows.
+///
+/// If either object returns an error they are considered not equal.
+///
+/// The [`FmtFn`](crate::FmtFn) type implements `PartialEq` using this function.
+pub fn display_eq<A: fmt::Display + ?Sized, B: fmt::Display + ?Sized>(a: &A, b: &B) -> bool {
+	let window = match Window::render([0; WINDOW], a, 0) {
+		Some(window) => window,
+		None => return false,
+	};
+	let mut compare = CompareWindows { value: a, window, offset: 0, pos: 0 };
+	fmt::write(&mut compare, format_args!("{}", b)).is_ok() && compare.is_done()
+}
+
+// Compares the output against the expected value, rendering the next window of the value when the current one is used up.
+struct CompareWindows<'a, T: ?Sized> {
+	value: &'a T,
+	window: Window<[u8; WINDOW]>,
+	offset: usize,
+	pos: usize,
+}
+impl<T: fmt::Display + ?Sized> CompareWindows<'_, T> {
+	// Renders the window after the current one, returns `None` if the value returns an error.
+	fn next_window(&self) -> Option<Window<[u8; WINDOW]>> {
+		// A partial window is the end of the output
+		if !self.window.is_full() {
+			return Window::render([0; WINDOW], &"", 0);
+		}
+		Window::render([0; WINDOW], self.value, self.offset + WINDOW)
+	}
+	// Checks that the whole value has been compared.
+	fn is_done(&self) -> bool {
+		self.pos == self.window.len() && matches!(self.next_window(), Some(window) if window.len() == 0)
+	}
+}
+impl<T: fmt::Display + ?Sized> fmt::Write for CompareWindows<'_, T> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let mut bytes = s.as_bytes();
+		while !bytes.is_empty() {
+			if self.pos == self.window.len() {
+				self.window = self.next_window().ok_or(fmt::Error)?;
+				self.offset += WINDOW;
+				self.pos = 0;
+				if self.window.len() == 0 {
+					return Err(fmt::Error);
+				}
+			}
+			let n = usize::min(bytes.len(), self.window.len() - self.pos);
+			if bytes[..n] != self.window.as_bytes()[self.pos..self.pos + n] {
+				return Err(fmt::Error);
+			}
+			self.pos += n;
+			bytes = &bytes[n..];
+		}
+		Ok(())
+	}
+}
+
+#[test]
+fn tests() {
+	// Lengths around the window size
+	let long = "0123456789".repeat(100);
+	let chunked = crate::fmt(|f| {
+		for chunk in long.as_bytes().chunks(7) {
+			f.write_str(std::str::from_utf8(chunk).unwrap())?;
+		}
+		Ok(())
+	});
+
+	// Equal
+	assert!(display_eq(&"", &""));
+	assert!(display_eq(&crate::fmt!("abc"{1}), &"abc1"));
+	assert!(display_eq(&chunked, &long));
+	assert!(display_eq(&long[..WINDOW], &crate::fmt!({&long[..100]}{&long[100..WINDOW]})));
+
+	// Unequal at the start
+	assert!(!display_eq(&"xbc", &"abc"));
+	assert!(!display_eq(&chunked, &format!("x{}", &long[1..])));
+
+	// Unequal at the end
+	assert!(!display_eq(&"abx", &"abc"));
+	assert!(!display_eq(&chunked, &format!("{}x", &long[..long.len() - 1])));
+
+	// Different lengths
+	assert!(!display_eq(&"ab", &"abc"));
+	assert!(!display_eq(&"abc", &"ab"));
+	assert!(!display_eq(&"", &"a"));
+	assert!(!display_eq(&chunked, &long[..WINDOW]));
+	assert!(!display_eq(&long[..WINDOW], &long[..WINDOW + 1]));
+	assert!(!display_eq(&long[..2 * WINDOW + 1], &long[..2 * WINDOW]));
+	assert!(display_eq(&long[..2 * WINDOW], &crate::fmt!({&long[..WINDOW + 1]}{&long[WINDOW + 1..2 * WINDOW]})));
+
+	// The first object is rendered once per window, the second object once
+	let renders = std::cell::Cell::new(0);
+	let counted = crate::fmt(|f| {
+		renders.set(renders.get() + 1);
+		f.write_str(&long)
+	});
+	assert!(display_eq(&counted, &counted));
+	assert_eq!(renders.get(), long.len().div_ceil(WINDOW) + 1);
+
+	// Errors
+	let error = crate::fmt(|f| { f.write_str("abc")?; Err(fmt::Error) });
+	assert!(!display_eq(&error, &"abc"));
+
+	// PartialEq
+	assert_eq!(crate::fmt!("a = "{42}), crate::fmt!("a = 42"));
+	assert_ne!(crate::fmt!("a = "{42}), crate::fmt!("a = 43"));
+}
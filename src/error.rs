@@ -0,0 +1,103 @@
+use core::fmt;
+
+/// Lightweight error type built from a displayable object.
+///
+/// Construct it with the [err!](crate::err!) macro using [fmt syntax](crate::fmt!).
+/// Both `Display` and `Debug` render the error message.
+///
+/// With the `std` feature the displayable object is boxed and rendered lazily when the error is displayed.
+/// Without the `std` feature the message is rendered immediately into a fixed buffer of [`FmtError::CAPACITY`] bytes, longer messages are truncated.
+///
+/// ```
+/// fn parse(id: u32) -> Result<(), Box<dyn std::error::Error>> {
+/// 	if id != 0 {
+/// 		Err(fmtools::err!("invalid header "{id:#x}))?;
+/// 	}
+/// 	Ok(())
+/// }
+/// let err = parse(42).unwrap_err();
+/// assert_eq!(err.to_string(), "invalid header 0x2a");
+/// ```
+pub struct FmtError {
+	#[cfg(feature = "std")]
+	display: Box<dyn fmt::Display + Send + Sync>,
+	#[cfg(not(feature = "std"))]
+	message: crate::FixedString<{ FmtError::CAPACITY }>,
+}
+
+impl FmtError {
+	/// Size of the message buffer without the `std` feature.
+	pub const CAPACITY: usize = 128;
+
+	/// Constructs a new error from a displayable object.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn new<T: fmt::Display + Send + Sync + 'static>(display: T) -> FmtError {
+		FmtError { display: Box::new(display) }
+	}
+
+	/// Constructs a new error from a displayable object.
+	#[cfg(not(feature = "std"))]
+	pub fn new<T: fmt::Display>(display: T) -> FmtError {
+		let mut message = crate::FixedString::new();
+		let _ = fmt::write(&mut message, format_args!("{}", display));
+		FmtError { message }
+	}
+}
+
+impl fmt::Display for FmtError {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.display.fmt(f)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+debug_as_display!([] FmtError);
+
+#[cfg(feature = "std")]
+impl std::error::Error for FmtError {}
+
+/// Constructs a [`FmtError`] using [fmt syntax](crate::fmt!).
+///
+/// The arguments are captured by value, as if by `fmt!(move ...)`.
+///
+/// ```
+/// let id = 42;
+/// let err = fmtools::err!("invalid header "{id:#x});
+/// assert_eq!(err.to_string(), "invalid header 0x2a");
+/// ```
+#[macro_export]
+macro_rules! err {
+	($($tt:tt)*) => {
+		$crate::FmtError::new($crate::fmt(move |_f| {
+			$crate::__fmt!{_f $($tt)*}
+			Ok(())
+		}))
+	};
+}
+
+#[test]
+fn tests() {
+	let id = 42;
+	let err = err!("invalid header "{id:#x});
+	assert_eq!(err.to_string(), "invalid header 0x2a");
+	assert_eq!(format!("{:?}", err), "invalid header 0x2a");
+
+	// The Error impl requires the std feature
+	#[cfg(feature = "std")]
+	{
+		use std::error::Error;
+		assert!(err.source().is_none());
+
+		fn fallible(name: String) -> Result<(), Box<dyn Error + Send + Sync>> {
+			Err(err!("unknown name: "{name}))?;
+			Ok(())
+		}
+		let err = fallible(String::from("foo")).unwrap_err();
+		assert_eq!(err.to_string(), "unknown name: foo");
+		assert!(err.source().is_none());
+	}
+}
@@ -0,0 +1,246 @@
+use core::fmt;
+
+/// Escapes the output for use in HTML text and attribute values.
+///
+/// ```
+/// let name = "<script>alert('hi')</script>";
+/// let s = fmtools::format!("<li>"{fmtools::escape_html(name)}"</li>");
+/// assert_eq!(s, "<li>&lt;script&gt;alert(&#39;hi&#39;)&lt;/script&gt;</li>");
+/// ```
+///
+/// The characters `&`, `<`, `>`, `"` and `'` are replaced by their character references.
+///
+/// See also the `html` modifier of [fmt!](crate::fmt!) which escapes every hole.
+#[inline]
+pub fn escape_html<T: fmt::Display>(value: T) -> EscapeHtml<T> {
+	EscapeHtml { value }
+}
+
+/// Displayable object returned by [`escape_html`].
+#[derive(Copy, Clone)]
+pub struct EscapeHtml<T> {
+	value: T,
+}
+impl<T: fmt::Display> fmt::Display for EscapeHtml<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::write(&mut HtmlWriter(f), format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display] EscapeHtml<T>);
+
+struct HtmlWriter<'a, 'f>(&'a mut fmt::Formatter<'f>);
+impl fmt::Write for HtmlWriter<'_, '_> {
+	fn write_str(&mut self, mut s: &str) -> fmt::Result {
+		while let Some(i) = s.find(['&', '<', '>', '"', '\'']) {
+			self.0.write_str(&s[..i])?;
+			self.0.write_str(match s.as_bytes()[i] {
+				b'&' => "&amp;",
+				b'<' => "&lt;",
+				b'>' => "&gt;",
+				b'"' => "&quot;",
+				_ => "&#39;",
+			})?;
+			s = &s[i + 1..];
+		}
+		self.0.write_str(s)
+	}
+}
+
+/// Escapes newlines and control characters, making the output single-line and safe to write to a terminal.
+///
+/// ```
+/// let user = "bob\n[INFO] login ok\x1b[2J";
+/// let s = fmtools::format!("login failed for "{fmtools::sanitize(user)});
+/// assert_eq!(s, r"login failed for bob\n[INFO] login ok\x1b[2J");
+/// ```
+///
+/// Guards log records against injected lines and terminal escape sequences in untrusted data:
+///
+/// * `\n` and `\r` are written as `\n` and `\r`, or as the symbols `␊` and `␍` with [`symbols`](Sanitize::symbols).
+/// * All other C0 and C1 control characters, including tab, escape and delete, are written as `\xNN`.
+/// * Everything else, including printable Unicode and backslashes, is written unchanged.
+///
+/// Unlike `Debug` for strings no quotes are added and non-ASCII text is not escaped.
+/// The output is not meant to be unescaped again, a backslash in the input is not distinguished from an escape.
+#[inline]
+pub fn sanitize<T: fmt::Display>(value: T) -> Sanitize<T> {
+	Sanitize { value, symbols: false }
+}
+
+/// Displayable object returned by [`sanitize`].
+#[derive(Copy, Clone)]
+pub struct Sanitize<T> {
+	value: T,
+	symbols: bool,
+}
+impl<T> Sanitize<T> {
+	/// Writes newlines as `␊` and carriage returns as `␍` instead of backslash escapes.
+	///
+	/// ```
+	/// let s = fmtools::sanitize("a\r\nb").symbols().to_string();
+	/// assert_eq!(s, "a␍␊b");
+	/// ```
+	#[inline]
+	pub fn symbols(mut self) -> Self {
+		self.symbols = true;
+		self
+	}
+}
+impl<T: fmt::Display> fmt::Display for Sanitize<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::write(&mut SanitizeWriter { f, symbols: self.symbols }, format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display] Sanitize<T>);
+
+struct SanitizeWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	symbols: bool,
+}
+impl fmt::Write for SanitizeWriter<'_, '_> {
+	fn write_str(&mut self, mut s: &str) -> fmt::Result {
+		while let Some((i, chr)) = s.char_indices().find(|&(_, chr)| chr.is_control()) {
+			self.f.write_str(&s[..i])?;
+			match chr {
+				'\n' => self.f.write_str(if self.symbols { "\u{240A}" } else { "\\n" })?,
+				'\r' => self.f.write_str(if self.symbols { "\u{240D}" } else { "\\r" })?,
+				_ => write!(self.f, "\\x{:02x}", chr as u32)?,
+			}
+			s = &s[i + chr.len_utf8()..];
+		}
+		self.f.write_str(s)
+	}
+}
+
+/// Replaces the non-ASCII chars in the output for sinks which only accept 7-bit ASCII.
+///
+/// ```
+/// let s = fmtools::ascii("café → 5€").to_string();
+/// assert_eq!(s, r"caf\u{00e9} \u{2192} 5\u{20ac}");
+/// assert_eq!(fmtools::ascii("café → 5€").replace().to_string(), "caf? ? 5?");
+/// assert_eq!(fmtools::ascii("café → 5€").transliterate().to_string(), "cafe ? 5?");
+/// ```
+///
+/// By default every non-ASCII char is written as a `\u{XXXX}` escape with at least four hex digits.
+/// The ASCII chars, including control characters and backslashes, are written unchanged.
+/// Combine with [`sanitize`] to escape the control characters as well.
+///
+/// The output never contains bytes of `0x80` and above.
+/// The output is streamed, chars are never split across writes as they are written as whole `str` slices.
+#[inline]
+pub fn ascii<T: fmt::Display>(value: T) -> Ascii<T> {
+	Ascii { value, mode: AsciiMode::Escape }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum AsciiMode {
+	Escape,
+	Replace,
+	Transliterate,
+}
+
+/// Displayable object returned by [`ascii`].
+#[derive(Copy, Clone)]
+pub struct Ascii<T> {
+	value: T,
+	mode: AsciiMode,
+}
+impl<T> Ascii<T> {
+	/// Writes every non-ASCII char as `?`.
+	#[inline]
+	pub fn replace(mut self) -> Self {
+		self.mode = AsciiMode::Replace;
+		self
+	}
+	/// Writes the Latin-1 chars as their closest ASCII equivalent and every other non-ASCII char as `?`.
+	///
+	/// ```
+	/// let s = fmtools::ascii("Ærøskøbing, Größe ½ £5 日本").transliterate().to_string();
+	/// assert_eq!(s, "AEroskobing, Grosse 1/2 L5 ??");
+	/// ```
+	///
+	/// Letters with diacritics lose them, ligatures and symbols may become multiple chars.
+	#[inline]
+	pub fn transliterate(mut self) -> Self {
+		self.mode = AsciiMode::Transliterate;
+		self
+	}
+}
+impl<T: fmt::Display> fmt::Display for Ascii<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::write(&mut AsciiWriter { f, mode: self.mode }, format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display] Ascii<T>);
+
+// Closest ASCII of the Latin-1 chars from U+00A0 to U+00FF.
+static LATIN1: [&str; 96] = [
+	" ", "!", "c", "L", "?", "Y", "|", "S", "\"", "(c)", "a", "<<", "!", "-", "(R)", "-",
+	"o", "+-", "2", "3", "'", "u", "P", ".", ",", "1", "o", ">>", "1/4", "1/2", "3/4", "?",
+	"A", "A", "A", "A", "A", "A", "AE", "C", "E", "E", "E", "E", "I", "I", "I", "I",
+	"D", "N", "O", "O", "O", "O", "O", "x", "O", "U", "U", "U", "U", "Y", "Th", "ss",
+	"a", "a", "a", "a", "a", "a", "ae", "c", "e", "e", "e", "e", "i", "i", "i", "i",
+	"d", "n", "o", "o", "o", "o", "o", "/", "o", "u", "u", "u", "u", "y", "th", "y",
+];
+
+struct AsciiWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	mode: AsciiMode,
+}
+impl fmt::Write for AsciiWriter<'_, '_> {
+	fn write_str(&mut self, mut s: &str) -> fmt::Result {
+		while let Some(i) = s.bytes().position(|b| !b.is_ascii()) {
+			self.f.write_str(&s[..i])?;
+			let chr = s[i..].chars().next().unwrap_or_default();
+			match self.mode {
+				AsciiMode::Escape => write!(self.f, "\\u{{{:04x}}}", chr as u32)?,
+				AsciiMode::Replace => self.f.write_str("?")?,
+				AsciiMode::Transliterate => match chr as u32 {
+					code @ 0xa0..=0xff => self.f.write_str(LATIN1[code as usize - 0xa0])?,
+					_ => self.f.write_str("?")?,
+				},
+			}
+			s = &s[i + chr.len_utf8()..];
+		}
+		self.f.write_str(s)
+	}
+}
+
+#[test]
+fn tests() {
+	assert_eq!(escape_html("").to_string(), "");
+	assert_eq!(escape_html("plain €").to_string(), "plain €");
+	assert_eq!(escape_html(r#"a & b < c > d "e" 'f'"#).to_string(), "a &amp; b &lt; c &gt; d &quot;e&quot; &#39;f&#39;");
+	assert_eq!(escape_html(crate::fmt!("<"{"&"}">")).to_string(), "&lt;&amp;&gt;");
+	assert_eq!(escape_html("&amp;").to_string(), "&amp;amp;");
+
+	// Terminal and log injection
+	let hostile = "ok\x1b[2J\x1b[H\r\nFAKE\0end\u{85}\u{7f}\t";
+	assert_eq!(sanitize(hostile).to_string(), r"ok\x1b[2J\x1b[H\r\nFAKE\x00end\x85\x7f\x09");
+	assert_eq!(sanitize(hostile).symbols().to_string(), "ok\\x1b[2J\\x1b[H\u{240D}\u{240A}FAKE\\x00end\\x85\\x7f\\x09");
+	assert_eq!(sanitize(crate::fmt!("a"{"\r"}{"\n"}"b")).to_string(), r"a\r\nb");
+
+	// Printable text untouched
+	assert_eq!(sanitize("héllo wörld ✓ \\n \"q\"").to_string(), "héllo wörld ✓ \\n \"q\"");
+	assert_eq!(sanitize("").to_string(), "");
+
+	// ASCII only output
+	let mixed = "plain ASCII\t\\ é ß Ω 日本 😀 \u{80}\u{9f}\u{a0}\u{ff}\u{100}";
+	let escaped = ascii(mixed).to_string();
+	assert_eq!(escaped, "plain ASCII\t\\ \\u{00e9} \\u{00df} \\u{03a9} \\u{65e5}\\u{672c} \\u{1f600} \\u{0080}\\u{009f}\\u{00a0}\\u{00ff}\\u{0100}");
+	let replaced = ascii(mixed).replace().to_string();
+	assert_eq!(replaced, "plain ASCII\t\\ ? ? ? ?? ? ?????");
+	let transliterated = ascii(mixed).transliterate().to_string();
+	assert_eq!(transliterated, "plain ASCII\t\\ e ss ? ?? ? ?? y?");
+	for s in [escaped, replaced, transliterated] {
+		assert!(s.bytes().all(|b| b < 0x80), "{:?}", s);
+	}
+	for code in (0..0x3000).chain(0x1f600..0x1f700) {
+		let Some(chr) = char::from_u32(code) else { continue };
+		let s = crate::format!({ascii(chr)}{ascii(chr).replace()}{ascii(chr).transliterate()});
+		assert!(s.is_ascii(), "{:?}", s);
+	}
+	assert_eq!(ascii(crate::fmt!("a"{"é"}{'€'}"b")).transliterate().to_string(), "ae?b");
+	assert_eq!(ascii("").to_string(), "");
+	assert_eq!(format!("{:>5}", ascii("é")), "\\u{00e9}");
+}
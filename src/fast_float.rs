@@ -0,0 +1,115 @@
+use core::fmt;
+
+/// Displays a float using the shortest representation which round-trips.
+///
+/// Requires the `ryu` feature.
+///
+/// ```
+/// let s = fmtools::format!("x="{fmtools::fast_float(0.1 + 0.2)});
+/// assert_eq!(s, "x=0.30000000000000004");
+/// assert_eq!(fmtools::fast_float(1.0).to_string(), "1.0");
+/// ```
+///
+/// The digits are produced by the [ryu](https://docs.rs/ryu) algorithm, parsing the output results in the identical float.
+/// Very large and very small values are written in exponent notation (eg. `1e30`) at different thresholds than the standard `Debug` implementation.
+///
+/// * Integral values keep a fractional part: `1.0`.
+/// * Negative zero is written as `-0.0`.
+/// * Not a number is written as `NaN`, infinities as `inf` and `-inf`.
+///
+/// The width, fill and alignment specifiers are supported.
+///
+/// The other numeric helpers do not use this as their backend.
+/// [`number`](crate::number()) and the `tiny-float` compact floats round to a given precision which the shortest representation does not provide,
+/// and enabling the feature must not change their output.
+/// Helpers accepting any displayable value compose with it instead:
+///
+/// ```
+/// let s = fmtools::group_frac(fmtools::fast_float(1234.5678), ',', ' ').to_string();
+/// assert_eq!(s, "1,234.567 8");
+/// ```
+#[inline]
+pub fn fast_float<T: Float>(value: T) -> FastFloat<T> {
+	FastFloat { value }
+}
+
+/// Displayable object returned by [`fast_float`].
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct FastFloat<T> {
+	value: T,
+}
+
+/// Primitive floats supported by [`fast_float`].
+pub trait Float: Copy + private::Sealed {}
+
+mod private {
+	pub trait Sealed {
+		fn fmt_shortest(self, f: &mut super::fmt::Formatter) -> super::fmt::Result;
+	}
+}
+
+macro_rules! impl_float {
+	($($ty:ty),*) => {
+		$(
+			impl private::Sealed for $ty {
+				#[inline]
+				fn fmt_shortest(self, f: &mut fmt::Formatter) -> fmt::Result {
+					let mut buf = ryu::Buffer::new();
+					f.pad(buf.format(self))
+				}
+			}
+			impl Float for $ty {}
+		)*
+	};
+}
+impl_float!(f32, f64);
+
+impl<T: Float> fmt::Display for FastFloat<T> {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		private::Sealed::fmt_shortest(self.value, f)
+	}
+}
+debug_as_display!([T: Float] FastFloat<T>);
+
+#[test]
+fn tests() {
+	// Special values
+	assert_eq!(fast_float(f64::NAN).to_string(), "NaN");
+	assert_eq!(fast_float(f64::INFINITY).to_string(), "inf");
+	assert_eq!(fast_float(f64::NEG_INFINITY).to_string(), "-inf");
+	assert_eq!(fast_float(-0.0f64).to_string(), "-0.0");
+	assert_eq!(fast_float(0.0f32).to_string(), "0.0");
+	assert_eq!(fast_float(-1.5f32).to_string(), "-1.5");
+	assert_eq!(format!("[{:>6}]", fast_float(2.5)), "[   2.5]");
+
+	// Known hard cases
+	let hard = [
+		0.1, 0.2, 0.1 + 0.2, 1.0 / 3.0, 5e-324, 2.2250738585072014e-308, f64::from_bits(0x000f_ffff_ffff_ffff),
+		f64::MIN_POSITIVE, f64::MAX, f64::MIN, f64::EPSILON, 9007199254740993.0, 1e23, 8.41e21, 1e-7, 123456789.0,
+	];
+	for value in hard {
+		let s = fast_float(value).to_string();
+		assert_eq!(s.parse::<f64>().unwrap().to_bits(), value.to_bits(), "{}", s);
+	}
+	for value in [f32::MIN_POSITIVE, f32::MAX, 1e-45, 16777217.0, 0.1] {
+		let s = fast_float(value).to_string();
+		assert_eq!(s.parse::<f32>().unwrap().to_bits(), value.to_bits(), "{}", s);
+	}
+
+	// Random sample of bit patterns
+	let mut state = 0x853c49e6748fea9bu64;
+	for _ in 0..10000 {
+		state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+		let value = f64::from_bits(state);
+		if value.is_finite() {
+			let s = fast_float(value).to_string();
+			assert_eq!(s.parse::<f64>().unwrap().to_bits(), value.to_bits(), "{}", s);
+		}
+		let value = f32::from_bits((state >> 32) as u32);
+		if value.is_finite() {
+			let s = fast_float(value).to_string();
+			assert_eq!(s.parse::<f32>().unwrap().to_bits(), value.to_bits(), "{}", s);
+		}
+	}
+}
@@ -0,0 +1,145 @@
+use core::{fmt, str};
+
+/// Displays an integer using a fast two-digits-at-a-time algorithm.
+///
+/// ```
+/// let s = fmtools::format!("id="{fmtools::fast_int(-1234)});
+/// assert_eq!(s, "id=-1234");
+/// ```
+///
+/// The output is identical to the standard `Display` implementation, including the width, fill, alignment and sign specifiers.
+#[inline]
+pub fn fast_int<T: Integer>(value: T) -> FastInt<T> {
+	FastInt { value }
+}
+
+/// Displayable object returned by [`fast_int`].
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct FastInt<T> {
+	value: T,
+}
+
+/// Primitive integers supported by [`fast_int`].
+pub trait Integer: Copy + private::Sealed {}
+
+mod private {
+	pub trait Sealed {
+		/// Writes the digits at the end of the buffer, returns the start of the digits and if the value is non-negative.
+		fn write_digits(self, buf: &mut [u8; 40]) -> (usize, bool);
+	}
+}
+
+const DIGITS: &[u8; 200] = b"\
+	0001020304050607080910111213141516171819\
+	2021222324252627282930313233343536373839\
+	4041424344454647484950515253545556575859\
+	6061626364656667686970717273747576777879\
+	8081828384858687888990919293949596979899";
+
+macro_rules! write_digits {
+	($name:ident, $ty:ty) => {
+		fn $name(mut n: $ty, buf: &mut [u8; 40]) -> usize {
+			let mut pos = buf.len();
+			while n >= 100 {
+				let d = (n % 100) as usize * 2;
+				n /= 100;
+				pos -= 2;
+				buf[pos..pos + 2].copy_from_slice(&DIGITS[d..d + 2]);
+			}
+			if n >= 10 {
+				let d = n as usize * 2;
+				pos -= 2;
+				buf[pos..pos + 2].copy_from_slice(&DIGITS[d..d + 2]);
+			}
+			else {
+				pos -= 1;
+				buf[pos] = b'0' + n as u8;
+			}
+			pos
+		}
+	};
+}
+write_digits!(write_u32, u32);
+write_digits!(write_u64, u64);
+write_digits!(write_u128, u128);
+
+macro_rules! impl_integer {
+	($write:ident, $wide:ty; unsigned $($ty:ty),*) => {
+		$(
+			impl private::Sealed for $ty {
+				#[inline]
+				fn write_digits(self, buf: &mut [u8; 40]) -> (usize, bool) {
+					($write(self as $wide, buf), true)
+				}
+			}
+			impl Integer for $ty {}
+		)*
+	};
+	($write:ident, $wide:ty; signed $($ty:ty),*) => {
+		$(
+			impl private::Sealed for $ty {
+				#[inline]
+				fn write_digits(self, buf: &mut [u8; 40]) -> (usize, bool) {
+					($write(self.unsigned_abs() as $wide, buf), self >= 0)
+				}
+			}
+			impl Integer for $ty {}
+		)*
+	};
+}
+impl_integer!(write_u32, u32; unsigned u8, u16, u32);
+impl_integer!(write_u32, u32; signed i8, i16, i32);
+impl_integer!(write_u64, u64; unsigned u64, usize);
+impl_integer!(write_u64, u64; signed i64, isize);
+impl_integer!(write_u128, u128; unsigned u128);
+impl_integer!(write_u128, u128; signed i128);
+
+impl<T: Integer> fmt::Display for FastInt<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut buf = [0u8; 40];
+		let (mut pos, is_nonnegative) = private::Sealed::write_digits(self.value, &mut buf);
+		// Skip the padding machinery in the common case
+		if f.width().is_none() && !f.sign_plus() {
+			if !is_nonnegative {
+				pos -= 1;
+				buf[pos] = b'-';
+			}
+			return f.write_str(digits(&buf[pos..]));
+		}
+		f.pad_integral(is_nonnegative, "", digits(&buf[pos..]))
+	}
+}
+debug_as_display!([T: Integer] FastInt<T>);
+
+#[inline]
+fn digits(bytes: &[u8]) -> &str {
+	// Only ASCII digits are ever written to the buffer
+	str::from_utf8(bytes).unwrap_or_default()
+}
+
+#[test]
+fn tests() {
+	macro_rules! check {
+		($($ty:ty),*) => {
+			$(
+				for value in [<$ty>::MIN, <$ty>::MIN + 1, <$ty>::MAX - 1, <$ty>::MAX, 0, 1, 9, 10, 11, 99, 100, 101, 127] {
+					assert_eq!(fast_int(value).to_string(), value.to_string());
+				}
+			)*
+		};
+	}
+	check!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+	for value in -1000..=1000 {
+		assert_eq!(fast_int(value).to_string(), value.to_string());
+	}
+	for shift in 0..64 {
+		let value = 1u64 << shift;
+		for value in [value - 1, value, value + 1] {
+			assert_eq!(fast_int(value).to_string(), value.to_string());
+		}
+	}
+
+	// Formatting specifiers
+	assert_eq!(format!("[{:5}][{:<5}][{:+}][{:05}]", fast_int(-42), fast_int(42), fast_int(42), fast_int(-42)), "[  -42][42   ][+42][-0042]");
+}
@@ -0,0 +1,85 @@
+use core::fmt;
+use crate::Align;
+
+/// Pads or truncates the value to exactly the given width.
+///
+/// ```
+/// use fmtools::{fit, Align::*};
+///
+/// let (name, bytes) = ("configuration.toml", 1234);
+/// let s = fmtools::format!("["{fit(12, Left, name)}"|"{fit(6, Right, bytes)}"]");
+/// assert_eq!(s, "[configurati…|  1234]");
+/// ```
+///
+/// Shorter output is padded with spaces according to the alignment.
+/// Longer output is truncated and ends with a suffix, `…` by default.
+///
+/// The width is measured in chars, with the `unicode-width` feature in display columns.
+/// When a wide char does not fit at the cut, the remaining columns are filled with spaces.
+#[inline]
+pub fn fit<T: fmt::Display>(width: usize, align: Align, value: T) -> Fit<'static, T> {
+	Fit { value, width, align, suffix: "…" }
+}
+
+/// Displayable object returned by [`fit`].
+#[derive(Copy, Clone)]
+pub struct Fit<'a, T> {
+	value: T,
+	width: usize,
+	align: Align,
+	suffix: &'a str,
+}
+impl<T> Fit<'_, T> {
+	/// Sets the suffix written when the output is truncated.
+	///
+	/// The suffix is omitted if it is wider than the width.
+	#[inline]
+	pub fn suffix(self, suffix: &str) -> Fit<'_, T> {
+		Fit { value: self.value, width: self.width, align: self.align, suffix }
+	}
+}
+impl<T: fmt::Display> fmt::Display for Fit<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let width = crate::pad::width_of(&self.value)?;
+		if width <= self.width {
+			return crate::pad::write_padded(f, &self.value, self.width - width, self.align);
+		}
+		let mut suffix = self.suffix;
+		let mut suffix_width = crate::width::str_width(suffix);
+		if suffix_width > self.width {
+			suffix = "";
+			suffix_width = 0;
+		}
+		let remaining = crate::truncate::write_truncated(f, &self.value, self.width - suffix_width)?.unwrap_or(0);
+		f.write_str(suffix)?;
+		crate::pad::write_fill(f, ' ', remaining)
+	}
+}
+debug_as_display!([T: fmt::Display] Fit<'_, T>);
+
+#[test]
+fn tests() {
+	use crate::width::str_width;
+
+	let chunked = crate::fmt!({"ab"}{"cdefgh"}).to_string();
+	let inputs = ["", "a", "abcd", "abcde", "abcdef", "héllo wörld", "€€€€€", "€€€€€€", &chunked];
+	for width in 0..8 {
+		for align in [Align::Left, Align::Center, Align::Right] {
+			for input in inputs {
+				let s = fit(width, align, input).to_string();
+				assert_eq!(str_width(&s), width, "{:?} {:?} {}", s, align, width);
+				let s = fit(width, align, input).suffix("...").to_string();
+				assert_eq!(str_width(&s), width, "{:?} {:?} {}", s, align, width);
+			}
+		}
+	}
+
+	assert_eq!(fit(5, Align::Left, "abc").to_string(), "abc  ");
+	assert_eq!(fit(5, Align::Right, "abc").to_string(), "  abc");
+	assert_eq!(fit(5, Align::Center, "abcde").to_string(), "abcde");
+	assert_eq!(fit(5, Align::Right, "abcdef").to_string(), "abcd…");
+	assert_eq!(fit(5, Align::Left, "€€€€€€").suffix("").to_string(), "€€€€€");
+	assert_eq!(fit(2, Align::Left, "abc").suffix("...").to_string(), "ab");
+	#[cfg(feature = "unicode-width")]
+	assert_eq!(fit(6, Align::Left, "日本語x").to_string(), "日本… ");
+}
@@ -0,0 +1,180 @@
+use core::{fmt, ops, str};
+
+/// Stack allocated string with a fixed capacity.
+///
+/// Writing past the capacity truncates the string at a char boundary and sets the [truncated](FixedString::truncated) flag.
+/// Any further writes are ignored until the string is [cleared](FixedString::clear).
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut s = fmtools::FixedString::<8>::new();
+/// write!(s, "{}", 42).unwrap();
+/// assert_eq!(s, "42");
+/// assert!(!s.truncated());
+///
+/// write!(s, " is the answer").unwrap();
+/// assert_eq!(s, "42 is th");
+/// assert!(s.truncated());
+/// ```
+///
+/// See [fixed_format!](crate::fixed_format!) to render templates into a fixed string.
+#[derive(Clone)]
+pub struct FixedString<const N: usize> {
+	buf: [u8; N],
+	len: usize,
+	truncated: bool,
+}
+
+impl<const N: usize> FixedString<N> {
+	/// Constructs a new empty string.
+	#[inline]
+	pub const fn new() -> FixedString<N> {
+		FixedString { buf: [0; N], len: 0, truncated: false }
+	}
+	/// Returns the capacity in bytes.
+	#[inline]
+	pub const fn capacity(&self) -> usize {
+		N
+	}
+	/// Returns if any output was discarded because the capacity was exceeded.
+	#[inline]
+	pub const fn truncated(&self) -> bool {
+		self.truncated
+	}
+	/// Clears the string and the truncated flag.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.len = 0;
+		self.truncated = false;
+	}
+	/// Returns the string slice.
+	#[inline]
+	pub fn as_str(&self) -> &str {
+		// Only complete chars are ever written to the buffer
+		str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+	}
+}
+
+impl<const N: usize> Default for FixedString<N> {
+	#[inline]
+	fn default() -> FixedString<N> {
+		FixedString::new()
+	}
+}
+
+impl<const N: usize> fmt::Write for FixedString<N> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if !self.truncated {
+			let n = crate::buf::copy_truncated(&mut self.buf[self.len..], s);
+			self.len += n;
+			self.truncated = n < s.len();
+		}
+		Ok(())
+	}
+}
+
+impl<const N: usize> ops::Deref for FixedString<N> {
+	type Target = str;
+	#[inline]
+	fn deref(&self) -> &str {
+		self.as_str()
+	}
+}
+impl<const N: usize> AsRef<str> for FixedString<N> {
+	#[inline]
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl<const N: usize> fmt::Display for FixedString<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self.as_str(), f)
+	}
+}
+impl<const N: usize> fmt::Debug for FixedString<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(self.as_str(), f)
+	}
+}
+
+impl<const N: usize, const M: usize> PartialEq<FixedString<M>> for FixedString<N> {
+	#[inline]
+	fn eq(&self, other: &FixedString<M>) -> bool {
+		self.as_str() == other.as_str()
+	}
+}
+impl<const N: usize> Eq for FixedString<N> {}
+impl<const N: usize> PartialEq<str> for FixedString<N> {
+	#[inline]
+	fn eq(&self, other: &str) -> bool {
+		self.as_str() == other
+	}
+}
+impl<const N: usize> PartialEq<&str> for FixedString<N> {
+	#[inline]
+	fn eq(&self, other: &&str) -> bool {
+		self.as_str() == *other
+	}
+}
+
+/// Renders [fmt syntax](crate::fmt!) into a [`FixedString`].
+///
+/// The first argument is the capacity in bytes.
+///
+/// ```
+/// let id = 42;
+/// let s = fmtools::fixed_format!(16; "id="{id:#x});
+/// assert_eq!(s, "id=0x2a");
+/// assert!(!s.truncated());
+/// ```
+#[macro_export]
+macro_rules! fixed_format {
+	($n:expr; $($tt:tt)*) => {{
+		let mut s = $crate::FixedString::<{ $n }>::new();
+		let _ = ::core::fmt::Write::write_fmt(&mut s, ::core::format_args!("{}", $crate::fmt(|_f| {
+			$crate::__fmt!{_f $($tt)*}
+			Ok(())
+		})));
+		s
+	}};
+}
+
+#[test]
+fn tests() {
+	use std::fmt::Write;
+
+	// Capacity edge cases
+	let s = fixed_format!(5; "ab"{123});
+	assert_eq!(s, "ab123");
+	assert!(!s.truncated());
+	let s = fixed_format!(5; "ab"{1234});
+	assert_eq!(s, "ab123");
+	assert!(s.truncated());
+	let s = fixed_format!(0; "");
+	assert_eq!(s, "");
+	assert!(!s.truncated());
+	let s = fixed_format!(0; "a");
+	assert_eq!(s, "");
+	assert!(s.truncated());
+
+	// Truncation at a char boundary
+	let s = fixed_format!(4; "ab"'€');
+	assert_eq!(s, "ab");
+	assert!(s.truncated());
+	assert_eq!(s.len(), 2);
+
+	// Truncated strings ignore further writes
+	let mut s = FixedString::<4>::new();
+	s.write_str("abc€").unwrap();
+	s.write_str("d").unwrap();
+	assert_eq!(s, "abc");
+
+	// Reuse
+	s.clear();
+	assert!(!s.truncated());
+	write!(s, "{}", 42).unwrap();
+	assert_eq!(s.as_str(), "42");
+	assert_eq!(format!("{:>4}|{:?}", s, s), "  42|\"42\"");
+}
@@ -0,0 +1,108 @@
+use core::cell::RefCell;
+use core::fmt;
+
+/// Returns a displayable object using the mutable closure argument as its implementation.
+///
+/// Like [`fmt()`](crate::fmt()) but accepts `FnMut` closures which mutate their captured state while formatting:
+///
+/// ```
+/// let mut line = 0;
+/// let numbered = fmtools::fmt_mut(move |f| {
+/// 	line += 1;
+/// 	write!(f, "{:>3}| ", line)
+/// });
+///
+/// let s = fmtools::format!({numbered}"first\n"{numbered}"second\n");
+/// assert_eq!(s, "  1| first\n  2| second\n");
+/// ```
+///
+/// The state persists across renders, displaying the object twice gives different output when the closure changes its state.
+/// This lets the closure drain iterators which cannot be cloned:
+///
+/// ```
+/// let mut rows = vec!["a", "b", "c"].into_iter();
+/// let next_row = fmtools::fmt_mut(move |f| match rows.next() {
+/// 	Some(row) => f.write_str(row),
+/// 	None => f.write_str("-"),
+/// });
+///
+/// assert_eq!(next_row.to_string(), "a");
+/// assert_eq!(fmtools::format!({next_row}{next_row}{next_row}), "bc-");
+/// ```
+///
+/// The closure is kept in a [`RefCell`] and borrowed mutably while it runs.
+/// Displaying the object again from within its own closure returns an error instead of panicking.
+///
+/// The closure is invoked with the formatter as its argument, including the formatting options.
+/// Unlike [`FmtFn`](crate::FmtFn) the width and precision are not applied to the whole output, which requires rendering it twice.
+#[inline]
+pub fn fmt_mut<F: FnMut(&mut fmt::Formatter) -> fmt::Result>(closure: F) -> FmtMut<F> {
+	FmtMut { closure: RefCell::new(closure) }
+}
+
+/// Displayable object returned by [`fmt_mut`].
+pub struct FmtMut<F> {
+	closure: RefCell<F>,
+}
+impl<F> FmtMut<F> {
+	/// Unwraps the closure with its current state.
+	#[inline]
+	pub fn into_inner(self) -> F {
+		self.closure.into_inner()
+	}
+}
+impl<F: FnMut(&mut fmt::Formatter) -> fmt::Result> fmt::Display for FmtMut<F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut closure = self.closure.try_borrow_mut().map_err(|_| fmt::Error)?;
+		(closure)(f)
+	}
+}
+debug_as_display!([F: FnMut(&mut fmt::Formatter) -> fmt::Result] FmtMut<F>);
+
+#[test]
+fn tests() {
+	// Counter producing different output on every render
+	let mut count = 0;
+	let counter = fmt_mut(|f| {
+		count += 1;
+		write!(f, "#{}", count)
+	});
+	assert_eq!(counter.to_string(), "#1");
+	assert_eq!(counter.to_string(), "#2");
+	assert_eq!(crate::format!({counter}", "{counter:?}), "#3, #4");
+	assert_eq!(count, 4);
+
+	// Draining a non-Clone iterator
+	let mut items = vec![String::from("x"), String::from("y"), String::from("z")].into_iter();
+	let drain = fmt_mut(move |f| {
+		if let Some(first) = items.next() {
+			f.write_str(&first)?;
+		}
+		for item in &mut items {
+			write!(f, ",{}", item)?;
+		}
+		Ok(())
+	});
+	assert_eq!(drain.to_string(), "x,y,z");
+	assert_eq!(drain.to_string(), "");
+	assert_eq!(fmt_mut(drain.into_inner()).to_string(), "");
+
+	// Formatting options are passed to the closure
+	let mut n = 0;
+	let options = fmt_mut(|f| { n += 1; fmt::Display::fmt(&n, f) });
+	assert_eq!(format!("[{:>3}|{:<3}]", options, options), "[  1|2  ]");
+
+	// Re-entrant renders fail instead of panicking
+	use core::cell::Cell;
+	let this: Cell<Option<&dyn fmt::Display>> = Cell::new(None);
+	let reentrant = fmt_mut(|f| match this.get() {
+		Some(inner) => write!(f, "<{}>", inner),
+		None => f.write_str("end"),
+	});
+	this.set(Some(&reentrant));
+	let mut s = String::new();
+	assert!(fmt::write(&mut s, format_args!("{}", reentrant)).is_err());
+	assert_eq!(s, "<");
+	this.set(None);
+	assert_eq!(reentrant.to_string(), "end");
+}
@@ -0,0 +1,102 @@
+use core::fmt;
+
+/// Displays the sections with the separator between the sections which produce output.
+///
+/// ```
+/// let (street, city, zip) = ("Main St 1", "", "12345");
+/// let s = fmtools::glue(", ", &[&street, &city, &zip]).to_string();
+/// assert_eq!(s, "Main St 1, 12345");
+/// ```
+///
+/// The separator is held back and only written when the next non-empty section starts writing,
+/// there is no separator before the first or after the last section with output.
+/// The sections are streamed, nothing is rendered twice.
+///
+/// The `glue` construct of [fmt!](crate::fmt!) renders parenthesized template sections this way:
+///
+/// ```
+/// let (name, age, email) = ("alice", None::<u32>, Some("a@example.com"));
+/// let s = fmtools::format!("{" glue ", " {
+/// 	("name: "{name})
+/// 	(if let Some(age) = age { "age: "{age} })
+/// 	(if let Some(email) = email { "email: "{email} })
+/// } "}");
+/// assert_eq!(s, "{name: alice, email: a@example.com}");
+/// ```
+#[inline]
+pub fn glue<'a>(sep: &'a str, sections: &'a [&'a dyn fmt::Display]) -> Glue<'a> {
+	Glue { sep, sections }
+}
+
+/// Displayable object returned by [`glue`].
+#[derive(Copy, Clone)]
+pub struct Glue<'a> {
+	sep: &'a str,
+	sections: &'a [&'a dyn fmt::Display],
+}
+impl fmt::Display for Glue<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut writer = GlueWriter { f, sep: self.sep, written: false, pending: false };
+		for (i, section) in self.sections.iter().enumerate() {
+			if i > 0 && writer.written {
+				writer.pending = true;
+			}
+			fmt::write(&mut writer, format_args!("{}", section))?;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([] Glue<'_>);
+
+struct GlueWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	sep: &'a str,
+	// Any section has produced output
+	written: bool,
+	// The separator is held back until the next output
+	pending: bool,
+}
+impl fmt::Write for GlueWriter<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if s.is_empty() {
+			return Ok(());
+		}
+		if self.pending {
+			self.pending = false;
+			self.f.write_str(self.sep)?;
+		}
+		self.written = true;
+		self.f.write_str(s)
+	}
+}
+
+#[test]
+fn tests() {
+	// All presence combinations of three optional sections
+	for bits in 0..8 {
+		let (a, b, c) = (bits & 1 != 0, bits & 2 != 0, bits & 4 != 0);
+		let s = crate::format!("[" glue ", " {
+			(if a { "a" })
+			(if b { {"b"} })
+			(if c { "c=" {3} })
+		} "]");
+		let expected: Vec<&str> = [(a, "a"), (b, "b"), (c, "c=3")].iter().filter(|(on, _)| *on).map(|&(_, s)| s).collect();
+		assert_eq!(s, format!("[{}]", expected.join(", ")));
+	}
+
+	// Sections with empty writes and nested control flow
+	let items = [1, 2];
+	let s = crate::format!(glue " | " {
+		({""}{""})
+		(for i in &items { {i} })
+		()
+		(let n = items.len(); "n=" {n})
+	});
+	assert_eq!(s, "12 | n=2");
+	assert_eq!(crate::format!(glue ", " {}), "");
+	assert_eq!(crate::format!(? glue "," { ({"a"}) ({1}) }), "\"a\",1");
+
+	// Helper with displayable sections
+	assert_eq!(glue("-", &[&"", &1, &"", &crate::nothing(), &'x']).to_string(), "1-x");
+	assert_eq!(glue("-", &[]).to_string(), "");
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+/// Wraps case-insensitive occurrences of the needle in the output with a prefix and suffix.
+///
+/// ```
+/// let line = "Error: the error handler failed";
+/// let s = fmtools::highlight("error", "\x1b[7m", "\x1b[27m", line).to_string();
+/// assert_eq!(s, "\x1b[7mError\x1b[27m: the \x1b[7merror\x1b[27m handler failed");
+/// ```
+///
+/// Matches are found in the streamed output, including matches split across multiple writes.
+/// Only text which may still turn out to be a match is held back.
+///
+/// Matching compares the lowercase forms of every char.
+/// Matches do not overlap, the search continues after the end of the previous match.
+#[inline]
+pub fn highlight<'a, T: fmt::Display>(needle: &'a str, prefix: &'a str, suffix: &'a str, value: T) -> Highlight<'a, T> {
+	Highlight { value, needle, needles: &[], prefix, suffix }
+}
+
+/// Wraps case-insensitive occurrences of any of the needles in the output with a prefix and suffix.
+///
+/// ```
+/// let s = fmtools::highlight_any(&["cat", "category"], "[", "]", "Categorize the cat").to_string();
+/// assert_eq!(s, "[Cat]egorize the [cat]");
+///
+/// let s = fmtools::highlight_any(&["cat", "category"], "[", "]", "a category").to_string();
+/// assert_eq!(s, "a [category]");
+/// ```
+///
+/// When multiple needles match at the same position the longest match wins.
+///
+/// See [`highlight`] for more information.
+#[inline]
+pub fn highlight_any<'a, T: fmt::Display>(needles: &'a [&'a str], prefix: &'a str, suffix: &'a str, value: T) -> Highlight<'a, T> {
+	Highlight { value, needle: "", needles, prefix, suffix }
+}
+
+/// Displayable object returned by [`highlight`] and [`highlight_any`].
+#[derive(Copy, Clone)]
+pub struct Highlight<'a, T> {
+	value: T,
+	needle: &'a str,
+	needles: &'a [&'a str],
+	prefix: &'a str,
+	suffix: &'a str,
+}
+impl<T: fmt::Display> fmt::Display for Highlight<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut writer = HighlightWriter {
+			f,
+			needles: self.needles,
+			needle: self.needle,
+			prefix: self.prefix,
+			suffix: self.suffix,
+			pending: String::new(),
+		};
+		fmt::write(&mut writer, format_args!("{}", self.value))?;
+		writer.resolve(true)
+	}
+}
+debug_as_display!([T: fmt::Display] Highlight<'_, T>);
+
+struct HighlightWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	needle: &'a str,
+	needles: &'a [&'a str],
+	prefix: &'a str,
+	suffix: &'a str,
+	// Output held back as it may still turn out to be a match
+	pending: String,
+}
+impl HighlightWriter<'_, '_> {
+	fn needles(&self) -> impl Iterator<Item = &str> {
+		Some(self.needle).into_iter().chain(self.needles.iter().copied()).filter(|needle| !needle.is_empty())
+	}
+
+	// Writes the pending text which can no longer change, at the end of the output everything is written.
+	fn resolve(&mut self, end: bool) -> fmt::Result {
+		while !self.pending.is_empty() {
+			let mut matched = 0;
+			let mut extends = false;
+			for needle in self.needles() {
+				match match_prefix(needle, &self.pending) {
+					Match::Full(len) => matched = usize::max(matched, len),
+					Match::Partial => extends = true,
+					Match::None => (),
+				}
+			}
+			// Wait for more output if a longer match is still possible
+			if extends && !end {
+				break;
+			}
+			if matched > 0 {
+				self.f.write_str(self.prefix)?;
+				self.f.write_str(&self.pending[..matched])?;
+				self.f.write_str(self.suffix)?;
+				self.pending.drain(..matched);
+			}
+			else {
+				let len = self.pending.chars().next().map_or(0, char::len_utf8);
+				self.f.write_str(&self.pending[..len])?;
+				self.pending.drain(..len);
+			}
+		}
+		Ok(())
+	}
+}
+impl fmt::Write for HighlightWriter<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for chr in s.chars() {
+			self.pending.push(chr);
+			self.resolve(false)?;
+		}
+		Ok(())
+	}
+}
+
+enum Match {
+	// The text starts with the needle, the length of the match in bytes
+	Full(usize),
+	// The text is a prefix of the needle
+	Partial,
+	None,
+}
+
+fn match_prefix(needle: &str, text: &str) -> Match {
+	let mut text_chars = text.char_indices();
+	for a in needle.chars() {
+		match text_chars.next() {
+			Some((_, b)) if a == b || a.to_lowercase().eq(b.to_lowercase()) => (),
+			Some(_) => return Match::None,
+			None => return Match::Partial,
+		}
+	}
+	Match::Full(text_chars.next().map_or(text.len(), |(i, _)| i))
+}
+
+#[test]
+fn tests() {
+	fn mark<T: fmt::Display>(needle: &str, value: T) -> String {
+		highlight(needle, "[", "]", value).to_string()
+	}
+
+	// Matches at chunk boundaries
+	assert_eq!(mark("needle", crate::fmt!({"a nee"}{"dle in a hay"}{"stack"})), "a [needle] in a haystack");
+	assert_eq!(mark("needle", crate::fmt!({"a n"}{"e"}{"e"}{"d"}{"l"}{"e"})), "a [needle]");
+	assert_eq!(mark("needle", crate::fmt!({"a needl"})), "a needl");
+
+	// No matches
+	assert_eq!(mark("xyz", "nothing to see"), "nothing to see");
+	assert_eq!(mark("", "empty needle"), "empty needle");
+	assert_eq!(mark("abc", ""), "");
+
+	// Adjacent and overlapping candidates
+	assert_eq!(mark("ab", "ababab"), "[ab][ab][ab]");
+	assert_eq!(mark("aab", "aaab"), "a[aab]");
+	assert_eq!(mark("aa", "aaa"), "[aa]a");
+	assert_eq!(mark("abac", crate::fmt!({"ab"}{"abac"})), "ab[abac]");
+
+	// Case-insensitive
+	assert_eq!(mark("ÉTÉ", "un été chaud"), "un [été] chaud");
+	assert_eq!(mark("rust", "RUST Rust rUsT"), "[RUST] [Rust] [rUsT]");
+
+	// Multiple needles
+	let needles = ["foo", "foobar", "bar"];
+	assert_eq!(highlight_any(&needles, "<", ">", "foobaz foobar bar").to_string(), "<foo>baz <foobar> <bar>");
+	assert_eq!(highlight_any(&needles, "<", ">", crate::fmt!({"foob"}{"ar"})).to_string(), "<foobar>");
+	assert_eq!(highlight_any(&[], "<", ">", "text").to_string(), "text");
+}
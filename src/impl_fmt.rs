@@ -0,0 +1,328 @@
+/// Implements `Display` for a type using [fmt syntax](crate::fmt!).
+///
+/// ```
+/// use std::fmt;
+///
+/// struct Point<T> {
+/// 	x: T,
+/// 	y: T,
+/// }
+///
+/// fmtools::impl_display! {
+/// 	for<T: fmt::Display> Point<T>;
+/// 	|self, f| "("{self.x}", "{self.y}")"
+/// }
+///
+/// assert_eq!(Point { x: 1, y: 2 }.to_string(), "(1, 2)");
+/// ```
+///
+/// The `self` and formatter arguments are named explicitly, the formatter can be used to inspect the formatting flags.
+/// The body supports the complete fmt syntax including control flow and escape hatches.
+///
+/// Generic parameters are listed after `for` and may not contain angle brackets, more complex bounds go in the optional where clause:
+///
+/// ```
+/// use std::fmt;
+///
+/// struct List<'a, T>(&'a [T]);
+///
+/// fmtools::impl_display! {
+/// 	for<'a, T> List<'a, T> where T: fmt::Display, Vec<T>: Clone;
+/// 	|self, f|
+/// 	if f.alternate() { "list: " }
+/// 	{fmtools::join(", ", self.0)}
+/// }
+///
+/// assert_eq!(format!("{:#}", List(&[1, 2, 3])), "list: 1, 2, 3");
+/// ```
+///
+/// The implementation writes directly to the formatter and only refers to `core`, it can be used in `no_std` crates:
+///
+/// ```
+/// #![no_std]
+/// # extern crate std;
+/// # use std::string::ToString;
+/// struct Celsius(f32);
+///
+/// fmtools::impl_display! {
+/// 	for Celsius;
+/// 	|self, f| {self.0:.1}" °C"
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Celsius(21.5).to_string(), "21.5 °C");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_display {
+	($($tt:tt)*) => {
+		$crate::__impl_fmt!{@start [Display] $($tt)*}
+	};
+}
+
+/// Implements `Debug` for a type using [fmt syntax](crate::fmt!).
+///
+/// See [impl_display!](crate::impl_display!) for the syntax.
+///
+/// ```
+/// enum Shape {
+/// 	Circle(f32),
+/// 	Square(f32),
+/// }
+///
+/// fmtools::impl_debug! {
+/// 	for Shape;
+/// 	|self, f| match self {
+/// 		Shape::Circle(r) => "Circle(r: "{r}")",
+/// 		Shape::Square(s) => "Square(s: "{s}")",
+/// 	}
+/// }
+///
+/// assert_eq!(format!("{:?}", Shape::Circle(1.5)), "Circle(r: 1.5)");
+/// ```
+#[macro_export]
+macro_rules! impl_debug {
+	($($tt:tt)*) => {
+		$crate::__impl_fmt!{@start [Debug] $($tt)*}
+	};
+}
+
+/// Implements several formatting traits for a type using [fmt syntax](crate::fmt!).
+///
+/// See [impl_display!](crate::impl_display!) for the syntax.
+///
+/// ```
+/// struct Id(u32);
+///
+/// fmtools::impl_fmt! {
+/// 	Display, Debug for Id;
+/// 	|self, f| "#"{self.0}
+/// }
+///
+/// assert_eq!(format!("{} {:?}", Id(1), Id(2)), "#1 #2");
+/// ```
+#[macro_export]
+macro_rules! impl_fmt {
+	($($tr:ident),+ for $($tt:tt)*) => {
+		$crate::__impl_fmt!{@start [$($tr)+] for $($tt)*}
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_fmt {
+	// generics
+	(@start [$($tr:ident)+] for < $($tail:tt)*) => {
+		$crate::__impl_fmt!{@gen [$($tr)+] [] $($tail)*}
+	};
+	(@start [$($tr:ident)+] for $($tail:tt)*) => {
+		$crate::__impl_fmt!{@ty [$($tr)+] [] $($tail)*}
+	};
+	(@gen [$($tr:ident)+] [$($g:tt)*] > $($tail:tt)*) => {
+		$crate::__impl_fmt!{@ty [$($tr)+] [$($g)*] $($tail)*}
+	};
+	(@gen [$($tr:ident)+] [$($g:tt)*] $nom:tt $($tail:tt)*) => {
+		$crate::__impl_fmt!{@gen [$($tr)+] [$($g)* $nom] $($tail)*}
+	};
+
+	// type
+	(@ty [$($tr:ident)+] [$($g:tt)*] $ty:ty; $($tail:tt)*) => {
+		$crate::__impl_fmt!{@impl [$($tr)+] [$($g)*] [$ty] [] $($tail)*}
+	};
+	(@ty [$($tr:ident)+] [$($g:tt)*] $ty:ty where $($tail:tt)*) => {
+		$crate::__impl_fmt!{@where [$($tr)+] [$($g)*] [$ty] [] $($tail)*}
+	};
+
+	// where clause
+	(@where [$($tr:ident)+] [$($g:tt)*] [$ty:ty] [$($w:tt)*] ; $($tail:tt)*) => {
+		$crate::__impl_fmt!{@impl [$($tr)+] [$($g)*] [$ty] [$($w)*] $($tail)*}
+	};
+	(@where [$($tr:ident)+] [$($g:tt)*] [$ty:ty] [$($w:tt)*] $nom:tt $($tail:tt)*) => {
+		$crate::__impl_fmt!{@where [$($tr)+] [$($g)*] [$ty] [$($w)* $nom] $($tail)*}
+	};
+
+	// impl
+	(@impl [$tr:ident $($more:ident)*] [$($g:tt)*] [$ty:ty] [$($w:tt)*] |$self_:tt, $f:ident| $($body:tt)*) => {
+		impl<$($g)*> ::core::fmt::$tr for $ty where $($w)* {
+			fn fmt(&$self_, $f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				$crate::__fmt!{$f $($body)*}
+				Ok(())
+			}
+		}
+		$crate::__impl_fmt!{@impl [$($more)*] [$($g)*] [$ty] [$($w)*] |$self_, $f| $($body)*}
+	};
+	(@impl [] [$($g:tt)*] [$ty:ty] [$($w:tt)*] |$self_:tt, $f:ident| $($body:tt)*) => {};
+}
+
+/// Defines a struct with `Display` and `Debug` implemented using [fmt syntax](crate::fmt!).
+///
+/// The fields are available by reference as variables in the template.
+///
+/// ```
+/// fmtools::display_struct! {
+/// 	#[derive(Clone)]
+/// 	pub struct UserLine {
+/// 		pub name: String,
+/// 		pub id: u64,
+/// 	}
+/// 	=> "user "{name}" (#"{id}")"
+/// }
+///
+/// let user = UserLine { name: String::from("alice"), id: 42 };
+/// assert_eq!(user.to_string(), "user alice (#42)");
+/// assert_eq!(format!("{:?}", user.clone()), "user alice (#42)");
+/// ```
+///
+/// The result is a nameable, constructible displayable object which can be stored in other types.
+///
+/// Lifetimes come before the type parameters, which may have trait bounds joined with `+`.
+/// Bounds with generic arguments or lifetimes go in the optional where clause:
+///
+/// ```
+/// use std::fmt;
+///
+/// fmtools::display_struct! {
+/// 	struct Labeled<'a, L: fmt::Display + Clone, T> where T: AsRef<[u8]> {
+/// 		label: L,
+/// 		name: &'a str,
+/// 		value: T,
+/// 	}
+/// 	=> {label}" "{name}": "{value.as_ref():?}
+/// }
+///
+/// let value = Labeled { label: "bytes", name: "answer", value: vec![4, 2] };
+/// assert_eq!(value.to_string(), "bytes answer: [4, 2]");
+/// ```
+#[macro_export]
+macro_rules! display_struct {
+	(
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident <$($lt:lifetime),+ $(,)?> $($tail:tt)*
+	) => {
+		$crate::__display_struct!{
+			[$(#[$attr])* $vis] $name [$($lt),+] [$($lt),+] [] $($tail)*
+		}
+	};
+	(
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident <$($lt:lifetime,)* $($T:ident $(: $($bound:ident)::+ $(+ $($bounds:ident)::+)*)?),+ $(,)?> $($tail:tt)*
+	) => {
+		$crate::__display_struct!{
+			[$(#[$attr])* $vis] $name [$($lt,)* $($T $(: $($bound)::+ $(+ $($bounds)::+)*)?),+] [$($lt,)* $($T),+] [] $($tail)*
+		}
+	};
+	(
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident $($tail:tt)*
+	) => {
+		$crate::__display_struct!{
+			[$(#[$attr])* $vis] $name [] [] [] $($tail)*
+		}
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __display_struct {
+	(
+		[$($head:tt)*] $name:ident [$($g:tt)*] [$($a:tt)*] [$($w:tt)*]
+		{ $($(#[$fattr:meta])* $fvis:vis $field:ident: $fty:ty),* $(,)? }
+		=> $($tmpl:tt)*
+	) => {
+		$($head)* struct $name<$($g)*> where $($w)* {
+			$($(#[$fattr])* $fvis $field: $fty,)*
+		}
+		impl<$($g)*> ::core::fmt::Display for $name<$($a)*> where $($w)* {
+			fn fmt(&self, _f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				#[allow(unused_variables)]
+				let Self { $($field),* } = self;
+				$crate::__fmt!{_f $($tmpl)*}
+				Ok(())
+			}
+		}
+		impl<$($g)*> ::core::fmt::Debug for $name<$($a)*> where $($w)* {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				::core::fmt::Display::fmt(self, f)
+			}
+		}
+	};
+	([$($head:tt)*] $name:ident [$($g:tt)*] [$($a:tt)*] [$($w:tt)*] where $($tail:tt)*) => {
+		$crate::__display_struct!{[$($head)*] $name [$($g)*] [$($a)*] [$($w)*] $($tail)*}
+	};
+	([$($head:tt)*] $name:ident [$($g:tt)*] [$($a:tt)*] [$($w:tt)*] $nom:tt $($tail:tt)*) => {
+		$crate::__display_struct!{[$($head)*] $name [$($g)*] [$($a)*] [$($w)* $nom] $($tail)*}
+	};
+}
+
+#[test]
+fn tests() {
+	use std::fmt;
+
+	struct Pair<K, V> {
+		key: K,
+		value: V,
+	}
+	impl_fmt! {
+		Display, Debug for<K: fmt::Display, V> Pair<K, V> where V: fmt::Debug + Clone;
+		|self, f|
+		{self.key}"="
+		if f.alternate() { {self.value:#?} }
+		else { {self.value:?} }
+	}
+	let pair = Pair { key: "a", value: Some(1) };
+	assert_eq!(pair.to_string(), "a=Some(1)");
+	assert_eq!(format!("{:?}", pair), "a=Some(1)");
+	assert_eq!(format!("{:#}", pair), "a=Some(\n    1,\n)");
+
+	enum Token {
+		Number(i32),
+		Ident(&'static str),
+		Eof,
+	}
+	impl_display! {
+		for Token;
+		|self, f| match self {
+			Token::Number(n) => "number "{n},
+			Token::Ident(name) => {
+				"ident "
+				|f| f.write_str(name)?;
+			}
+			Token::Eof => "eof",
+		}
+	}
+	assert_eq!(Token::Number(42).to_string(), "number 42");
+	assert_eq!(Token::Ident("foo").to_string(), "ident foo");
+	assert_eq!(Token::Eof.to_string(), "eof");
+
+	display_struct! {
+		#[derive(Clone)]
+		struct Entry<T> where T: fmt::Display {
+			name: &'static str,
+			value: T,
+		}
+		=> {name}
+		if !name.is_empty() { "=" }
+		{value}
+	}
+	let entries = vec![Entry { name: "a", value: 1 }, Entry { name: "", value: 2 }];
+	assert_eq!(crate::join(", ", &entries).to_string(), "a=1, 2");
+	assert_eq!(format!("{:?}", entries[0].clone()), "a=1");
+
+	display_struct! {
+		struct Borrowed<'a, 'b, T: fmt::Display + Clone, U: core::fmt::Debug,> {
+			name: &'a str,
+			tag: &'b T,
+			extra: U,
+		}
+		=> {name}"#"{tag}" "{extra:?}
+	}
+	assert_eq!(Borrowed { name: "x", tag: &1, extra: ("y",) }.to_string(), "x#1 (\"y\",)");
+
+	display_struct! {
+		struct Quoted<'a> {
+			text: &'a str,
+		}
+		=> "["{text}"]"
+	}
+	assert_eq!(Quoted { text: "t" }.to_string(), "[t]");
+}
@@ -0,0 +1,186 @@
+use core::fmt;
+
+/// Prefixes every line of the output.
+///
+/// ```
+/// let children = ["a", "b"];
+/// let tree = fmtools::fmt!(
+/// 	"root\n"
+/// 	{fmtools::indent("  ", fmtools::fmt!(for child in &children { {child}"\n" }))}
+/// );
+/// assert_eq!(tree.to_string(), "root\n  a\n  b\n");
+/// ```
+///
+/// The prefix is written lazily before the first char of every line, lines end at `'\n'` and `"\r\n"` is kept intact.
+/// Empty output stays empty and a trailing newline does not produce a dangling prefix.
+/// The output is streamed to the formatter without allocating, nesting the indentation adds up the prefixes:
+///
+/// ```
+/// let inner = fmtools::indent("- ", "x\ny");
+/// assert_eq!(fmtools::indent("  ", inner).to_string(), "  - x\n  - y");
+/// ```
+///
+/// See [`IndentWriter`] to indent the writes from an escape hatch.
+#[inline]
+pub fn indent<'a, T: fmt::Display>(prefix: &'a str, value: T) -> Indent<'a, T> {
+	Indent { value, prefix }
+}
+
+/// Displayable object returned by [`indent`] and [`DisplayExt::indented`](crate::DisplayExt::indented).
+#[derive(Copy, Clone)]
+pub struct Indent<'a, T> {
+	value: T,
+	prefix: &'a str,
+}
+impl<T: fmt::Display> fmt::Display for Indent<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut writer = IndentWriter::new(f, self.prefix);
+		fmt::write(&mut writer, format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display] Indent<'_, T>);
+
+/// Prefixes the first line and the remaining lines of the output differently.
+///
+/// ```
+/// let reply = "Sounds good.\nSee you then.";
+/// let s = fmtools::prefix_lines("> ", "> ", reply).to_string();
+/// assert_eq!(s, "> Sounds good.\n> See you then.");
+///
+/// let doc = fmtools::prefix_lines("/// ", "///\t", "Summary\nDetails\n").to_string();
+/// assert_eq!(doc, "/// Summary\n///\tDetails\n");
+/// ```
+///
+/// The prefix is written lazily before the first char of every line, the output may arrive in arbitrary chunks.
+/// Empty output stays empty and a trailing newline does not produce a dangling prefix.
+/// Empty lines are prefixed by default, use [`skip_empty`](PrefixLines::skip_empty) to leave them empty:
+///
+/// ```
+/// let block = "key:\n\n  value\n";
+/// let s = fmtools::prefix_lines("- ", "  ", block).skip_empty().to_string();
+/// assert_eq!(s, "- key:\n\n    value\n");
+/// ```
+///
+/// Using the same prefix for every line is the same as [`indent`].
+#[inline]
+pub fn prefix_lines<'a, T: fmt::Display>(first: &'a str, rest: &'a str, value: T) -> PrefixLines<'a, T> {
+	PrefixLines { value, first, rest, skip_empty: false }
+}
+
+/// Displayable object returned by [`prefix_lines`].
+#[derive(Copy, Clone)]
+pub struct PrefixLines<'a, T> {
+	value: T,
+	first: &'a str,
+	rest: &'a str,
+	skip_empty: bool,
+}
+impl<T> PrefixLines<'_, T> {
+	/// Writes empty lines without the prefix.
+	#[inline]
+	pub fn skip_empty(mut self) -> Self {
+		self.skip_empty = true;
+		self
+	}
+}
+impl<T: fmt::Display> fmt::Display for PrefixLines<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut writer = IndentWriter { w: f, prefix: self.first, rest: self.rest, skip_empty: self.skip_empty, line_start: true };
+		fmt::write(&mut writer, format_args!("{}", self.value))
+	}
+}
+debug_as_display!([T: fmt::Display] PrefixLines<'_, T>);
+
+/// Writer adapter which prefixes every line written through it.
+///
+/// The streaming implementation of [`indent`] for use in escape hatches and helper functions:
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let fields = [("name", "alice"), ("id", "42")];
+/// let s = fmtools::format!(
+/// 	"user {\n"
+/// 	|f| {
+/// 		let mut w = fmtools::IndentWriter::new(f, "\t");
+/// 		for (key, value) in fields {
+/// 			writeln!(w, "{}: {}", key, value)?;
+/// 		}
+/// 	}
+/// 	"}"
+/// );
+/// assert_eq!(s, "user {\n\tname: alice\n\tid: 42\n}");
+/// ```
+///
+/// The state of the line is kept between writes, lines may be written in arbitrary chunks.
+pub struct IndentWriter<'a, W: ?Sized> {
+	w: &'a mut W,
+	prefix: &'a str,
+	rest: &'a str,
+	skip_empty: bool,
+	line_start: bool,
+}
+impl<'a, W: fmt::Write + ?Sized> IndentWriter<'a, W> {
+	/// Wraps the writer, the prefix is written before the first char of every line.
+	#[inline]
+	pub fn new(w: &'a mut W, prefix: &'a str) -> IndentWriter<'a, W> {
+		IndentWriter { w, prefix, rest: prefix, skip_empty: false, line_start: true }
+	}
+}
+impl<W: fmt::Write + ?Sized> fmt::Write for IndentWriter<'_, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for line in s.split_inclusive('\n') {
+			if self.line_start {
+				if !(self.skip_empty && line == "\n") {
+					self.w.write_str(self.prefix)?;
+				}
+				self.prefix = self.rest;
+			}
+			self.w.write_str(line)?;
+			self.line_start = line.ends_with('\n');
+		}
+		Ok(())
+	}
+}
+
+#[test]
+fn tests() {
+	assert_eq!(indent("> ", "a\nb").to_string(), "> a\n> b");
+	assert_eq!(indent("> ", "a\nb\n").to_string(), "> a\n> b\n");
+	assert_eq!(indent("> ", "a\n\nb").to_string(), "> a\n> \n> b");
+	assert_eq!(indent("> ", "").to_string(), "");
+	assert_eq!(indent("> ", crate::fmt!("a"{"\n"}"b"{"c\n"})).to_string(), "> a\n> bc\n");
+
+	// Multi-line templates, nesting and carriage returns
+	let items = ["x", "y"];
+	let body = crate::fmt!("list:\n" for item in &items { "- "{item}"\n" });
+	assert_eq!(indent("  ", body).to_string(), "  list:\n  - x\n  - y\n");
+	assert_eq!(indent("  ", indent("  ", body)).to_string(), "    list:\n    - x\n    - y\n");
+	assert_eq!(indent("# ", indent("", "")).to_string(), "");
+	assert_eq!(indent("| ", crate::fmt!()).to_string(), "");
+	assert_eq!(indent("| ", "a\r\nb\r\n").to_string(), "| a\r\n| b\r\n");
+
+	// Writer adapter
+	let mut s = String::new();
+	let mut w = IndentWriter::new(&mut s, "> ");
+	fmt::Write::write_str(&mut w, "ab").unwrap();
+	fmt::Write::write_str(&mut w, "c\nd").unwrap();
+	fmt::Write::write_str(&mut w, "\n").unwrap();
+	assert_eq!(s, "> abc\n> d\n");
+
+	// First line and continuation prefixes
+	assert_eq!(prefix_lines("1. ", "   ", "a\nb\nc").to_string(), "1. a\n   b\n   c");
+	assert_eq!(prefix_lines("> ", "  ", "").to_string(), "");
+	assert_eq!(prefix_lines("> ", "  ", "a").to_string(), "> a");
+
+	// Leading and trailing newlines
+	assert_eq!(prefix_lines("# ", "| ", "\na\n\n").to_string(), "# \n| a\n| \n");
+	assert_eq!(prefix_lines("# ", "| ", "\na\n\n").skip_empty().to_string(), "\n| a\n\n");
+	assert_eq!(prefix_lines("# ", "| ", "\n\n").skip_empty().to_string(), "\n\n");
+
+	// Chunked writes splitting lines
+	let chunks = crate::fmt!("fir" "st" {"\nsec"} {""} "ond\n" {"\n"} "th" {"ird\nfo"} "urth");
+	assert_eq!(prefix_lines("- ", "  ", chunks).to_string(), "- first\n  second\n  \n  third\n  fourth");
+	assert_eq!(prefix_lines("- ", "  ", chunks).skip_empty().to_string(), "- first\n  second\n\n  third\n  fourth");
+	assert_eq!(format!("{:?}", prefix_lines("", "\t", "a\nb")), "a\n\tb");
+}
@@ -0,0 +1,73 @@
+use core::fmt;
+
+/// Extension trait to join iterables with method call syntax.
+///
+/// Import it with `use fmtools::prelude::*;` or directly.
+///
+/// ```
+/// use fmtools::IterDisplayExt;
+///
+/// struct User { name: &'static str }
+/// let users = [User { name: "alice" }, User { name: "bob" }];
+///
+/// let s = fmtools::format!("users: "{users.iter().map(|u| u.name).joined(", ")});
+/// assert_eq!(s, "users: alice, bob");
+/// ```
+///
+/// Implemented for all iterables, the methods require the same bounds as [`join`](crate::join()).
+pub trait IterDisplayExt: IntoIterator + Sized {
+	/// Displays the items with the separator between each item.
+	///
+	/// Same as [`join(sep, self)`](crate::join()).
+	#[inline]
+	fn joined<S: crate::Separator>(self, sep: S) -> impl fmt::Display + fmt::Debug
+		where Self::Item: fmt::Display, Self::IntoIter: Clone
+	{
+		crate::join(sep, self)
+	}
+
+	/// Displays the items using their `Debug` implementation with the separator between each item.
+	///
+	/// ```
+	/// use fmtools::IterDisplayExt;
+	///
+	/// let s = ["a", "b"].iter().joined_debug(", ").to_string();
+	/// assert_eq!(s, "\"a\", \"b\"");
+	/// ```
+	#[inline]
+	fn joined_debug<S: crate::Separator>(self, sep: S) -> impl fmt::Display + fmt::Debug
+		where Self::Item: fmt::Debug, Self::IntoIter: Clone
+	{
+		crate::join(sep, self.into_iter().map(crate::dbg))
+	}
+
+	/// Displays the items mapped to displayable objects with the separator between each item.
+	///
+	/// ```
+	/// use fmtools::IterDisplayExt;
+	///
+	/// let s = [1, 2].iter().joined_map(", ", |x| fmtools::fmt!(move "#"{x})).to_string();
+	/// assert_eq!(s, "#1, #2");
+	/// ```
+	#[inline]
+	fn joined_map<S, D, F>(self, sep: S, f: F) -> impl fmt::Display + fmt::Debug
+		where S: crate::Separator, D: fmt::Display, F: Fn(Self::Item) -> D + Clone, Self::IntoIter: Clone
+	{
+		crate::join(sep, self.into_iter().map(f))
+	}
+}
+
+impl<T: IntoIterator> IterDisplayExt for T {}
+
+#[test]
+fn tests() {
+	let values = [1, 2, 3];
+	assert_eq!(values.iter().joined(", ").to_string(), crate::join(", ", &values).to_string());
+	assert_eq!(crate::format!({values.iter().joined("-")}), "1-2-3");
+	assert_eq!(crate::format!({values.iter().map(|x| x * 2).joined(", "):?}), "2, 4, 6");
+	assert_eq!(crate::format!({values.iter().map(|&x| std::cmp::max(x, 2)).joined(",")}), "2,2,3");
+	assert_eq!(crate::format!({[Some(1), None].iter().joined_debug(", ")}), "Some(1), None");
+	assert_eq!(format!("{:.1?}", [1.25, 2.0].iter().joined_debug(", ")), "1.2, 2.0");
+	assert_eq!(crate::format!({values.joined_map(" ", |x| x * x)}), "1 4 9");
+	assert_eq!(Vec::<i32>::new().joined(", ").to_string(), "");
+}
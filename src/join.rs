@@ -1,3 +1,4 @@
+use core::cell::Cell;
 use core::fmt;
 
 /// Displays an iterable with given separator between each item.
@@ -6,26 +7,480 @@ use core::fmt;
 /// let result = fmtools::join("--", &[1, 2, 3, 4]).to_string();
 /// assert_eq!(result, "1--2--3--4");
 /// ```
+///
+/// The separator is a string slice, a `String` built at runtime, a `char` or another template, see [`Separator`]:
+///
+/// ```
+/// let sep = String::from(" | ");
+/// assert_eq!(fmtools::join(&sep, ["a", "b"]).to_string(), "a | b");
+/// assert_eq!(fmtools::join('/', ["usr", "bin"]).to_string(), "usr/bin");
+///
+/// let level = 3;
+/// let sep = fmtools::fmt!(" "{level}"> ");
+/// assert_eq!(fmtools::join(sep, [1, 2]).to_string(), "1 3> 2");
+/// ```
+///
+/// The formatting options are forwarded to the items, the separator is written without them.
+/// The iterator is cloned every time the object is displayed.
+///
+/// See [`Separated`](crate::Separated) for more options.
+#[inline]
+pub fn join<S, T>(sep: S, collection: T) -> Join<S, T::IntoIter>
+	where S: Separator,
+		T: IntoIterator,
+		T::Item: fmt::Display,
+		T::IntoIter: Clone
+{
+	Join { sep, iter: collection.into_iter() }
+}
+
+/// Displayable object returned by [`join`].
+#[derive(Copy, Clone)]
+pub struct Join<S, I> {
+	sep: S,
+	iter: I,
+}
+impl<S: Separator, I: Iterator + Clone> fmt::Display for Join<S, I> where I::Item: fmt::Display {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write_joined(f, &self.sep, self.iter.clone(), |f, item| item.fmt(f))
+	}
+}
+debug_as_display!([S: Separator, I: Iterator + Clone] Join<S, I> where I::Item: fmt::Display);
+impl<S: Separator + crate::SizeHint, I: ExactSizeIterator> crate::SizeHint for Join<S, I> {
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// Only the separators are counted, measuring the items would iterate them
+		match self.iter.len() {
+			0 => (0, Some(0)),
+			len => (self.sep.size_hint().0.saturating_mul(len - 1), None),
+		}
+	}
+}
+
+/// Displays key value pairs with the key separator between each key and value and the entry separator between each pair.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// let map = BTreeMap::from([("a", 1), ("b", 2)]);
+/// let result = fmtools::join_pairs("=", ", ", &map).to_string();
+/// assert_eq!(result, "a=1, b=2");
+///
+/// let pairs = [("x", 1.5), ("y", -2.0)];
+/// let result = fmtools::join_pairs(": ", "; ", &pairs).to_string();
+/// assert_eq!(result, "x: 1.5; y: -2");
+/// ```
+///
+/// The items are tuples or references to tuples, see [`KeyValue`].
+/// The formatting options are forwarded to every key and value, the separators are written without them.
+/// Like [`join`] the separators can be any [`Separator`] and the iterator is cloned every time the object is displayed.
+///
+/// See [join_pairs!](crate::join_pairs!) to specify the formatting of the values.
+#[inline]
+pub fn join_pairs<S, E, T>(kv_sep: S, entry_sep: E, collection: T) -> JoinPairs<S, E, T::IntoIter>
+	where S: Separator,
+		E: Separator,
+		T: IntoIterator,
+		T::Item: KeyValue,
+		T::IntoIter: Clone
+{
+	JoinPairs { kv_sep, entry_sep, iter: collection.into_iter() }
+}
+
+/// Key value pairs displayed by [`join_pairs`].
+///
+/// Implemented for `(K, V)` as yielded by iterating maps and for `&(K, V)` as yielded by iterating slices of tuples.
+pub trait KeyValue {
+	/// Type of the key.
+	type Key: fmt::Display + ?Sized;
+	/// Type of the value.
+	type Value: fmt::Display + ?Sized;
+	/// Returns the key and the value.
+	fn key_value(&self) -> (&Self::Key, &Self::Value);
+}
+impl<K: fmt::Display, V: fmt::Display> KeyValue for (K, V) {
+	type Key = K;
+	type Value = V;
+	#[inline]
+	fn key_value(&self) -> (&K, &V) {
+		(&self.0, &self.1)
+	}
+}
+impl<K: fmt::Display, V: fmt::Display> KeyValue for &(K, V) {
+	type Key = K;
+	type Value = V;
+	#[inline]
+	fn key_value(&self) -> (&K, &V) {
+		(&self.0, &self.1)
+	}
+}
+
+/// Displayable object returned by [`join_pairs`].
+#[derive(Copy, Clone)]
+pub struct JoinPairs<S, E, I> {
+	kv_sep: S,
+	entry_sep: E,
+	iter: I,
+}
+impl<S: Separator, E: Separator, I: Iterator + Clone> fmt::Display for JoinPairs<S, E, I> where I::Item: KeyValue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write_joined(f, &self.entry_sep, self.iter.clone(), |f, pair| {
+			let (key, value) = pair.key_value();
+			key.fmt(f)?;
+			self.kv_sep.write_sep(f)?;
+			value.fmt(f)
+		})
+	}
+}
+debug_as_display!([S: Separator, E: Separator, I: Iterator + Clone] JoinPairs<S, E, I> where I::Item: KeyValue);
+
+/// Displays an iterable once with given separator between each item.
+///
+/// ```
+/// let words = vec!["alpha", "beta", "gamma"];
+/// let long = words.into_iter().filter(|w| w.len() > 4);
+///
+/// let s = fmtools::join_once(", ", long);
+/// assert_eq!(s.to_string(), "alpha, gamma");
+/// assert_eq!(s.to_string(), "");
+/// ```
+///
+/// Unlike [`join`] the iterator does not need to be `Clone`, eg. line readers, channel receivers or boxed iterators.
+/// The iterator is consumed by the first render, every following render writes nothing.
+///
+/// The `Debug` implementation shows whether the iterator was consumed without consuming it.
+#[inline]
+pub fn join_once<S, T>(sep: S, collection: T) -> JoinOnce<S, T::IntoIter>
+	where S: Separator,
+		T: IntoIterator,
+		T::Item: fmt::Display
+{
+	JoinOnce { sep, iter: Cell::new(Some(collection.into_iter())) }
+}
+
+/// Displayable object returned by [`join_once`].
+pub struct JoinOnce<S, I> {
+	sep: S,
+	iter: Cell<Option<I>>,
+}
+impl<S: Separator, I: Iterator> fmt::Display for JoinOnce<S, I> where I::Item: fmt::Display {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let Some(iter) = self.iter.take() else {
+			return Ok(());
+		};
+		write_joined(f, &self.sep, iter, |f, item| item.fmt(f))
+	}
+}
+impl<S, I> fmt::Debug for JoinOnce<S, I> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let iter = self.iter.take();
+		let consumed = iter.is_none();
+		self.iter.set(iter);
+		f.debug_struct("JoinOnce").field("consumed", &consumed).finish_non_exhaustive()
+	}
+}
+
+/// Displays an iterable with given separator between each item and the last separator between the last two items.
+///
+/// ```
+/// let names = ["alice", "bob", "carol"];
+/// assert_eq!(fmtools::join_last(", ", " and ", &names).to_string(), "alice, bob and carol");
+/// assert_eq!(fmtools::join_last(", ", " and ", &names[..2]).to_string(), "alice and bob");
+/// assert_eq!(fmtools::join_last(", ", " and ", &names[..1]).to_string(), "alice");
+/// ```
+///
+/// The length of the iterator is not needed, a single item is looked ahead to find the last item.
+/// Like [`join`] the separators can be any [`Separator`] and the iterator is cloned every time the object is displayed.
 #[inline]
-pub fn join<T>(sep: &'static str, collection: T) -> impl fmt::Display + fmt::Debug
-	where T: IntoIterator,
-		<T as IntoIterator>::Item: fmt::Display,
-		<T as IntoIterator>::IntoIter: Clone
+pub fn join_last<S, L, T>(sep: S, last_sep: L, collection: T) -> JoinLast<S, L, T::IntoIter>
+	where S: Separator,
+		L: Separator,
+		T: IntoIterator,
+		T::Item: fmt::Display,
+		T::IntoIter: Clone
 {
-	let iter = collection.into_iter();
-	crate::fmt(move |f| {
-		let mut draw = false;
-		for item in iter.clone() {
-			if sep.len() > 0 {
-				if draw {
-					f.write_str(sep)?;
-				}
-				draw = true;
+	JoinLast { sep, last_sep, iter: collection.into_iter() }
+}
+
+/// Displayable object returned by [`join_last`].
+#[derive(Copy, Clone)]
+pub struct JoinLast<S, L, I> {
+	sep: S,
+	last_sep: L,
+	iter: I,
+}
+impl<S: Separator, L: Separator, I: Iterator + Clone> fmt::Display for JoinLast<S, L, I> where I::Item: fmt::Display {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut iter = self.iter.clone();
+		let Some(mut item) = iter.next() else {
+			return Ok(());
+		};
+		// One item lookahead to find the separator before the last item
+		let mut next = iter.next();
+		loop {
+			item.fmt(f)?;
+			let Some(following) = next else { break };
+			next = iter.next();
+			if next.is_some() {
+				self.sep.write_sep(f)?;
+			}
+			else {
+				self.last_sep.write_sep(f)?;
 			}
-			fmt::Display::fmt(&item, f)?;
+			item = following;
 		}
 		Ok(())
-	})
+	}
+}
+debug_as_display!([S: Separator, L: Separator, I: Iterator + Clone] JoinLast<S, L, I> where I::Item: fmt::Display);
+
+/// Displays an iterable with given separator between each item or the placeholder if there are no items.
+///
+/// ```
+/// let tags: Vec<&str> = Vec::new();
+/// assert_eq!(fmtools::join_or(", ", &tags, "<none>").to_string(), "<none>");
+/// assert_eq!(fmtools::join_or(", ", ["a", "b"], "<none>").to_string(), "a, b");
+/// ```
+///
+/// The first item is looked ahead to find out if the iterator is empty, the iterator does not need to know its length.
+/// The formatting options are forwarded to the items and the placeholder, the separator is written without them.
+/// Like [`join`] the separator can be any [`Separator`], the placeholder any displayable object and the iterator is cloned every time the object is displayed.
+#[inline]
+pub fn join_or<S, T, E>(sep: S, collection: T, empty: E) -> JoinOr<S, T::IntoIter, E>
+	where S: Separator,
+		T: IntoIterator,
+		T::Item: fmt::Display,
+		T::IntoIter: Clone,
+		E: fmt::Display,
+{
+	JoinOr { sep, iter: collection.into_iter(), empty }
+}
+
+/// Displayable object returned by [`join_or`].
+#[derive(Copy, Clone)]
+pub struct JoinOr<S, I, E> {
+	sep: S,
+	iter: I,
+	empty: E,
+}
+impl<S: Separator, I: Iterator + Clone, E: fmt::Display> fmt::Display for JoinOr<S, I, E> where I::Item: fmt::Display {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut iter = self.iter.clone();
+		let Some(first) = iter.next() else {
+			return self.empty.fmt(f);
+		};
+		write_joined(f, &self.sep, core::iter::once(first).chain(iter), |f, item| item.fmt(f))
+	}
+}
+debug_as_display!([S: Separator, I: Iterator + Clone, E: fmt::Display] JoinOr<S, I, E> where I::Item: fmt::Display);
+
+/// Displays an iterable by writing every item with the closure, with given separator between each item.
+///
+/// ```
+/// struct User { name: &'static str, id: u32 }
+/// let users = [User { name: "alice", id: 1 }, User { name: "bob", id: 2 }];
+///
+/// let result = fmtools::join_map(", ", &users, |f, u| write!(f, "{}={}", u.name, u.id)).to_string();
+/// assert_eq!(result, "alice=1, bob=2");
+/// ```
+///
+/// The closure writes the item directly to the formatter, there is no need to return a displayable object.
+/// The iterator is cloned and the closure invoked again every time the object is displayed.
+#[inline]
+pub fn join_map<S, T, F>(sep: S, collection: T, f: F) -> JoinMap<S, T::IntoIter, F>
+	where S: Separator,
+		T: IntoIterator,
+		T::IntoIter: Clone,
+		F: Fn(&mut fmt::Formatter, T::Item) -> fmt::Result
+{
+	JoinMap { sep, iter: collection.into_iter(), f }
+}
+
+/// Displayable object returned by [`join_map`].
+#[derive(Copy, Clone)]
+pub struct JoinMap<S, I, F> {
+	sep: S,
+	iter: I,
+	f: F,
+}
+impl<S: Separator, I: Iterator + Clone, F: Fn(&mut fmt::Formatter, I::Item) -> fmt::Result> fmt::Display for JoinMap<S, I, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write_joined(f, &self.sep, self.iter.clone(), &self.f)
+	}
+}
+debug_as_display!([S: Separator, I: Iterator + Clone, F: Fn(&mut fmt::Formatter, I::Item) -> fmt::Result] JoinMap<S, I, F>);
+
+/// Displays a slice with given separator between each item.
+///
+/// ```
+/// struct Token(&'static str);
+/// impl std::fmt::Display for Token {
+/// 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+/// 		f.write_str(self.0)
+/// 	}
+/// }
+///
+/// let tokens = [Token("a"), Token("b"), Token("c")];
+/// let result = fmtools::join_slice(" + ", &tokens).to_string();
+/// assert_eq!(result, "a + b + c");
+/// ```
+///
+/// Unlike [`join`] the slice is iterated fresh on every render, neither the items nor the iterator need to be `Clone`.
+/// The formatting options are forwarded to every item.
+#[inline]
+pub fn join_slice<'a, T: fmt::Display>(sep: &'a str, slice: &'a [T]) -> JoinSlice<'a, T> {
+	JoinSlice { sep, slice }
+}
+
+/// Displayable object returned by [`join_slice`].
+pub struct JoinSlice<'a, T> {
+	sep: &'a str,
+	slice: &'a [T],
+}
+impl<T> Copy for JoinSlice<'_, T> {}
+impl<T> Clone for JoinSlice<'_, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<T: fmt::Display> fmt::Display for JoinSlice<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write_joined(f, self.sep, self.slice.iter(), |f, item| item.fmt(f))
+	}
+}
+debug_as_display!([T: fmt::Display] JoinSlice<'_, T>);
+impl<T: crate::SizeHint> crate::SizeHint for JoinSlice<'_, T> {
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		crate::size_hint::join_hint(self.sep, self.slice.iter())
+	}
+}
+
+/// Displays the result of the closure for every item of a slice with given separator between each item.
+///
+/// ```
+/// let sizes = [1024, 2048, 4096];
+/// let result = fmtools::join_slice_map(", ", &sizes, |&n| fmtools::fmt!(move {n / 1024}"K")).to_string();
+/// assert_eq!(result, "1K, 2K, 4K");
+/// ```
+///
+/// The closure is invoked again for every item on every render, it may return non-`Clone` display objects borrowing from the item.
+#[inline]
+pub fn join_slice_map<'a, T, F, D>(sep: &'a str, slice: &'a [T], f: F) -> JoinSliceMap<'a, T, F>
+	where F: Fn(&'a T) -> D,
+		D: fmt::Display
+{
+	JoinSliceMap { sep, slice, f }
+}
+
+/// Displayable object returned by [`join_slice_map`].
+pub struct JoinSliceMap<'a, T, F> {
+	sep: &'a str,
+	slice: &'a [T],
+	f: F,
+}
+impl<T, F: Copy> Copy for JoinSliceMap<'_, T, F> {}
+impl<T, F: Clone> Clone for JoinSliceMap<'_, T, F> {
+	#[inline]
+	fn clone(&self) -> Self {
+		JoinSliceMap { sep: self.sep, slice: self.slice, f: self.f.clone() }
+	}
+}
+impl<'a, T, F: Fn(&'a T) -> D, D: fmt::Display> fmt::Display for JoinSliceMap<'a, T, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write_joined(f, self.sep, self.slice.iter(), |f, item| (self.f)(item).fmt(f))
+	}
+}
+debug_as_display!(['a, T, F: Fn(&'a T) -> D, D: fmt::Display] JoinSliceMap<'a, T, F>);
+
+/// Separator written between the items by [`join`] and the other join functions.
+///
+/// Implemented for string slices, `String` and `char`, empty strings write nothing.
+/// Templates returned by [fmt!](crate::fmt!) and any other displayable object behind `&dyn Display` are written without the formatting options.
+///
+/// ```
+/// use std::fmt::Display;
+///
+/// let arrow = fmtools::join("+", ['a', 'b']);
+/// let s = fmtools::join(&arrow as &dyn Display, [1, 2]).to_string();
+/// assert_eq!(s, "1a+b2");
+/// ```
+pub trait Separator {
+	/// Writes the separator.
+	fn write_sep(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+impl<T: Separator + ?Sized> Separator for &T {
+	#[inline]
+	fn write_sep(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(**self).write_sep(f)
+	}
+}
+impl Separator for str {
+	#[inline]
+	fn write_sep(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_empty() {
+			return Ok(());
+		}
+		f.write_str(self)
+	}
+}
+#[cfg(feature = "std")]
+impl Separator for String {
+	#[inline]
+	fn write_sep(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().write_sep(f)
+	}
+}
+impl Separator for char {
+	#[inline]
+	fn write_sep(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Write::write_char(f, *self)
+	}
+}
+impl Separator for dyn fmt::Display + '_ {
+	#[inline]
+	fn write_sep(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self)
+	}
+}
+impl<F: Fn(&mut fmt::Formatter) -> fmt::Result> Separator for crate::fmt<F> {
+	#[inline]
+	fn write_sep(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self)
+	}
+}
+
+// Writes the items with the separator between each item.
+fn write_joined<S: Separator + ?Sized, I: Iterator>(f: &mut fmt::Formatter, sep: &S, iter: I, write: impl Fn(&mut fmt::Formatter, I::Item) -> fmt::Result) -> fmt::Result {
+	for (i, item) in iter.enumerate() {
+		if i > 0 {
+			sep.write_sep(f)?;
+		}
+		write(f, item)?;
+	}
+	Ok(())
+}
+
+/// Joins string slices into a new string with the separator between each item.
+///
+/// ```
+/// let names = vec![String::from("alice"), String::from("bob")];
+/// assert_eq!(fmtools::join_str(", ", &names), "alice, bob");
+/// ```
+///
+/// Faster than formatting [`join`] as the exact capacity is reserved up front and the items are copied without going through `Display`.
+#[cfg(feature = "std")]
+pub fn join_str<T: AsRef<str>>(sep: &str, items: &[T]) -> String {
+	let len = items.iter().map(|item| item.as_ref().len()).sum::<usize>() + sep.len() * items.len().saturating_sub(1);
+	let mut s = String::with_capacity(len);
+	for (i, item) in items.iter().enumerate() {
+		if i > 0 {
+			s.push_str(sep);
+		}
+		s.push_str(item.as_ref());
+	}
+	s
 }
 
 /// Joins the arguments in a displayable object.
@@ -52,28 +507,166 @@ pub fn join<T>(sep: &'static str, collection: T) -> impl fmt::Display + fmt::Deb
 /// let result = inner().to_string();
 /// assert_eq!(result, "1--2.5--true");
 /// ```
+///
+/// A literal separator is concatenated into a single format string at compile time.
+/// Any other separator expression is written between the arguments at runtime, it is displayed without the formatting options:
+///
+/// ```
+/// fn list(sep: &str) -> String {
+/// 	fmtools::join!(sep; 'a', 'b', 'c',).to_string()
+/// }
+/// assert_eq!(list(" / "), "a / b / c");
+/// ```
+///
+/// The separator expression is evaluated every time the object is displayed, with `move` it is evaluated once and captured by value.
+/// A trailing comma after the arguments and a trailing semicolon are allowed.
 #[macro_export]
 macro_rules! join {
-	(move $sep:literal; $($e:expr),+) => {
+	(move $sep:literal; $($e:expr),+ $(,)? $(;)?) => {
 		$crate::fmt(move |f| {
 			f.write_fmt($crate::__join!(concat!(), ; $sep; $($e),+; "{}"))
 		})
 	};
-	(move $sep:literal; $($e:expr),+; $s:literal) => {
+	(move $sep:literal; $($e:expr),+ $(,)?; $s:literal $(;)?) => {
 		$crate::fmt(move |f| {
 			f.write_fmt($crate::__join!(concat!(), ; $sep; $($e),+; $s))
 		})
 	};
-	($sep:literal; $($e:expr),+) => {
+	(move $sep:expr; $($e:expr),+ $(,)? $(;)?) => {{
+		let sep = $sep;
+		$crate::fmt(move |f| {
+			$crate::__join_sep!(f, sep; $($e),+; "{}")
+		})
+	}};
+	(move $sep:expr; $($e:expr),+ $(,)?; $s:literal $(;)?) => {{
+		let sep = $sep;
+		$crate::fmt(move |f| {
+			$crate::__join_sep!(f, sep; $($e),+; $s)
+		})
+	}};
+	($sep:literal; $($e:expr),+ $(,)? $(;)?) => {
 		$crate::fmt(|f| {
 			f.write_fmt($crate::__join!(concat!(), ; $sep; $($e),+; "{}"))
 		})
 	};
-	($sep:literal; $($e:expr),+; $s:literal) => {
+	($sep:literal; $($e:expr),+ $(,)?; $s:literal $(;)?) => {
 		$crate::fmt(|f| {
 			f.write_fmt($crate::__join!(concat!(), ; $sep; $($e),+; $s))
 		})
 	};
+	($sep:expr; $($e:expr),+ $(,)? $(;)?) => {
+		$crate::fmt(|f| {
+			$crate::__join_sep!(f, $sep; $($e),+; "{}")
+		})
+	};
+	($sep:expr; $($e:expr),+ $(,)?; $s:literal $(;)?) => {
+		$crate::fmt(|f| {
+			$crate::__join_sep!(f, $sep; $($e),+; $s)
+		})
+	};
+}
+
+/// Joins key value pairs with the formatting of the values specified.
+///
+/// ```
+/// let env = [("PATH", "/bin"), ("HOME", "/root")];
+/// let result = fmtools::join_pairs!("=", " "; env; "{:?}").to_string();
+/// assert_eq!(result, r#"PATH="/bin" HOME="/root""#);
+/// ```
+///
+/// Without the format string the values are displayed, same as [`join_pairs`](crate::join_pairs()).
+/// The keys are always displayed.
+///
+/// ```
+/// let ports = [("http", 80), ("https", 443)];
+/// let result = fmtools::join_pairs!(": ", ", "; &ports).to_string();
+/// assert_eq!(result, "http: 80, https: 443");
+///
+/// let result = fmtools::join_pairs!(": ", ", "; &ports; "{:#06x}").to_string();
+/// assert_eq!(result, "http: 0x0050, https: 0x01bb");
+/// ```
+#[macro_export]
+macro_rules! join_pairs {
+	($kv_sep:expr, $entry_sep:expr; $collection:expr) => {
+		$crate::join_pairs($kv_sep, $entry_sep, $collection)
+	};
+	($kv_sep:expr, $entry_sep:expr; $collection:expr; $s:literal) => {
+		$crate::join_map($entry_sep, $collection, {
+			let kv_sep = $kv_sep;
+			move |f, pair| {
+				let (key, value) = $crate::KeyValue::key_value(&pair);
+				::core::write!(f, "{}{}", key, kv_sep)?;
+				::core::write!(f, $s, value)
+			}
+		})
+	};
+}
+
+/// Joins the arguments in a displayable object with the last separator between the last two arguments.
+///
+/// ```
+/// let result = fmtools::join_last!(", ", " or "; 1, 2.5, true).to_string();
+/// assert_eq!(result, "1, 2.5 or true");
+///
+/// let result = fmtools::join_last!(", ", " & "; 10, 11, 12; "{:#x}").to_string();
+/// assert_eq!(result, "0xa, 0xb & 0xc");
+/// ```
+///
+/// Supports the same syntax as [join!](crate::join!) with the last separator after the separator.
+#[macro_export]
+macro_rules! join_last {
+	(move $sep:literal, $last_sep:literal; $($e:expr),+) => {
+		$crate::fmt(move |f| {
+			f.write_fmt($crate::__join_last!(concat!(), ; $sep, $last_sep; $($e),+; "{}"))
+		})
+	};
+	(move $sep:literal, $last_sep:literal; $($e:expr),+; $s:literal) => {
+		$crate::fmt(move |f| {
+			f.write_fmt($crate::__join_last!(concat!(), ; $sep, $last_sep; $($e),+; $s))
+		})
+	};
+	($sep:literal, $last_sep:literal; $($e:expr),+) => {
+		$crate::fmt(|f| {
+			f.write_fmt($crate::__join_last!(concat!(), ; $sep, $last_sep; $($e),+; "{}"))
+		})
+	};
+	($sep:literal, $last_sep:literal; $($e:expr),+; $s:literal) => {
+		$crate::fmt(|f| {
+			f.write_fmt($crate::__join_last!(concat!(), ; $sep, $last_sep; $($e),+; $s))
+		})
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __join_last {
+	(concat!($($fmt:expr,)*), $($arg:expr,)*; $sep:literal, $last_sep:literal; $e:expr; $s:literal) => {
+		format_args!(concat!($($fmt,)* $s), $($arg,)* $e)
+	};
+	(concat!($($fmt:expr,)*), $($arg:expr,)*; $sep:literal, $last_sep:literal; $e:expr, $last:expr; $s:literal) => {
+		format_args!(concat!($($fmt,)* $s, $last_sep, $s), $($arg,)* $e, $last)
+	};
+	(concat!($($fmt:expr,)*), $($arg:expr,)*; $sep:literal, $last_sep:literal; $e:expr, $($tail:expr),+; $s:literal) => {
+		$crate::__join_last!(concat!($($fmt,)* $s, $sep,), $($arg,)* $e,; $sep, $last_sep; $($tail),+; $s)
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __join_sep {
+	($f:ident, $sep:expr; $e:expr; $s:literal) => {{
+		let _ = &$sep;
+		::core::write!($f, $s, $e)
+	}};
+	($f:ident, $sep:expr; $e:expr $(, $tail:expr)+; $s:literal) => {{
+		let sep = &$sep;
+		::core::write!($f, $s, $e)?;
+		$(
+			::core::write!($f, "{}", sep)?;
+			::core::write!($f, $s, $tail)?;
+		)+
+		Ok(())
+	}};
 }
 
 #[doc(hidden)]
@@ -98,7 +691,155 @@ fn tests() {
 	check(join!(","; 10, 11; "{:#x}"), "0xa,0xb");
 	fn inner() -> impl fmt::Display {
 		let (a, b) = (10, 11);
-		return join!(move " "; a, b);
+		join!(move " "; a, b)
 	}
 	check(join!("; "; join!(" "; 'a', 'b'), join!(" "; 'b', 'c'), inner()), "a b; b c; 10 11");
+
+	// Expression separators and trailing commas
+	fn expr_sep(sep: &str) -> String {
+		join!(sep; 1, 2, 3,).to_string()
+	}
+	check(expr_sep(", "), "1, 2, 3");
+	check(expr_sep(""), "123");
+	let sep = String::from(" | ");
+	check(join!(sep; 'a'), "a");
+	check(join!(&sep; 'a', "b"; "<{}>"), "<a> | <b>");
+	check(join!(sep.clone(); 1.5, 2,; "{:>4}";), " 1.5 |    2");
+	check(join!("--"; 1, 2, 3,), "1--2--3");
+	check(join!("--"; 1, 2,;), "1--2");
+	check(join!(','; 10, 11,; "{:#x}";), "0xa,0xb");
+	assert_eq!(format!("[{:>3}]", join!(crate::fmt!("-"); 1, 2)), "[1-2]");
+	fn owned_spec() -> impl fmt::Display {
+		let (sep, a, b) = (String::from("; "), 255, 16);
+		join!(move sep; a, b,; "{:#06x}")
+	}
+	check(owned_spec(), "0x00ff; 0x0010");
+	fn literal_spec() -> impl fmt::Display {
+		let (a, b) = (1.0, 2.25);
+		join!(move " "; a, b,; "{:.1}";)
+	}
+	check(literal_spec(), "1.0 2.2");
+
+	// Runtime and displayable separators
+	let config = String::from("; ");
+	let sep = config.trim_end().to_string();
+	check(join(&sep, [1, 2, 3]), "1;2;3");
+	check(join(sep.as_str(), [1, 2, 3]), "1;2;3");
+	check(join(',', ["a", "b"]), "a,b");
+	check(join(',', [0; 0]), "");
+	check(join("", [1, 2]), "12");
+	let depth = 2;
+	let nested = join(crate::fmt!(" "{depth}"> "), ["x", "y", "z"]);
+	check(nested, "x 2> y 2> z");
+	check(join(&join("+", ['a', 'b']) as &dyn fmt::Display, [1, 2, 3]), "1a+b2a+b3");
+	assert_eq!(format!("[{:>3}]", join('-', [1, 2])), "[  1-  2]");
+	let cloned = join(String::from(", "), vec![1, 2]).clone();
+	check(&cloned, "1, 2");
+	check(cloned, "1, 2");
+
+	// Key value pairs
+	let map: std::collections::BTreeMap<&str, f64> = [("b", 2.5), ("a", 1.0)].into_iter().collect();
+	let pairs = join_pairs("=", ", ", &map);
+	check(&pairs, "a=1, b=2.5");
+	check(&pairs, "a=1, b=2.5");
+	assert_eq!(format!("{:>2}", pairs), " a= 1,  b=2.5");
+	check(join_pairs(':', ';', &[(1, 'x'), (2, 'y')]), "1:x;2:y");
+	check(join_pairs("=", ", ", &std::collections::BTreeMap::<u8, u8>::new()), "");
+	check(join_pairs!("=", ", "; &map; "{:.2}"), "a=1.00, b=2.50");
+	check(join_pairs!("=", ", "; [("s", "x\"y")]; "{:?}"), "s=\"x\\\"y\"");
+	check(join_pairs!(String::from(" => "), crate::fmt!(" | "); map.iter().rev()), "b => 2.5 | a => 1");
+	check(join_pairs!("=", ","; &[] as &[(u8, u8)]; "{:?}"), "");
+
+	// Consumed by the first render
+	let boxed: Box<dyn Iterator<Item = u32>> = Box::new(vec![1, 2, 3, 4].into_iter().filter(|x| x % 2 == 0));
+	let once = join_once(" + ", boxed);
+	assert_eq!(format!("{:?}", once), "JoinOnce { consumed: false, .. }");
+	assert_eq!(format!("[{:>2}]", once), "[ 2 +  4]");
+	assert_eq!(format!("{:?}", once), "JoinOnce { consumed: true, .. }");
+	check(&once, "");
+	check(join_once(',', core::iter::empty::<char>()), "");
+	check(join_once(',', core::iter::once('a')), "a");
+	let lines = std::io::BufRead::lines(&b"first\nsecond\n"[..]).map(|line| line.unwrap());
+	check(join_once(crate::fmt!(" | "), lines), "first | second");
+
+	// Last separator
+	let lookahead = |n: usize| join_last(", ", " and ", (1..=n).map(|i| crate::fmt!(move "#"{i})));
+	check(lookahead(0), "");
+	check(lookahead(1), "#1");
+	check(lookahead(2), "#1 and #2");
+	check(lookahead(4), "#1, #2, #3 and #4");
+	let words = [String::from("x"), String::from("y"), String::from("z")];
+	let last = String::from(" or ");
+	let joined = join_last(';', &last, words.iter().filter(|w| *w != "y"));
+	check(joined.clone(), "x or z");
+	check(joined, "x or z");
+	assert_eq!(format!("{:>2}", join_last("", "", "abc".chars())), " a b c");
+	check(join_last!(", ", " and "; 1), "1");
+	check(join_last!(", ", " and "; 1, 'b'), "1 and b");
+	check(join_last!(", ", " and "; 1, 'b', "c", 4.5), "1, b, c and 4.5");
+	fn owned() -> impl fmt::Display {
+		let (a, b) = (String::from("a"), String::from("b"));
+		join_last!(move "-", "+"; a, b; "<{}>")
+	}
+	check(owned(), "<a>+<b>");
+
+	// Placeholder for empty iterators
+	let empty: Vec<u32> = Vec::new();
+	let placeholder = join_or(", ", &empty, "<none>");
+	check(&placeholder, "<none>");
+	check(&placeholder, "<none>");
+	check(join_or(", ", &[1, 2], "<none>"), "1, 2");
+	check(join_or(", ", &[0], "<none>"), "0");
+	let reason = "filtered";
+	let filtered = join_or('/', (1..10).filter(|x| x % 7 == 0), crate::fmt!("<"{reason}">"));
+	check(&filtered, "7");
+	check(join_or('/', (1..6).filter(|x| x % 7 == 0), crate::fmt!("<"{reason}">")), "<filtered>");
+	assert_eq!(format!("[{:>3}|{:>3}]", join_or(',', [1, 2], '-'), join_or(',', &empty, '-')), "[  1,  2|  -]");
+
+	// Items written by the closure
+	struct User { name: String, id: u32 }
+	let users = vec![User { name: String::from("alice"), id: 1 }, User { name: String::from("bob"), id: 22 }];
+	let prefix = String::from("#");
+	let mapped = join_map(", ", &users, |f, u| write!(f, "{}={}{}", u.name, prefix, u.id));
+	assert_eq!(format!("{}", mapped), "alice=#1, bob=#22");
+	assert_eq!(format!("[{:?}]", mapped), "[alice=#1, bob=#22]");
+	check(join_map(", ", &users[..0], |f, u| f.write_str(&u.name)), "");
+	check(join_map('|', 1..4, |f, x| fmt::Display::fmt(&crate::fmt!({x * x:>2}), f)), " 1| 4| 9");
+	assert_eq!(format!("{:>3}", join_map("", [1, 2], |f, x| fmt::Display::fmt(&x, f))), "  1  2");
+
+	// String slices
+	let items = ["héllo", "", "wörld", "€"];
+	let s = join_str(", ", &items);
+	assert_eq!(s, join(", ", &items).to_string());
+	assert_eq!(s.capacity(), s.len());
+	assert_eq!(join_str(", ", &[] as &[&str]), "");
+	assert_eq!(join_str(", ", &[""]), "");
+	assert_eq!(join_str("", &[String::from("a"), String::from("b")]), "ab");
+
+	// Slices of non-Clone items
+	struct Item(u32);
+	impl fmt::Display for Item {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			self.0.fmt(f)
+		}
+	}
+	let items = [1, 2, 3].map(Item);
+	let joined = join_slice(", ", &items);
+	check(joined, "1, 2, 3");
+	check(joined, "1, 2, 3");
+	assert_eq!(format!("[{:>3}]", joined), "[  1,   2,   3]");
+	check(join_slice(", ", &items[..1]), "1");
+	check(join_slice(", ", &[] as &[Item]), "");
+
+	// Mapped to non-Clone display objects
+	struct Wrapped<'a>(&'a Item);
+	impl fmt::Display for Wrapped<'_> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "<{}>", self.0)
+		}
+	}
+	let mapped = join_slice_map("", &items, Wrapped);
+	check(mapped, "<1><2><3>");
+	check(mapped, "<1><2><3>");
+	check(join_slice_map("-", &["a", "b"], |s| crate::fmt!(move {s}{s})), "aa-bb");
 }
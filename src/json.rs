@@ -0,0 +1,185 @@
+use core::fmt;
+
+/// Types which can be written as JSON values.
+///
+/// Used by the `json` modifier of [fmt!](crate::fmt!) to write the holes.
+/// Strings are quoted and escaped, numbers and booleans are written bare, `None` is written as `null`.
+/// Non-finite floats are written as `null` as JSON has no representation for them.
+pub trait JsonValue {
+	/// Writes the value as JSON.
+	fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+impl<T: JsonValue + ?Sized> JsonValue for &T {
+	#[inline]
+	fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(**self).fmt_json(f)
+	}
+}
+impl<T: JsonValue> JsonValue for Option<T> {
+	fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Some(value) => value.fmt_json(f),
+			None => f.write_str("null"),
+		}
+	}
+}
+impl JsonValue for str {
+	fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&json_string(self), f)
+	}
+}
+#[cfg(feature = "std")]
+impl JsonValue for String {
+	#[inline]
+	fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt_json(f)
+	}
+}
+impl JsonValue for char {
+	fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&json_string(self), f)
+	}
+}
+impl JsonValue for bool {
+	#[inline]
+	fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(if *self { "true" } else { "false" })
+	}
+}
+macro_rules! impl_json_int {
+	($($ty:ty),*) => {
+		$(
+			impl JsonValue for $ty {
+				#[inline]
+				fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+					write!(f, "{}", self)
+				}
+			}
+		)*
+	};
+}
+impl_json_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+macro_rules! impl_json_float {
+	($($ty:ty),*) => {
+		$(
+			impl JsonValue for $ty {
+				fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+					if self.is_finite() {
+						write!(f, "{}", self)
+					}
+					else {
+						f.write_str("null")
+					}
+				}
+			}
+		)*
+	};
+}
+impl_json_float!(f32, f64);
+
+/// Displays the value as JSON.
+///
+/// ```
+/// assert_eq!(fmtools::json(&"say \"hi\"").to_string(), r#""say \"hi\"""#);
+/// assert_eq!(fmtools::json(&42).to_string(), "42");
+/// assert_eq!(fmtools::json(&None::<bool>).to_string(), "null");
+/// ```
+#[inline]
+pub fn json<T: JsonValue + ?Sized>(value: &T) -> Json<'_, T> {
+	Json { value }
+}
+
+/// Displayable object returned by [`json`].
+pub struct Json<'a, T: ?Sized> {
+	value: &'a T,
+}
+impl<T: ?Sized> Copy for Json<'_, T> {}
+impl<T: ?Sized> Clone for Json<'_, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<T: JsonValue + ?Sized> fmt::Display for Json<'_, T> {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.value.fmt_json(f)
+	}
+}
+impl<T: JsonValue + ?Sized> fmt::Debug for Json<'_, T> {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.value.fmt_json(f)
+	}
+}
+
+/// Displays the output as a quoted and escaped JSON string.
+///
+/// ```
+/// let s = fmtools::json_string(fmtools::fmt!("line "{1}"\n\t\"end\"")).to_string();
+/// assert_eq!(s, r#""line 1\n\t\"end\"""#);
+/// ```
+///
+/// Quotes, backslashes and control characters are escaped.
+#[inline]
+pub fn json_string<T: fmt::Display>(value: T) -> JsonString<T> {
+	JsonString { value }
+}
+
+/// Displayable object returned by [`json_string`].
+#[derive(Copy, Clone)]
+pub struct JsonString<T> {
+	value: T,
+}
+impl<T: fmt::Display> fmt::Display for JsonString<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("\"")?;
+		fmt::write(&mut JsonWriter(f), format_args!("{}", self.value))?;
+		f.write_str("\"")
+	}
+}
+debug_as_display!([T: fmt::Display] JsonString<T>);
+
+struct JsonWriter<'a, 'f>(&'a mut fmt::Formatter<'f>);
+impl fmt::Write for JsonWriter<'_, '_> {
+	fn write_str(&mut self, mut s: &str) -> fmt::Result {
+		while let Some(i) = s.find(|chr: char| chr == '"' || chr == '\\' || chr < ' ') {
+			self.0.write_str(&s[..i])?;
+			match s.as_bytes()[i] {
+				b'"' => self.0.write_str("\\\"")?,
+				b'\\' => self.0.write_str("\\\\")?,
+				b'\n' => self.0.write_str("\\n")?,
+				b'\r' => self.0.write_str("\\r")?,
+				b'\t' => self.0.write_str("\\t")?,
+				byte => write!(self.0, "\\u{:04x}", byte)?,
+			}
+			s = &s[i + 1..];
+		}
+		self.0.write_str(s)
+	}
+}
+
+#[test]
+fn tests() {
+	#[track_caller]
+	fn roundtrip(s: &str) {
+		let encoded = json(s).to_string();
+		assert_eq!(serde_json::from_str::<String>(&encoded).unwrap(), s);
+	}
+	roundtrip("");
+	roundtrip("plain €😀");
+	roundtrip("\"quoted\" \\back\\slash");
+	roundtrip("\n\r\t\x08\x0c\x00\x1f\x7f");
+	roundtrip("</script>\u{2028}");
+
+	assert_eq!(json(&'"').to_string(), r#""\"""#);
+	assert_eq!(json(&String::from("a")).to_string(), r#""a""#);
+	assert_eq!(json(&-12i8).to_string(), "-12");
+	assert_eq!(json(&u128::MAX).to_string(), "340282366920938463463374607431768211455");
+	assert_eq!(json(&1.5f32).to_string(), "1.5");
+	assert_eq!(json(&f64::NAN).to_string(), "null");
+	assert_eq!(json(&true).to_string(), "true");
+	assert_eq!(json(&Some(Some("x"))).to_string(), r#""x""#);
+	assert_eq!(json_string(42).to_string(), r#""42""#);
+}
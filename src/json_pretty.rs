@@ -0,0 +1,181 @@
+use core::fmt;
+use serde_json::Value;
+
+/// Pretty prints the JSON value with a custom indentation.
+///
+/// ```
+/// let value = serde_json::json!({"name": "fmtools", "tags": ["fmt", "template"]});
+/// let s = fmtools::json_pretty(&value, "\t").to_string();
+/// assert_eq!(s, "{\n\t\"name\": \"fmtools\",\n\t\"tags\": [\n\t\t\"fmt\",\n\t\t\"template\"\n\t]\n}");
+/// ```
+///
+/// The output has no trailing newline and composes with [`indented`](crate::DisplayExt::indented) to nest it inside indented output:
+///
+/// ```
+/// use fmtools::DisplayExt;
+///
+/// let value = serde_json::json!({"id": 1});
+/// let s = fmtools::format!("request:\n"{fmtools::json_pretty(&value, "  ").indented("    ")});
+/// assert_eq!(s, "request:\n    {\n      \"id\": 1\n    }");
+/// ```
+///
+/// Strings and object keys are escaped with [`json_string`](crate::json_string).
+#[inline]
+pub fn json_pretty<'a>(value: &'a Value, indent: &'a str) -> JsonPretty<'a> {
+	JsonPretty { value, indent, max_width: None }
+}
+
+/// Displayable object returned by [`json_pretty`].
+#[derive(Copy, Clone)]
+pub struct JsonPretty<'a> {
+	value: &'a Value,
+	indent: &'a str,
+	max_width: Option<usize>,
+}
+impl<'a> JsonPretty<'a> {
+	/// Writes arrays and objects on a single line if they fit in the given width.
+	///
+	/// ```
+	/// let value = serde_json::json!({"point": [1, 2], "name": "a long name which does not fit"});
+	///
+	/// let s = fmtools::json_pretty(&value, "  ").compact(16).to_string();
+	/// assert_eq!(s, "{\n  \"name\": \"a long name which does not fit\",\n  \"point\": [1,2]\n}");
+	///
+	/// let s = fmtools::json_pretty(&value, "  ").compact(80).to_string();
+	/// assert_eq!(s, r#"{"name":"a long name which does not fit","point":[1,2]}"#);
+	/// ```
+	///
+	/// Every array and object is checked separately, the single line form has no whitespace.
+	/// The width is measured without the indentation in front of the value.
+	#[inline]
+	pub const fn compact(self, max_width: usize) -> JsonPretty<'a> {
+		JsonPretty { max_width: Some(max_width), ..self }
+	}
+
+	fn write_value(&self, f: &mut fmt::Formatter, value: &Value, depth: usize) -> fmt::Result {
+		let (open, close, len) = match value {
+			Value::Array(array) => ("[", "]", array.len()),
+			Value::Object(object) => ("{", "}", object.len()),
+			_ => return write_compact(f, value),
+		};
+		if len == 0 || self.max_width.is_some_and(|max_width| fits(value, max_width)) {
+			return write_compact(f, value);
+		}
+		f.write_str(open)?;
+		let mut first = true;
+		let mut item = |f: &mut fmt::Formatter, key: Option<&str>, value: &Value| {
+			f.write_str(if first { "\n" } else { ",\n" })?;
+			first = false;
+			self.write_indent(f, depth + 1)?;
+			if let Some(key) = key {
+				write!(f, "{}: ", crate::json_string(key))?;
+			}
+			self.write_value(f, value, depth + 1)
+		};
+		match value {
+			Value::Array(array) => for value in array {
+				item(f, None, value)?;
+			},
+			Value::Object(object) => for (key, value) in object {
+				item(f, Some(key), value)?;
+			},
+			_ => (),
+		}
+		f.write_str("\n")?;
+		self.write_indent(f, depth)?;
+		f.write_str(close)
+	}
+
+	fn write_indent(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+		for _ in 0..depth {
+			f.write_str(self.indent)?;
+		}
+		Ok(())
+	}
+}
+impl fmt::Display for JsonPretty<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.write_value(f, self.value, 0)
+	}
+}
+debug_as_display!([] JsonPretty<'_>);
+
+fn write_compact<W: fmt::Write + ?Sized>(w: &mut W, value: &Value) -> fmt::Result {
+	match value {
+		Value::Null => w.write_str("null"),
+		Value::Bool(value) => w.write_str(if *value { "true" } else { "false" }),
+		Value::Number(value) => write!(w, "{}", value),
+		Value::String(value) => write!(w, "{}", crate::json_string(value)),
+		Value::Array(array) => {
+			w.write_str("[")?;
+			for (i, value) in array.iter().enumerate() {
+				if i != 0 {
+					w.write_str(",")?;
+				}
+				write_compact(w, value)?;
+			}
+			w.write_str("]")
+		},
+		Value::Object(object) => {
+			w.write_str("{")?;
+			for (i, (key, value)) in object.iter().enumerate() {
+				if i != 0 {
+					w.write_str(",")?;
+				}
+				write!(w, "{}:", crate::json_string(key))?;
+				write_compact(w, value)?;
+			}
+			w.write_str("}")
+		},
+	}
+}
+
+// Measures the single line form, aborting as soon as it is too wide.
+fn fits(value: &Value, max_width: usize) -> bool {
+	struct Measure {
+		width: usize,
+		max_width: usize,
+	}
+	impl fmt::Write for Measure {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			self.width += crate::width::str_width(s);
+			if self.width > self.max_width { Err(fmt::Error) } else { Ok(()) }
+		}
+	}
+	write_compact(&mut Measure { width: 0, max_width }, value).is_ok()
+}
+
+#[test]
+fn tests() {
+	use serde_json::json;
+
+	let value = json!({
+		"name": "nested \"quotes\"\n€",
+		"empty": {},
+		"list": [],
+		"items": [1, 2.5, null, true, {"deep": [[]]}],
+	});
+
+	// Matches serde_json's pretty printer with the same indentation
+	assert_eq!(json_pretty(&value, "  ").to_string(), serde_json::to_string_pretty(&value).unwrap());
+	assert_eq!(json_pretty(&json!(42), "  ").to_string(), "42");
+	assert_eq!(json_pretty(&json!([]), "  ").to_string(), "[]");
+	assert_eq!(json_pretty(&json!([[1]]), "--").to_string(), "[\n--[\n----1\n--]\n]");
+
+	// Output parses back to the same value
+	let s = json_pretty(&value, "\t").to_string();
+	assert_eq!(serde_json::from_str::<Value>(&s).unwrap(), value);
+
+	// Width threshold
+	let value = json!({"a": [1, 2, 3], "b": {"c": "0123456789"}});
+	assert_eq!(json_pretty(&value, " ").compact(100).to_string(), r#"{"a":[1,2,3],"b":{"c":"0123456789"}}"#);
+	assert_eq!(json_pretty(&value, " ").compact(36).to_string(), r#"{"a":[1,2,3],"b":{"c":"0123456789"}}"#);
+	assert_eq!(json_pretty(&value, " ").compact(35).to_string(), "{\n \"a\": [1,2,3],\n \"b\": {\"c\":\"0123456789\"}\n}");
+	assert_eq!(json_pretty(&value, " ").compact(10).to_string(), "{\n \"a\": [1,2,3],\n \"b\": {\n  \"c\": \"0123456789\"\n }\n}");
+	assert_eq!(json_pretty(&value, " ").compact(0).to_string(), json_pretty(&value, " ").to_string());
+
+	// Nested inside indented output
+	use crate::DisplayExt;
+	let s = crate::format!("{\n"{json_pretty(&json!({"a": [1]}), "  ").indented("  ")}"\n}");
+	assert_eq!(s, "{\n  {\n    \"a\": [\n      1\n    ]\n  }\n}");
+}
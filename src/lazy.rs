@@ -0,0 +1,121 @@
+use core::cell::{Cell, OnceCell};
+use core::fmt;
+
+/// Displays the value produced by the closure, invoking it on every render.
+///
+/// Defers an expensive computation until the value is actually displayed.
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// let calls = Cell::new(0);
+/// let expensive = || { calls.set(calls.get() + 1); 42 };
+///
+/// let value = fmtools::lazy(expensive);
+/// assert_eq!(calls.get(), 0);
+///
+/// let s = fmtools::format!("answer = "{value});
+/// assert_eq!(s, "answer = 42");
+/// assert_eq!(calls.get(), 1);
+/// ```
+///
+/// The formatting options are forwarded to the produced value.
+///
+/// Contrast with [`memoize`](crate::memoize) which renders once and caches the string.
+#[inline]
+pub fn lazy<T: fmt::Display, F: Fn() -> T>(f: F) -> Lazy<F> {
+	Lazy { f }
+}
+
+/// Displayable object returned by [`lazy`].
+#[derive(Copy, Clone)]
+pub struct Lazy<F> {
+	f: F,
+}
+impl<T: fmt::Display, F: Fn() -> T> fmt::Display for Lazy<F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(self.f)().fmt(f)
+	}
+}
+impl<T: fmt::Display, F: Fn() -> T> fmt::Debug for Lazy<F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(self.f)().fmt(f)
+	}
+}
+
+/// Displays the value produced by the closure, invoking it on the first render only.
+///
+/// Supports `FnOnce` producers, the produced value is kept and displayed again on every subsequent render.
+///
+/// ```
+/// let names = vec!["a", "b", "c"];
+/// let value = fmtools::lazy_once(move || names.join(", "));
+/// assert_eq!(value.get(), None);
+/// assert_eq!(value.to_string(), "a, b, c");
+/// assert_eq!(value.get().map(String::as_str), Some("a, b, c"));
+/// ```
+///
+/// Unlike [`memoize`](crate::memoize) the value itself is kept, not its rendered string, and the formatting options are forwarded to it on every render.
+#[inline]
+pub fn lazy_once<T: fmt::Display, F: FnOnce() -> T>(f: F) -> LazyOnce<T, F> {
+	LazyOnce { f: Cell::new(Some(f)), value: OnceCell::new() }
+}
+
+/// Displayable object returned by [`lazy_once`].
+pub struct LazyOnce<T, F> {
+	f: Cell<Option<F>>,
+	value: OnceCell<T>,
+}
+impl<T: fmt::Display, F: FnOnce() -> T> LazyOnce<T, F> {
+	/// Returns the produced value if the object has been rendered.
+	#[inline]
+	pub fn get(&self) -> Option<&T> {
+		self.value.get()
+	}
+
+	fn force(&self) -> &T {
+		self.value.get_or_init(|| match self.f.take() {
+			Some(f) => f(),
+			// The closure is only taken when initializing the value
+			None => unreachable!(),
+		})
+	}
+}
+impl<T: fmt::Display, F: FnOnce() -> T> fmt::Display for LazyOnce<T, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.force().fmt(f)
+	}
+}
+impl<T: fmt::Display, F: FnOnce() -> T> fmt::Debug for LazyOnce<T, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.force().fmt(f)
+	}
+}
+
+#[test]
+fn tests() {
+	let calls = Cell::new(0);
+	let produce = || { calls.set(calls.get() + 1); "value" };
+
+	// Never rendered
+	let value = lazy(produce);
+	let _unused = crate::fmt!("never "{value});
+	assert_eq!(calls.get(), 0);
+
+	// Once per render
+	assert_eq!(value.to_string(), "value");
+	assert_eq!(format!("[{:>7}]", value), "[  value]");
+	assert_eq!(crate::format!({value}" "{value:<6}"|"), "value value |");
+	assert_eq!(calls.get(), 4);
+
+	// Only the first render
+	calls.set(0);
+	let value = lazy_once(produce);
+	let _unused = crate::fmt!("never "{value});
+	assert_eq!(calls.get(), 0);
+	assert_eq!(value.get(), None);
+	assert_eq!(crate::format!({value}" "{value:>6}), "value  value");
+	assert_eq!(value.to_string(), "value");
+	assert_eq!(value.get(), Some(&"value"));
+	assert_eq!(calls.get(), 1);
+}
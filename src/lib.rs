@@ -16,19 +16,256 @@ See [fmt!] for more information.
 */
 
 #![cfg_attr(not(any(test, feature = "std")), no_std)]
+#![allow(clippy::tabs_in_doc_comments)]
 
 use core::fmt as core_fmt;
 
+// Implements Debug by forwarding to the Display implementation.
+macro_rules! debug_as_display {
+	([$($generics:tt)*] $ty:ty $(where $($bounds:tt)*)?) => {
+		impl<$($generics)*> ::core::fmt::Debug for $ty $(where $($bounds)*)? {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				::core::fmt::Display::fmt(self, f)
+			}
+		}
+	};
+}
+
 mod template;
-mod prelude;
+pub mod prelude;
+pub mod ansi;
+pub mod style;
+pub mod runtime;
+#[cfg(feature = "std")]
+pub mod output;
+mod impl_fmt;
 
 mod join;
 pub use self::join::*;
 
+mod separated;
+pub use self::separated::*;
+
+mod lines_of;
+pub use self::lines_of::*;
+
+mod iter_ext;
+pub use self::iter_ext::*;
+
+mod position;
+pub use self::position::*;
+
+mod width;
+
+mod display_ext;
+pub use self::display_ext::*;
+
+mod pad;
+pub use self::pad::*;
+
+mod truncate;
+pub use self::truncate::*;
+
+mod fit;
+pub use self::fit::*;
+
+mod case;
+pub use self::case::*;
+
+mod repeat;
+pub use self::repeat::*;
+
+mod indent;
+pub use self::indent::*;
+
+mod trim;
+pub use self::trim::*;
+
+mod number;
+pub use self::number::*;
+
+mod words;
+pub use self::words::*;
+
+mod fast_int;
+pub use self::fast_int::*;
+
+#[cfg(feature = "ryu")]
+mod fast_float;
+#[cfg(feature = "ryu")]
+pub use self::fast_float::*;
+
+#[cfg(feature = "tiny-float")]
+mod tiny_float;
+#[cfg(feature = "tiny-float")]
+pub use self::tiny_float::*;
+
+mod radix;
+pub use self::radix::*;
+
+mod strftime;
+pub use self::strftime::*;
+
+mod spec;
+pub use self::spec::*;
+
+mod duration;
+pub use self::duration::*;
+
+mod rate;
+pub use self::rate::*;
+
+mod deterministic;
+pub use self::deterministic::*;
+
+mod raw;
+pub use self::raw::*;
+
+mod dbg;
+pub use self::dbg::*;
+
+mod escape;
+pub use self::escape::*;
+
+mod json;
+pub use self::json::*;
+
+mod logfmt;
+pub use self::logfmt::*;
+
+#[cfg(feature = "serde_json")]
+mod json_pretty;
+#[cfg(feature = "serde_json")]
+pub use self::json_pretty::*;
+
+mod glue;
+pub use self::glue::*;
+
+mod nothing;
+pub use self::nothing::*;
+
+mod coalesce;
+pub use self::coalesce::*;
+
+mod cond;
+pub use self::cond::*;
+
+mod either;
+pub use self::either::*;
+
+mod error;
+pub use self::error::*;
+
+mod counted;
+pub use self::counted::*;
+
+mod column;
+pub use self::column::*;
+
+mod try_fmt;
+pub use self::try_fmt::*;
+
+mod lazy;
+pub use self::lazy::*;
+
+mod fmt_mut;
+pub use self::fmt_mut::*;
+
+mod size_hint;
+pub use self::size_hint::*;
+
+mod buf;
+pub use self::buf::*;
+
+mod fixed;
+pub use self::fixed::*;
+
+#[cfg(feature = "std")]
+mod small;
+#[cfg(feature = "std")]
+pub use self::small::*;
+
+mod eq;
+pub use self::eq::*;
+
+mod chunks;
+pub use self::chunks::*;
+
+#[cfg(feature = "std")]
+mod lines;
+#[cfg(feature = "std")]
+pub use self::lines::*;
+
+#[cfg(feature = "std")]
+mod tree;
+#[cfg(feature = "std")]
+pub use self::tree::*;
+
+#[cfg(feature = "std")]
+mod side_by_side;
+#[cfg(feature = "std")]
+pub use self::side_by_side::*;
+
+#[cfg(feature = "std")]
+mod aligned_column;
+#[cfg(feature = "std")]
+pub use self::aligned_column::*;
+
+#[cfg(feature = "std")]
+mod highlight;
+#[cfg(feature = "std")]
+pub use self::highlight::*;
+
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+pub use self::diff::*;
+
+#[cfg(feature = "defmt")]
+mod defmt_impl;
+#[cfg(feature = "defmt")]
+pub use self::defmt_impl::*;
+
+#[cfg(feature = "ufmt")]
+mod ufmt_impl;
+#[cfg(feature = "ufmt")]
+pub use self::ufmt_impl::*;
+
+#[cfg(feature = "std")]
+mod as_read;
+#[cfg(feature = "std")]
+pub use self::as_read::*;
+
+#[cfg(all(feature = "std", feature = "futures-io"))]
+mod async_write;
+#[cfg(all(feature = "std", feature = "futures-io"))]
+pub use self::async_write::*;
+
+#[cfg(feature = "trace-errors")]
+mod trace;
+#[cfg(feature = "trace-errors")]
+pub use self::trace::*;
+
+#[cfg(feature = "std")]
+mod throttle;
+#[cfg(feature = "std")]
+pub use self::throttle::*;
+
+#[cfg(feature = "std")]
+mod memoize;
+#[cfg(feature = "std")]
+pub use self::memoize::*;
+
+#[cfg(feature = "std")]
+mod buffered;
+#[cfg(feature = "std")]
+pub use self::buffered::*;
+
 // Formattable object holder.
 //
 // Exported but hidden to support `Copy` + `Clone` if the closure implements these traits.
 // This is otherwise not supported by existential impl trait types.
+// The public name for this type is the `FmtFn` alias.
 #[doc(hidden)]
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone)]
@@ -41,12 +278,121 @@ impl<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result> core_fmt::Display for
 		(self.closure)(f)
 	}
 }
-impl<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result> core_fmt::Debug for fmt<F> {
-	fn fmt(&self, f: &mut core_fmt::Formatter) -> core_fmt::Result {
-		(self.closure)(f)
+macro_rules! impl_fmt_traits {
+	($($name:ident),*) => {$(
+		impl<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result> core_fmt::$name for fmt<F> {
+			fn fmt(&self, f: &mut core_fmt::Formatter) -> core_fmt::Result {
+				core_fmt::Display::fmt(self, f)
+			}
+		}
+	)*};
+}
+impl_fmt_traits!(Debug, LowerHex, UpperHex, Octal, Binary, LowerExp, UpperExp, Pointer);
+
+impl<F, G> PartialEq<fmt<G>> for fmt<F>
+	where F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result,
+		G: Fn(&mut core_fmt::Formatter) -> core_fmt::Result
+{
+	#[inline]
+	fn eq(&self, other: &fmt<G>) -> bool {
+		display_eq(self, other)
 	}
 }
 
+impl<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result> fmt<F> {
+	/// Writes the output to the writer.
+	///
+	/// ```
+	/// let mut s = String::from("> ");
+	/// fmtools::fmt!("value = "{42}).fmt_to(&mut s).unwrap();
+	/// assert_eq!(s, "> value = 42");
+	/// ```
+	#[inline]
+	pub fn fmt_to<W: core_fmt::Write + ?Sized>(&self, w: &mut W) -> core_fmt::Result {
+		w.write_fmt(::core::format_args!("{}", self))
+	}
+	/// Converts into a displayable object which does not implement `Debug`, see [`display_only`].
+	///
+	/// ```
+	/// let value = fmtools::fmt!("value = "{42}).display_only();
+	/// assert_eq!(value.to_string(), "value = 42");
+	/// ```
+	#[inline]
+	pub fn display_only(self) -> DisplayOnly<F> {
+		DisplayOnly { closure: self.closure }
+	}
+}
+impl<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result> SizeHint for fmt<F> {}
+
+/// Displayable object returned by [`fmt()`] and [fmt!].
+///
+/// The object is `Copy` and `Clone` if the closure is, `Send` and `Sync` if the closure is.
+/// Both `Display` and `Debug` invoke the closure with the formatter as its argument.
+///
+/// The other formatting traits `LowerHex`, `UpperHex`, `Octal`, `Binary`, `LowerExp`, `UpperExp` and `Pointer` are implemented the same way.
+/// The closure cannot tell which trait was used, this lets the object be embedded where any specifier is expected:
+///
+/// ```
+/// let bytes = [0xde, 0xad, 0xbe, 0xef];
+/// let dump = fmtools::fmt!(for b in &bytes { {b:02x} });
+/// assert_eq!(format!("{:x}|{:>10X}", dump, dump), "deadbeef|  deadbeef");
+/// ```
+///
+/// For templates built by [fmt!] the width, fill, alignment and precision apply to the whole output like they do for strings:
+///
+/// ```
+/// let x = 42;
+/// let s = format!("{:>8}|{:.3}|", fmtools::fmt!("["{x}"]"), fmtools::fmt!("["{x}"]"));
+/// assert_eq!(s, "    [42]|[42|");
+/// ```
+///
+/// The default alignment is left, the width is measured in chars and the precision truncates the output to a number of chars.
+/// When these options are set the template is rendered without them, twice if the width is set: once to measure the output and once to write it.
+/// Without these options the template is rendered directly with the formatter.
+///
+/// Closures passed to [`fmt()`] receive the formatter with its options as is, so closures forwarding to a value keep them.
+/// Wrap the object in [`with_options`] to apply the options to the whole output instead:
+///
+/// ```
+/// let n = -3;
+/// let value = fmtools::fmt(|f| std::fmt::Display::fmt(&n, f));
+/// assert_eq!(format!("{:05}|{:05}|", value, fmtools::with_options(value)), "-0003|-3   |");
+/// ```
+///
+/// Comparing with `PartialEq` compares the rendered output, see [`display_eq`].
+///
+/// Closures which do not capture anything coerce to function pointers, giving a fully named type:
+///
+/// ```
+/// use std::fmt;
+///
+/// struct Header {
+/// 	title: fmtools::FmtFn<fn(&mut fmt::Formatter) -> fmt::Result>,
+/// }
+///
+/// let header = Header {
+/// 	title: fmtools::fmt(|f| f.write_str("title")),
+/// };
+/// assert_eq!(header.title.to_string(), "title");
+/// ```
+///
+/// Closures which capture their environment can be stored generically or boxed with [`fmt_boxed`]:
+///
+/// ```
+/// use std::fmt;
+///
+/// struct Header<'a> {
+/// 	title: fmtools::FmtFn<Box<dyn Fn(&mut fmt::Formatter) -> fmt::Result + 'a>>,
+/// }
+///
+/// let name = "world";
+/// let header = Header {
+/// 	title: fmtools::fmt_boxed(move |f| write!(f, "Hello {}!", name)),
+/// };
+/// assert_eq!(header.title.to_string(), "Hello world!");
+/// ```
+pub type FmtFn<F> = fmt<F>;
+
 /// Returns a displayable object using the closure argument as its implementation.
 ///
 /// ```
@@ -68,10 +414,211 @@ pub fn fmt<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result>(closure: F) -> f
 	fmt { closure }
 }
 
+/// Returns a displayable object which is guaranteed to be `Send + Sync`.
+///
+/// Behaves exactly like [`fmt()`] but requires the closure to be `Send + Sync`.
+/// The constraint is checked here, at the construction site, instead of far away where the object is sent to another thread or boxed into an error.
+///
+/// ```
+/// let value = 42;
+/// let s = fmtools::fmt_send(move |f| {
+/// 	write!(f, "value = {}", value)
+/// });
+/// let s = std::thread::spawn(move || s.to_string()).join().unwrap();
+/// assert_eq!(s, "value = 42");
+/// ```
+///
+/// Captures which typically break this guarantee are `Rc`, `RefCell` (not `Sync`), `Cell` (not `Sync`) and raw pointers:
+///
+/// ```compile_fail
+/// let value = std::rc::Rc::new(42);
+/// let _ = fmtools::fmt_send(move |f| {
+/// 	write!(f, "value = {}", value)
+/// });
+/// ```
+///
+/// The [fmt!] macro supports this with the `send` modifier: `fmt!(send move ...)`.
+pub fn fmt_send<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result + Send + Sync>(closure: F) -> fmt<F> {
+	fmt { closure }
+}
+
+type DynFn<'a> = dyn Fn(&mut core_fmt::Formatter) -> core_fmt::Result + 'a;
+
+/// Returns a type-erased displayable object borrowing the closure argument.
+///
+/// Every closure has a unique type, every [fmt!] invocation instantiates its own displayable object.
+/// Funneling closures through `&dyn Fn` shares a single instantiation between all call sites, trading a virtual call for smaller code size.
+///
+/// ```
+/// let name = "world";
+/// let s = fmtools::fmt_dyn(&|f| {
+/// 	write!(f, "Hello {}!", name)
+/// }).to_string();
+/// assert_eq!(s, "Hello world!");
+/// ```
+///
+/// The [fmt!] macro supports this with the `dyn` modifier: `fmt!(dyn ...)`.
+/// As the closure is a temporary, the displayable object can only be used within the statement it is created in.
+#[inline]
+pub fn fmt_dyn<'a>(closure: &'a DynFn<'a>) -> fmt<&'a DynFn<'a>> {
+	fmt { closure }
+}
+
+/// Returns a type-erased displayable object owning the boxed closure argument.
+///
+/// Like [`fmt_dyn`] but the displayable object owns the closure and can be returned from functions.
+///
+/// ```
+/// fn greet(name: String) -> impl std::fmt::Display {
+/// 	fmtools::fmt_boxed(move |f| {
+/// 		write!(f, "Hello {}!", name)
+/// 	})
+/// }
+/// assert_eq!(greet(String::from("world")).to_string(), "Hello world!");
+/// ```
+#[cfg(feature = "std")]
+#[inline]
+pub fn fmt_boxed<'a, F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result + 'a>(closure: F) -> fmt<Box<DynFn<'a>>> {
+	fmt { closure: Box::new(closure) }
+}
+
+/// Returns a displayable object from a closure writing to a `dyn fmt::Write`.
+///
+/// Adapts helper code written against `&mut dyn fmt::Write` so it can be used as a displayable object.
+///
+/// ```
+/// use std::fmt;
+///
+/// fn render_header(w: &mut dyn fmt::Write, id: u32) -> fmt::Result {
+/// 	write!(w, "header {:#x}", id)
+/// }
+///
+/// let id = 42;
+/// let s = fmtools::format!("["{fmtools::fmt_write(|w| render_header(w, id))}"]");
+/// assert_eq!(s, "[header 0x2a]");
+/// ```
+///
+/// The formatter is passed as the writer, errors returned by the closure are propagated.
+#[inline]
+pub fn fmt_write<F: Fn(&mut dyn core_fmt::Write) -> core_fmt::Result>(closure: F) -> fmt<impl Fn(&mut core_fmt::Formatter) -> core_fmt::Result> {
+	fmt { closure: move |f: &mut core_fmt::Formatter| closure(f) }
+}
+
+/// Writes the displayable object to the formatter.
+///
+/// Delegates a region of the output to another displayable object from an escape hatch of [fmt!] or from helper functions taking a formatter:
+///
+/// ```
+/// use std::fmt;
+///
+/// fn header(title: &str) -> impl fmt::Display + '_ {
+/// 	fmtools::fmt!(move "== "{title}" ==\n")
+/// }
+///
+/// fn section(f: &mut fmt::Formatter, title: &str, lines: &[&str]) -> fmt::Result {
+/// 	fmtools::fmt_into(f, header(title))?;
+/// 	for line in lines {
+/// 		fmtools::fmt_into(f, fmtools::fmt!("  "{line}"\n"))?;
+/// 	}
+/// 	Ok(())
+/// }
+///
+/// let s = fmtools::format! {
+/// 	|f| section(f, "a", &["1", "2"])?;
+/// 	|f| fmtools::fmt_into(f, header("b"))?;
+/// };
+/// assert_eq!(s, "== a ==\n  1\n  2\n== b ==\n");
+/// ```
+///
+/// The value is written like a hole of the template, the formatting options of the formatter are not forwarded to it.
+/// Errors are propagated, return early with `?`.
+#[inline]
+pub fn fmt_into<T: core_fmt::Display>(f: &mut core_fmt::Formatter, value: T) -> core_fmt::Result {
+	f.write_fmt(::core::format_args!("{}", value))
+}
+
+/// Returns a displayable object which implements only `Display`.
+///
+/// Behaves exactly like [`fmt()`] but the returned object does not implement `Debug`.
+///
+/// ```
+/// let name = "world";
+/// let s = fmtools::display_only(|f| write!(f, "Hello {}!", name));
+/// assert_eq!(s.to_string(), "Hello world!");
+/// ```
+///
+/// Prefer [`fmt()`] and [fmt!] in general, the `Debug` implementation lets the object be used in `{:?}` and in types deriving `Debug`.
+/// Use this when the `Debug` implementation gets in the way, for example when generic code is implemented for both `T: Display` and `T: Debug`:
+///
+/// ```
+/// use std::fmt;
+///
+/// trait ViaDisplay { fn describe(&self) -> String; }
+/// impl<T: fmt::Display> ViaDisplay for T {
+/// 	fn describe(&self) -> String { format!("display: {}", self) }
+/// }
+/// trait ViaDebug { fn describe(&self) -> String; }
+/// impl<T: fmt::Debug> ViaDebug for T {
+/// 	fn describe(&self) -> String { format!("debug: {:?}", self) }
+/// }
+///
+/// let value = fmtools::display_only(|f| f.write_str("x"));
+/// assert_eq!(value.describe(), "display: x");
+/// ```
+///
+/// The same method call is ambiguous for objects implementing both traits:
+///
+/// ```compile_fail
+/// # use std::fmt;
+/// # trait ViaDisplay { fn describe(&self) -> String; }
+/// # impl<T: fmt::Display> ViaDisplay for T {
+/// # 	fn describe(&self) -> String { format!("display: {}", self) }
+/// # }
+/// # trait ViaDebug { fn describe(&self) -> String; }
+/// # impl<T: fmt::Debug> ViaDebug for T {
+/// # 	fn describe(&self) -> String { format!("debug: {:?}", self) }
+/// # }
+/// let value = fmtools::fmt(|f| f.write_str("x"));
+/// let _ = value.describe();
+/// ```
+///
+/// The object cannot be debug formatted:
+///
+/// ```compile_fail
+/// let value = fmtools::display_only(|f| f.write_str("x"));
+/// let _ = format!("{:?}", value);
+/// ```
+///
+/// Convert the result of [fmt!] with [`display_only`](FmtFn::display_only).
+/// Not emitting the `Debug` implementation also saves a little code size when the object is only ever displayed.
+#[inline]
+pub fn display_only<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result>(closure: F) -> DisplayOnly<F> {
+	DisplayOnly { closure }
+}
+
+/// Displayable object returned by [`display_only`].
+///
+/// The object is `Copy` and `Clone` if the closure is, `Send` and `Sync` if the closure is.
+/// Formats exactly like [`FmtFn`], passing the formatting options through to the closure, but does not implement `Debug`.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct DisplayOnly<F> {
+	closure: F,
+}
+impl<F: Fn(&mut core_fmt::Formatter) -> core_fmt::Result> core_fmt::Display for DisplayOnly<F> {
+	fn fmt(&self, f: &mut core_fmt::Formatter) -> core_fmt::Result {
+		(self.closure)(f)
+	}
+}
+
 #[cfg(feature = "obfstr")]
 #[doc(hidden)]
 pub use obfstr::obfstr;
 
+// Obfuscated literals must not take the write_char shortcut.
+#[doc(hidden)]
+pub const __OBFSTR: bool = cfg!(feature = "obfstr");
+
 #[cfg(not(feature = "obfstr"))]
 #[doc(hidden)]
 #[macro_export]
@@ -0,0 +1,143 @@
+use std::{fmt, iter};
+use std::ops::ControlFlow;
+
+/// Renders the value and returns an iterator over its lines.
+///
+/// Lines are split the same way as [`str::lines`]: on `\n` with an optional preceding `\r` stripped, a final line ending is optional.
+///
+/// ```
+/// let lines: Vec<String> = fmtools::render_lines(&fmtools::fmt!("a = "{1}"\r\nb = "{2})).collect();
+/// assert_eq!(lines, ["a = 1", "b = 2"]);
+/// ```
+///
+/// The value is rendered once into a buffer, if it returns an error the iterator is empty.
+/// See [`for_each_line`] to process the lines as the output is streamed.
+pub fn render_lines<T: fmt::Display + ?Sized>(value: &T) -> impl Iterator<Item = String> {
+	let mut s = String::new();
+	if fmt::write(&mut s, format_args!("{}", value)).is_err() {
+		s.clear();
+	}
+	let mut pos = 0;
+	iter::from_fn(move || {
+		if pos >= s.len() {
+			return None;
+		}
+		let rest = &s[pos..];
+		let (line, len) = match rest.find('\n') {
+			Some(i) => (&rest[..i], i + 1),
+			None => (rest, rest.len()),
+		};
+		pos += len;
+		Some(String::from(strip_cr(line)))
+	})
+}
+
+/// Invokes the callback for every line of the value's output.
+///
+/// Lines are split the same way as [`str::lines`]: on `\n` with an optional preceding `\r` stripped, a final line ending is optional.
+///
+/// ```
+/// use std::ops::ControlFlow;
+///
+/// let mut log = String::new();
+/// fmtools::for_each_line(&fmtools::fmt!("a = "{1}"\nb = "{2}"\n"), |line| {
+/// 	log.push_str("[info] ");
+/// 	log.push_str(line);
+/// 	log.push('\n');
+/// 	ControlFlow::Continue(())
+/// }).unwrap();
+/// assert_eq!(log, "[info] a = 1\n[info] b = 2\n");
+/// ```
+///
+/// Only the current partial line is buffered, complete lines written at once are passed through without copying.
+/// Returning `ControlFlow::Break` stops rendering the value, this is not an error.
+pub fn for_each_line<T, F>(value: &T, f: F) -> fmt::Result
+	where T: fmt::Display + ?Sized,
+		F: FnMut(&str) -> ControlFlow<()>
+{
+	let mut writer = LineWriter { f, line: String::new(), done: false };
+	let result = fmt::write(&mut writer, format_args!("{}", value)).and_then(|_| {
+		if writer.line.is_empty() {
+			return Ok(());
+		}
+		let line = std::mem::take(&mut writer.line);
+		writer.emit(&line)
+	});
+	match result {
+		Err(_) if writer.done => Ok(()),
+		result => result,
+	}
+}
+
+struct LineWriter<F> {
+	f: F,
+	line: String,
+	done: bool,
+}
+impl<F: FnMut(&str) -> ControlFlow<()>> LineWriter<F> {
+	fn emit(&mut self, line: &str) -> fmt::Result {
+		match (self.f)(strip_cr(line)) {
+			ControlFlow::Continue(()) => Ok(()),
+			ControlFlow::Break(()) => {
+				self.done = true;
+				Err(fmt::Error)
+			},
+		}
+	}
+}
+impl<F: FnMut(&str) -> ControlFlow<()>> fmt::Write for LineWriter<F> {
+	fn write_str(&mut self, mut s: &str) -> fmt::Result {
+		while let Some(i) = s.find('\n') {
+			if self.line.is_empty() {
+				self.emit(&s[..i])?;
+			}
+			else {
+				self.line.push_str(&s[..i]);
+				let line = std::mem::take(&mut self.line);
+				self.emit(&line)?;
+				// Reuse the allocation for the next line
+				self.line = line;
+				self.line.clear();
+			}
+			s = &s[i + 1..];
+		}
+		self.line.push_str(s);
+		Ok(())
+	}
+}
+
+fn strip_cr(line: &str) -> &str {
+	line.strip_suffix('\r').unwrap_or(line)
+}
+
+#[test]
+fn tests() {
+	fn streamed(value: &dyn fmt::Display) -> Vec<String> {
+		let mut lines = Vec::new();
+		for_each_line(value, |line| {
+			lines.push(line.to_string());
+			ControlFlow::Continue(())
+		}).unwrap();
+		lines
+	}
+	#[track_caller]
+	fn check(value: &dyn fmt::Display, expected: &[&str]) {
+		assert_eq!(render_lines(value).collect::<Vec<_>>(), expected);
+		assert_eq!(streamed(value), expected);
+	}
+
+	check(&"", &[]);
+	check(&"no newline", &["no newline"]);
+	check(&"a\nb\n", &["a", "b"]);
+	check(&"a\r\nb\r\n\nc", &["a", "b", "", "c"]);
+	check(&"\n\n", &["", ""]);
+	check(&crate::fmt!("par"{"tial\nli"}"ne\r"{"\n"}"end"), &["partial", "line", "end"]);
+
+	// Early break
+	let mut lines = Vec::new();
+	for_each_line(&"a\nb\nc", |line| {
+		lines.push(line.to_string());
+		if lines.len() == 2 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+	}).unwrap();
+	assert_eq!(lines, ["a", "b"]);
+}
@@ -0,0 +1,86 @@
+use core::fmt;
+
+/// Displays each item followed by a newline.
+///
+/// ```
+/// let entries = ["a = 1", "b = 2"];
+/// let s = fmtools::lines_of(&entries).to_string();
+/// assert_eq!(s, "a = 1\nb = 2\n");
+/// ```
+///
+/// An empty iterable produces no output.
+/// Use [`crlf`](LinesOf::crlf) to terminate the lines with `\r\n` instead.
+#[inline]
+pub fn lines_of<T>(collection: T) -> LinesOf<T::IntoIter>
+	where T: IntoIterator,
+		T::Item: fmt::Display,
+		T::IntoIter: Clone
+{
+	LinesOf { iter: collection.into_iter(), newline: "\n" }
+}
+
+/// Displays each item mapped to a displayable object followed by a newline.
+///
+/// ```
+/// let values = [1, 2];
+/// let s = fmtools::lines_of_map(&values, |x| fmtools::fmt!(move "value = "{x})).to_string();
+/// assert_eq!(s, "value = 1\nvalue = 2\n");
+/// ```
+#[inline]
+pub fn lines_of_map<T, D, F>(collection: T, f: F) -> LinesOf<core::iter::Map<T::IntoIter, F>>
+	where T: IntoIterator,
+		T::IntoIter: Clone,
+		D: fmt::Display,
+		F: Fn(T::Item) -> D + Clone
+{
+	lines_of(collection.into_iter().map(f))
+}
+
+/// Displayable object returned by [`lines_of`] and [`lines_of_map`].
+#[derive(Copy, Clone)]
+pub struct LinesOf<I> {
+	iter: I,
+	newline: &'static str,
+}
+impl<I> LinesOf<I> {
+	/// Terminates the lines with `\r\n`.
+	#[inline]
+	pub const fn crlf(mut self) -> LinesOf<I> {
+		self.newline = "\r\n";
+		self
+	}
+}
+impl<I: Iterator + Clone> fmt::Display for LinesOf<I> where I::Item: fmt::Display {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for item in self.iter.clone() {
+			fmt::Display::fmt(&item, f)?;
+			f.write_str(self.newline)?;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([I: Iterator + Clone] LinesOf<I> where I::Item: fmt::Display);
+
+#[test]
+fn tests() {
+	use std::fmt::Write;
+
+	let entries = [("a", 1), ("b", 2), ("c", 3)];
+	let mut expected = String::new();
+	for (key, value) in &entries {
+		writeln!(expected, "{} = {}", key, value).unwrap();
+	}
+	fn render(&(key, value): &(&'static str, i32)) -> impl fmt::Display + Clone {
+		crate::fmt!(move {key}" = "{value})
+	}
+	assert_eq!(lines_of(entries.iter().map(render)).to_string(), expected);
+	assert_eq!(lines_of_map(&entries, render).to_string(), expected);
+	assert_eq!(lines_of_map(&entries, render).crlf().to_string(), expected.replace('\n', "\r\n"));
+
+	// Empty
+	assert_eq!(lines_of(&[] as &[i32]).to_string(), "");
+	assert_eq!(lines_of(&[] as &[i32]).crlf().to_string(), "");
+
+	// Empty items are not skipped
+	assert_eq!(lines_of(["", "a", ""]).to_string(), "\na\n\n");
+}
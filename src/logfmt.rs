@@ -0,0 +1,197 @@
+use core::fmt;
+use crate::KeyValue;
+
+/// Displays key value pairs as a logfmt line.
+///
+/// ```
+/// let user = 42;
+/// let msg = "login failed";
+/// let pairs = [
+/// 	("level", &"warn" as &dyn std::fmt::Display),
+/// 	("user", &user),
+/// 	("msg", &msg),
+/// ];
+/// let s = fmtools::logfmt(&pairs).to_string();
+/// assert_eq!(s, r#"level=warn user=42 msg="login failed""#);
+/// ```
+///
+/// The pairs are written in order separated by a space.
+/// Values containing spaces, `=`, `"` or control characters and empty values are quoted.
+/// Inside quotes the backslash and double quote are escaped with a backslash, newlines, carriage returns and tabs as `\n`, `\r` and `\t` and other control characters as `\u00XX`.
+/// The keys are written as is.
+///
+/// The values are rendered twice, once to decide on the quoting and once to write them.
+/// The items are tuples or references to tuples, see [`KeyValue`], the iterator is cloned every time the object is displayed.
+///
+/// See [logfmt!](crate::logfmt!) to build the pairs tersely.
+#[inline]
+pub fn logfmt<T>(pairs: T) -> Logfmt<T::IntoIter>
+	where T: IntoIterator,
+		T::Item: KeyValue,
+		T::IntoIter: Clone
+{
+	Logfmt { iter: pairs.into_iter(), skip_empty: false }
+}
+
+/// Displayable object returned by [`logfmt()`].
+#[derive(Copy, Clone)]
+pub struct Logfmt<I> {
+	iter: I,
+	skip_empty: bool,
+}
+impl<I> Logfmt<I> {
+	/// Skips the pairs whose value renders empty instead of writing `key=""`.
+	///
+	/// ```
+	/// let pairs = [("method", "GET"), ("query", ""), ("status", "200")];
+	/// let s = fmtools::logfmt(&pairs).skip_empty().to_string();
+	/// assert_eq!(s, "method=GET status=200");
+	/// ```
+	#[inline]
+	pub fn skip_empty(mut self) -> Self {
+		self.skip_empty = true;
+		self
+	}
+}
+impl<I: Iterator + Clone> fmt::Display for Logfmt<I> where I::Item: KeyValue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut first = true;
+		for pair in self.iter.clone() {
+			let (key, value) = pair.key_value();
+			let mut scan = Scan { empty: true, quote: false };
+			fmt::write(&mut scan, format_args!("{}", value))?;
+			if self.skip_empty && scan.empty {
+				continue;
+			}
+			if !first {
+				f.write_str(" ")?;
+			}
+			first = false;
+			write!(f, "{}=", key)?;
+			if scan.empty || scan.quote {
+				f.write_str("\"")?;
+				fmt::write(&mut Escape(f), format_args!("{}", value))?;
+				f.write_str("\"")?;
+			}
+			else {
+				write!(f, "{}", value)?;
+			}
+		}
+		Ok(())
+	}
+}
+debug_as_display!([I: Iterator + Clone] Logfmt<I> where I::Item: KeyValue);
+
+/// Displays the key value pairs as a logfmt line.
+///
+/// ```
+/// let user_id = 42;
+/// let path = "/index.html";
+/// let line = fmtools::logfmt! {
+/// 	level: "info",
+/// 	user: user_id,
+/// 	msg: fmtools::fmt!("GET "{path}),
+/// 	"http.status": 200,
+/// };
+/// assert_eq!(line.to_string(), r#"level=info user=42 msg="GET /index.html" http.status=200"#);
+/// ```
+///
+/// The keys are identifiers or string literals, the values are any displayable expression.
+/// Like [fmt!](crate::fmt!) the values are captured by reference and evaluated every time the object is displayed.
+///
+/// See [`logfmt`](crate::logfmt()) for the quoting rules.
+#[macro_export]
+macro_rules! logfmt {
+	($($key:tt: $value:expr),+ $(,)?) => {
+		$crate::fmt(|f| {
+			::core::fmt::Display::fmt(&$crate::logfmt([$(
+				($crate::__logfmt_key!($key), &$value as &dyn ::core::fmt::Display)
+			),+]), f)
+		})
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __logfmt_key {
+	($key:ident) => { ::core::stringify!($key) };
+	($key:literal) => { $key };
+}
+
+// Checks whether the value needs quotes.
+struct Scan {
+	empty: bool,
+	quote: bool,
+}
+impl fmt::Write for Scan {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.empty &= s.is_empty();
+		self.quote |= s.chars().any(|chr| matches!(chr, ' ' | '=' | '"') || chr.is_control());
+		Ok(())
+	}
+}
+
+// Escapes the value inside quotes.
+struct Escape<'a, 'f>(&'a mut fmt::Formatter<'f>);
+impl fmt::Write for Escape<'_, '_> {
+	fn write_str(&mut self, mut s: &str) -> fmt::Result {
+		while let Some(i) = s.find(|chr: char| matches!(chr, '"' | '\\') || chr.is_control()) {
+			self.0.write_str(&s[..i])?;
+			let chr = s[i..].chars().next().unwrap();
+			match chr {
+				'"' => self.0.write_str("\\\"")?,
+				'\\' => self.0.write_str("\\\\")?,
+				'\n' => self.0.write_str("\\n")?,
+				'\r' => self.0.write_str("\\r")?,
+				'\t' => self.0.write_str("\\t")?,
+				_ => write!(self.0, "\\u{:04x}", chr as u32)?,
+			}
+			s = &s[i + chr.len_utf8()..];
+		}
+		self.0.write_str(s)
+	}
+}
+
+#[test]
+fn tests() {
+	// Quoting triggers
+	const TESTS: &[(&str, &str)] = &[
+		("plain", "k=plain"),
+		("a b", r#"k="a b""#),
+		("a=b", r#"k="a=b""#),
+		("say \"hi\"", r#"k="say \"hi\"""#),
+		("tab\there", r#"k="tab\there""#),
+		("two\nlines\r", r#"k="two\nlines\r""#),
+		("bell\x07", r#"k="bell\u0007""#),
+		("back\\slash", r"k=back\slash"),
+		("back\\slash and space", r#"k="back\\slash and space""#),
+		("ünïcödé", "k=ünïcödé"),
+		("", r#"k="""#),
+	];
+	for &(value, expected) in TESTS {
+		assert_eq!(logfmt([("k", value)]).to_string(), expected);
+	}
+
+	// Empty values
+	let pairs = [("a", ""), ("b", "x"), ("c", ""), ("d", "")];
+	assert_eq!(logfmt(&pairs).to_string(), r#"a="" b=x c="" d="""#);
+	assert_eq!(logfmt(&pairs).skip_empty().to_string(), "b=x");
+	assert_eq!(logfmt(&pairs[2..]).skip_empty().to_string(), "");
+	assert_eq!(logfmt([("k", ""); 0]).to_string(), "");
+
+	// Non-string values in input order
+	let (count, ratio, ok) = (3, 0.5, true);
+	let line = crate::logfmt! {
+		z: count,
+		a: ratio,
+		ok: ok,
+		"list": crate::join(", ", [1, 2]),
+		empty: crate::fmt!(),
+		chunks: crate::fmt!("x" {1} "=" {2}),
+	};
+	assert_eq!(line.to_string(), r#"z=3 a=0.5 ok=true list="1, 2" empty="" chunks="x1=2""#);
+	assert_eq!(crate::format!({line:?}), line.to_string());
+
+	// Formatting options are not forwarded to the values
+	assert_eq!(format!("{:>5}", logfmt([("n", 1)])), "n=1");
+}
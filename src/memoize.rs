@@ -0,0 +1,174 @@
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+/// Displays the value once and reuses the cached string afterwards.
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// let calls = Cell::new(0);
+/// let header = fmtools::memoize(fmtools::fmt(|f| {
+/// 	calls.set(calls.get() + 1);
+/// 	f.write_str("header")
+/// }));
+/// assert_eq!(header.as_str(), None);
+/// assert_eq!(header.to_string(), "header");
+/// assert_eq!(header.to_string(), "header");
+/// assert_eq!(header.as_str(), Some("header"));
+/// assert_eq!(calls.get(), 1);
+/// ```
+///
+/// Rendering the value is done without any formatting options.
+/// The cached string is then formatted as a `str`, respecting width, fill, alignment and precision.
+///
+/// If the value returns an error it is not cached and the next display tries again.
+/// When displayed from several threads at once the value is rendered by one thread while the others wait for the result.
+#[inline]
+pub fn memoize<T: fmt::Display>(value: T) -> Memoize<T> {
+	Memoize { value, cache: OnceLock::new(), lock: Mutex::new(()) }
+}
+
+/// Returns a displayable object which runs the closure once and reuses its output.
+///
+/// Shorthand for [`memoize`]`(`[`fmt`](crate::fmt())`(closure))`:
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// let walks = Cell::new(0);
+/// let tree = fmtools::fmt_cached(|f| {
+/// 	walks.set(walks.get() + 1);
+/// 	f.write_str("root(a, b(c))")
+/// });
+///
+/// let log = fmtools::format!("before: "{tree}"\nafter: "{tree}"\n");
+/// assert_eq!(log, "before: root(a, b(c))\nafter: root(a, b(c))\n");
+/// assert_eq!(walks.get(), 1);
+/// ```
+///
+/// The closure is invoked without any formatting options, see [`memoize`] for how the cached string is formatted.
+#[inline]
+pub fn fmt_cached<F: Fn(&mut fmt::Formatter) -> fmt::Result>(closure: F) -> Memoize<crate::fmt<F>> {
+	memoize(crate::fmt(closure))
+}
+
+/// Memoized displayable object.
+///
+/// See [`memoize`] for more information.
+///
+/// Cloning the object clones the cached string if the value has been rendered.
+pub struct Memoize<T> {
+	value: T,
+	cache: OnceLock<String>,
+	// Serializes the renders so the value is rendered once, a failed render leaves the cache empty
+	lock: Mutex<()>,
+}
+
+impl<T: Clone> Clone for Memoize<T> {
+	fn clone(&self) -> Memoize<T> {
+		Memoize { value: self.value.clone(), cache: self.cache.clone(), lock: Mutex::new(()) }
+	}
+}
+
+impl<T: fmt::Display> Memoize<T> {
+	/// Returns the cached string if the value has been rendered.
+	#[inline]
+	pub fn as_str(&self) -> Option<&str> {
+		self.cache.get().map(String::as_str)
+	}
+
+	fn render(&self) -> Result<&str, fmt::Error> {
+		if let Some(s) = self.cache.get() {
+			return Ok(s);
+		}
+		let _guard = self.lock.lock().unwrap_or_else(|err| err.into_inner());
+		if let Some(s) = self.cache.get() {
+			return Ok(s);
+		}
+		let mut s = String::new();
+		fmt::write(&mut s, format_args!("{}", self.value))?;
+		Ok(self.cache.get_or_init(move || s))
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Memoize<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(self.render()?)
+	}
+}
+impl<T: fmt::Display> fmt::Debug for Memoize<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(self.render()?)
+	}
+}
+
+#[test]
+fn tests() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static CALLS: AtomicUsize = AtomicUsize::new(0);
+	let value = memoize(crate::fmt(|f| {
+		let n = CALLS.fetch_add(1, Ordering::Relaxed);
+		write!(f, "call {}", n)
+	}));
+	assert_eq!(value.to_string(), "call 0");
+	assert_eq!(value.to_string(), "call 0");
+	assert_eq!(format!("[{:>8}]", value), "[  call 0]");
+
+	std::thread::scope(|s| {
+		for _ in 0..4 {
+			s.spawn(|| assert_eq!(value.to_string(), "call 0"));
+		}
+	});
+	assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+	// Threads racing on the first render
+	let renders = AtomicUsize::new(0);
+	let barrier = std::sync::Barrier::new(8);
+	let racy = memoize(crate::fmt(|f| {
+		renders.fetch_add(1, Ordering::Relaxed);
+		std::thread::sleep(std::time::Duration::from_millis(10));
+		f.write_str("first")
+	}));
+	std::thread::scope(|s| {
+		for _ in 0..8 {
+			s.spawn(|| {
+				barrier.wait();
+				assert_eq!(racy.to_string(), "first");
+			});
+		}
+	});
+	assert_eq!(renders.load(Ordering::Relaxed), 1);
+
+	// Errors are not cached
+	let fails = memoize(crate::fmt(|_| Err(fmt::Error)));
+	assert!(fmt::write(&mut String::new(), format_args!("{}", fails)).is_err());
+	assert_eq!(fails.as_str(), None);
+
+	// Cached closures run once across formats, padding applies to the cached string
+	let runs = std::cell::Cell::new(0);
+	let cached = fmt_cached(|f| {
+		runs.set(runs.get() + 1);
+		f.write_str("tree")
+	});
+	assert_eq!(cached.to_string(), "tree");
+	assert_eq!(format!("[{:>6}|{:<6}|{:^6}]", cached, cached, cached), "[  tree|tree  | tree ]");
+	assert_eq!(format!("{:.2?}", cached), "tr");
+	assert_eq!(runs.get(), 1);
+
+	let cloned = cached.clone();
+	assert_eq!(cloned.as_str(), Some("tree"));
+	assert_eq!(cloned.to_string(), "tree");
+	assert_eq!(runs.get(), 1);
+
+	// Failed renders are retried
+	let attempts = std::cell::Cell::new(0);
+	let flaky = fmt_cached(|f| {
+		attempts.set(attempts.get() + 1);
+		if attempts.get() == 1 { Err(fmt::Error) } else { f.write_str("ok") }
+	});
+	assert!(fmt::write(&mut String::new(), format_args!("{}", flaky)).is_err());
+	assert_eq!(flaky.to_string(), "ok");
+	assert_eq!(flaky.to_string(), "ok");
+	assert_eq!(attempts.get(), 2);
+}
@@ -0,0 +1,49 @@
+use core::fmt;
+
+/// Displays nothing.
+///
+/// ```
+/// let (count, truncated) = (10, false);
+/// let note = fmtools::either(truncated, " (truncated)", fmtools::nothing());
+/// let s = fmtools::format!({count}" items"{note});
+/// assert_eq!(s, "10 items");
+/// ```
+///
+/// Useful as the empty branch of [`Either`](crate::Either) and as a default value in generic code.
+#[inline]
+pub const fn nothing() -> Nothing {
+	Nothing
+}
+
+/// Zero-sized displayable object which writes nothing.
+///
+/// Returned by [`nothing`]. The formatting options are ignored, padding an empty output writes nothing.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Nothing;
+
+impl fmt::Display for Nothing {
+	#[inline]
+	fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+#[test]
+fn tests() {
+	assert_eq!(core::mem::size_of::<Nothing>(), 0);
+	assert_eq!(nothing().to_string(), "");
+	assert_eq!(format!("[{:>5}]", nothing()), "[]");
+	assert_eq!(format!("{:?}", nothing()), "Nothing");
+
+	// Empty branches
+	let s = crate::format!({crate::either(false, 1, nothing())}"|"{crate::cond(true, nothing())});
+	assert_eq!(s, "|");
+	struct Row {
+		name: &'static str,
+		note: crate::Either<&'static str, Nothing>,
+	}
+	let rows = [Row { name: "a", note: crate::left(" (new)") }, Row { name: "b", note: crate::right(Nothing) }];
+	let s = crate::format!(for row in &rows { {row.name}{row.note}";" });
+	assert_eq!(s, "a (new);b;");
+	assert_eq!(crate::format!(html "<p>"{nothing()}"</p>"), "<p></p>");
+}
@@ -0,0 +1,413 @@
+use core::fmt;
+
+/// Decimal and grouping separators for [`number`].
+///
+/// This is not full localization, only mechanical separator substitution and digit grouping.
+///
+/// The group sizes are listed from the decimal point outwards, the last size repeats.
+/// For example the Indian system `12,34,56,789` groups by `[3, 2]`.
+/// An empty list or a group size of zero disables (further) grouping.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NumberFormat<'a> {
+	/// Separator between the integer and fraction digits.
+	pub decimal: char,
+	/// Separator between groups of integer digits.
+	pub grouping: char,
+	/// Sizes of the digit groups from the decimal point outwards.
+	pub groups: &'a [u8],
+}
+
+impl NumberFormat<'static> {
+	/// English style `1,234,567.89`.
+	pub const EN_US: NumberFormat<'static> = NumberFormat { decimal: '.', grouping: ',', groups: &[3] };
+	/// German style `1.234.567,89`.
+	pub const DE_DE: NumberFormat<'static> = NumberFormat { decimal: ',', grouping: '.', groups: &[3] };
+	/// Indian style `12,34,567.89`.
+	pub const EN_IN: NumberFormat<'static> = NumberFormat { decimal: '.', grouping: ',', groups: &[3, 2] };
+}
+
+impl<'a> NumberFormat<'a> {
+	/// Constructs a new number format.
+	#[inline]
+	pub const fn new(decimal: char, grouping: char, groups: &'a [u8]) -> NumberFormat<'a> {
+		NumberFormat { decimal, grouping, groups }
+	}
+
+	// Returns if a separator goes before the integer digit with `n` digits to its right.
+	fn is_boundary(&self, n: usize) -> bool {
+		let mut pos = 0;
+		let mut size = 0;
+		for (i, &group) in self.groups.iter().enumerate() {
+			size = group as usize;
+			if size == 0 {
+				return false;
+			}
+			pos += size;
+			if pos == n {
+				return true;
+			}
+			if pos > n || i + 1 == self.groups.len() {
+				break;
+			}
+		}
+		size != 0 && n > pos && (n - pos) % size == 0
+	}
+}
+
+/// Displays a number with the given decimal separator and digit grouping.
+///
+/// ```
+/// use fmtools::NumberFormat;
+///
+/// let value = -1234567.891;
+/// assert_eq!(fmtools::format!({fmtools::number(value, &NumberFormat::EN_US):.2}), "-1,234,567.89");
+/// assert_eq!(fmtools::format!({fmtools::number(value, &NumberFormat::DE_DE):.2}), "-1.234.567,89");
+/// assert_eq!(fmtools::format!({fmtools::number(value, &NumberFormat::EN_IN):.2}), "-12,34,567.89");
+/// ```
+///
+/// The formatting specifiers for width, alignment, sign and precision are supported.
+/// Zero padding is inserted after the sign and is not grouped.
+///
+/// ```
+/// use fmtools::NumberFormat;
+///
+/// let n = fmtools::number(-12345, &NumberFormat::EN_US);
+/// assert_eq!(fmtools::format!("["{n:>9}"]["{n:<9}"]["{n:09}"]"), "[  -12,345][-12,345  ][-0012,345]");
+/// ```
+#[inline]
+pub fn number<'a, T: Numeric>(value: T, format: &'a NumberFormat<'a>) -> Number<'a, T> {
+	Number { value, format }
+}
+
+/// Primitive integers and floats supported by [`number`].
+pub trait Numeric: fmt::Display + Copy + private::Sealed {}
+
+mod private {
+	pub trait Sealed {}
+}
+
+macro_rules! impl_numeric {
+	($($ty:ty),*) => {
+		$(
+			impl private::Sealed for $ty {}
+			impl Numeric for $ty {}
+		)*
+	};
+}
+impl_numeric!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Displayable object returned by [`number`].
+#[derive(Copy, Clone)]
+pub struct Number<'a, T> {
+	value: T,
+	format: &'a NumberFormat<'a>,
+}
+
+impl<T: Numeric> fmt::Display for Number<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let precision = f.precision();
+
+		// Measure the output to find the grouping and padding
+		let mut measure = Measure { len: 0, negative: false, int_digits: 0, in_int: true };
+		write_value(&mut measure, self.value, precision)?;
+		let separators = (1..measure.int_digits).filter(|&n| self.format.is_boundary(n)).count();
+		let sign = if measure.negative { "-" } else if f.sign_plus() { "+" } else { "" };
+		let len = sign.len() + measure.len + separators;
+		let padding = f.width().unwrap_or(0).saturating_sub(len);
+
+		let (pre, post) = if f.sign_aware_zero_pad() {
+			(0, 0)
+		}
+		else {
+			match f.align() {
+				Some(fmt::Alignment::Left) => (0, padding),
+				Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+				_ => (padding, 0),
+			}
+		};
+		let fill = f.fill();
+		crate::pad::write_fill(f, fill, pre)?;
+		f.write_str(sign)?;
+		if f.sign_aware_zero_pad() {
+			crate::pad::write_fill(f, '0', padding)?;
+		}
+		let mut writer = Grouping { f, format: self.format, remaining: measure.int_digits };
+		write_value(&mut writer, self.value, precision)?;
+		crate::pad::write_fill(f, fill, post)
+	}
+}
+debug_as_display!([T: Numeric] Number<'_, T>);
+
+fn write_value<W: fmt::Write, T: fmt::Display>(w: &mut W, value: T, precision: Option<usize>) -> fmt::Result {
+	match precision {
+		Some(precision) => write!(w, "{:.*}", precision, value),
+		None => write!(w, "{}", value),
+	}
+}
+
+// Counts the chars (without sign) and the integer digits.
+struct Measure {
+	len: usize,
+	negative: bool,
+	int_digits: usize,
+	in_int: bool,
+}
+impl fmt::Write for Measure {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for chr in s.chars() {
+			match chr {
+				'-' => self.negative = true,
+				'0'..='9' if self.in_int => {
+					self.int_digits += 1;
+					self.len += 1;
+				},
+				'.' => {
+					self.in_int = false;
+					self.len += 1;
+				},
+				_ => self.len += 1,
+			}
+		}
+		Ok(())
+	}
+}
+
+// Writes the digits with the separators substituted.
+struct Grouping<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	format: &'a NumberFormat<'a>,
+	remaining: usize,
+}
+impl fmt::Write for Grouping<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for chr in s.chars() {
+			match chr {
+				'-' => (),
+				'0'..='9' if self.remaining > 0 => {
+					self.f.write_char(chr)?;
+					self.remaining -= 1;
+					if self.remaining > 0 && self.format.is_boundary(self.remaining) {
+						self.f.write_char(self.format.grouping)?;
+					}
+				},
+				'.' => self.f.write_char(self.format.decimal)?,
+				_ => self.f.write_char(chr)?,
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Groups the digits on both sides of the decimal point in threes.
+///
+/// ```
+/// let s = fmtools::group_frac(fmtools::float_fixed(3.141592653589793, 9), ',', '\u{202f}').to_string();
+/// assert_eq!(s, "3.141\u{202f}592\u{202f}654");
+///
+/// let s = fmtools::format!({fmtools::group_frac(-1234567.25, ',', ' '):.5});
+/// assert_eq!(s, "-1,234,567.250 00");
+/// ```
+///
+/// The integer digits are grouped from the decimal point outwards with the integer separator,
+/// the fraction digits are grouped from the decimal point outwards with the fraction separator, commonly a narrow no-break space (U+202F).
+///
+/// Any displayable object is accepted, the first number in its output is grouped and the rest is written as is.
+/// This composes with other number formatting, eg. [`float_fixed`](crate::float_fixed()) or trimmed compact floats.
+/// The output is rendered twice, first to measure the digits and then to write the grouped digits.
+///
+/// The formatting specifiers for width, fill and alignment are supported, the precision is forwarded to the value.
+#[inline]
+pub fn group_frac<T: fmt::Display>(value: T, int_sep: char, frac_sep: char) -> GroupFrac<T> {
+	GroupFrac { value, int_sep, frac_sep }
+}
+
+/// Displayable object returned by [`group_frac`].
+#[derive(Copy, Clone)]
+pub struct GroupFrac<T> {
+	value: T,
+	int_sep: char,
+	frac_sep: char,
+}
+
+impl<T: fmt::Display> fmt::Display for GroupFrac<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let precision = f.precision();
+
+		// Measure the output to find the grouping and padding
+		let mut measure = FracMeasure { scan: FracScan::Before, width: 0, int_digits: 0, frac_digits: 0 };
+		write_value(&mut measure, &self.value, precision)?;
+		let int_seps = measure.int_digits.saturating_sub(1) / 3;
+		let frac_seps = measure.frac_digits.saturating_sub(1) / 3;
+		let len = measure.width
+			+ int_seps * crate::width::char_width(self.int_sep)
+			+ frac_seps * crate::width::char_width(self.frac_sep);
+		let padding = f.width().unwrap_or(0).saturating_sub(len);
+		let (pre, post) = match f.align() {
+			Some(fmt::Alignment::Left) => (0, padding),
+			Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+			_ => (padding, 0),
+		};
+
+		let fill = f.fill();
+		crate::pad::write_fill(f, fill, pre)?;
+		let mut writer = FracGrouping {
+			f,
+			scan: FracScan::Before,
+			int_sep: self.int_sep,
+			frac_sep: self.frac_sep,
+			int_remaining: measure.int_digits,
+			frac_written: 0,
+		};
+		write_value(&mut writer, &self.value, precision)?;
+		crate::pad::write_fill(f, fill, post)
+	}
+}
+debug_as_display!([T: fmt::Display] GroupFrac<T>);
+
+// Position in the output relative to the first number.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum FracScan {
+	Before,
+	Int,
+	Frac,
+	After,
+}
+impl FracScan {
+	fn next(self, chr: char) -> FracScan {
+		match (self, chr) {
+			(FracScan::Before, '0'..='9') => FracScan::Int,
+			(FracScan::Before, _) => FracScan::Before,
+			(FracScan::Int, '0'..='9') => FracScan::Int,
+			(FracScan::Int, '.') => FracScan::Frac,
+			(FracScan::Frac, '0'..='9') => FracScan::Frac,
+			_ => FracScan::After,
+		}
+	}
+}
+
+// Counts the width and the integer and fraction digits of the first number.
+struct FracMeasure {
+	scan: FracScan,
+	width: usize,
+	int_digits: usize,
+	frac_digits: usize,
+}
+impl fmt::Write for FracMeasure {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for chr in s.chars() {
+			let prev = self.scan;
+			self.scan = self.scan.next(chr);
+			match self.scan {
+				FracScan::Int => self.int_digits += 1,
+				FracScan::Frac if prev == FracScan::Frac => self.frac_digits += 1,
+				_ => (),
+			}
+			self.width += crate::width::char_width(chr);
+		}
+		Ok(())
+	}
+}
+
+// Writes the output with the separators inserted between the digit groups.
+struct FracGrouping<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	scan: FracScan,
+	int_sep: char,
+	frac_sep: char,
+	int_remaining: usize,
+	frac_written: usize,
+}
+impl fmt::Write for FracGrouping<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for chr in s.chars() {
+			let prev = self.scan;
+			self.scan = self.scan.next(chr);
+			match self.scan {
+				FracScan::Int => {
+					self.f.write_char(chr)?;
+					// The value may write more digits than measured if it renders differently the second time
+					self.int_remaining = self.int_remaining.saturating_sub(1);
+					if self.int_remaining > 0 && self.int_remaining % 3 == 0 {
+						self.f.write_char(self.int_sep)?;
+					}
+				},
+				FracScan::Frac if prev == FracScan::Frac => {
+					if self.frac_written > 0 && self.frac_written % 3 == 0 {
+						self.f.write_char(self.frac_sep)?;
+					}
+					self.f.write_char(chr)?;
+					self.frac_written += 1;
+				},
+				_ => self.f.write_char(chr)?,
+			}
+		}
+		Ok(())
+	}
+}
+
+#[test]
+fn tests() {
+	#[track_caller]
+	fn check(t: impl fmt::Display, s: &str) {
+		assert_eq!(t.to_string(), s);
+	}
+
+	let us = &NumberFormat::EN_US;
+	let de = &NumberFormat::DE_DE;
+	let ind = &NumberFormat::EN_IN;
+
+	// Integers
+	check(number(0, us), "0");
+	check(number(999, us), "999");
+	check(number(1000, us), "1,000");
+	check(number(-1234567, us), "-1,234,567");
+	check(number(u64::MAX, us), "18,446,744,073,709,551,615");
+	check(number(i8::MIN, de), "-128");
+	check(number(1234567, de), "1.234.567");
+	check(number(1234567, ind), "12,34,567");
+	check(number(123456789, ind), "12,34,56,789");
+	check(number(-100000, ind), "-1,00,000");
+	check(number(1234567, &NumberFormat::new('.', '\'', &[])), "1234567");
+	check(number(1234567, &NumberFormat::new('.', ' ', &[2, 0])), "12345 67");
+
+	// Floats
+	check(number(0.5, us), "0.5");
+	check(number(1234.5, de), "1.234,5");
+	check(crate::fmt!({number(-1234567.891, ind):.1}), "-12,34,567.9");
+	check(crate::fmt!({number(1234.0f32, de):.3}), "1.234,000");
+	check(crate::fmt!({number(0.001, us):.2}), "0.00");
+	check(number(f64::NAN, us), "NaN");
+	check(number(f64::NEG_INFINITY, us), "-inf");
+
+	// Padding and sign
+	check(crate::fmt!({number(1234, us):+}), "+1,234");
+	check(crate::fmt!({number(1234, us):*^9}), "**1,234**");
+	check(crate::fmt!({number(-1234.5, de):010.2}), "-01.234,50");
+	check(crate::fmt!({number(1234, us):3}), "1,234");
+
+	// Grouped fractions
+	let nnbsp = '\u{202f}';
+	check(group_frac(0.1234567890123, ',', ' '), "0.123 456 789 012 3");
+	check(group_frac(crate::float_fixed(1234.5, 9), '\'', nnbsp), "1'234.500\u{202f}000\u{202f}000");
+	check(group_frac(1234567, ',', ' '), "1,234,567");
+	check(group_frac(-123, ',', ' '), "-123");
+	check(group_frac(123.25, nnbsp, nnbsp), "123.25");
+	check(group_frac(1234.0, nnbsp, nnbsp), "1\u{202f}234");
+	check(group_frac("1234.56789 s, 1234", ',', ' '), "1,234.567 89 s, 1234");
+	check(group_frac(1e-7, ',', ' '), "0.000 000 1");
+	check(group_frac(f64::NAN, ',', ' '), "NaN");
+	check(group_frac("", ',', ' '), "");
+	check(crate::fmt!({group_frac(1234.5, ',', nnbsp):.4}), "1,234.500\u{202f}0");
+	check(crate::fmt!("["{group_frac(1234.56789, ',', nnbsp):>13}"]"), "[ 1,234.567\u{202f}89]");
+	check(crate::fmt!("["{group_frac(-0.5, ',', ' '):*<6}"]"), "[-0.5**]");
+
+	// Output which grows between the renders
+	let renders = core::cell::Cell::new(0);
+	let growing = crate::fmt(|f| { renders.set(renders.get() + 1); f.write_str(if renders.get() == 1 { "999" } else { "12345" }) });
+	check(group_frac(growing, ',', ' '), "12345");
+	#[cfg(feature = "tiny-float")]
+	{
+		check(group_frac(crate::f64_compact(2.5, 6).trim_zeros(), ',', nnbsp), "2.5");
+		check(group_frac(crate::f64_compact(12345.678901, 6).trim_zeros(), ',', nnbsp), "12,345.678\u{202f}901");
+	}
+}
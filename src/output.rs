@@ -0,0 +1,234 @@
+/*!
+Redirectable output of the [`print!`](crate::print!) and [`println!`](crate::println!) macros.
+
+By default the prelude print macros write to stdout, same as the standard macros.
+The output can be redirected in two ways:
+
+* [`with_capture`] captures the output printed by the current thread while running a closure.
+  Captures are thread-local, scoped and nest: the innermost capture receives the output.
+
+* [`set_writer`] redirects the output of all threads to a writer until it is removed with [`take_writer`].
+  The writer is global and protected by a lock, threads printing at the same time write their output one whole call at a time.
+
+A thread-local capture takes precedence over the global writer.
+
+```
+fn greet(name: &str) {
+	fmtools::println!("Hello "{name}"!");
+}
+
+let s = fmtools::output::with_capture(|| greet("world"));
+assert_eq!(s, "Hello world!\n");
+```
+
+Only the stdout macros are redirected, [`eprint!`](crate::eprint!) and [`eprintln!`](crate::eprintln!) always write to stderr.
+*/
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::{fmt, io};
+
+/// Writer type accepted by [`set_writer`].
+pub type Writer = Box<dyn io::Write + Send>;
+
+static WRITER: Mutex<Option<Writer>> = Mutex::new(None);
+// Avoids taking the lock when no writer is set
+static WRITER_SET: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+	// Stack of nested captures on this thread
+	static CAPTURES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn lock() -> MutexGuard<'static, Option<Writer>> {
+	// The writer is never left in an inconsistent state, ignore poisoning
+	WRITER.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Redirects the output of the print macros in all threads to the writer.
+///
+/// Returns the previous writer, if any.
+///
+/// ```
+/// use std::io;
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Clone, Default)]
+/// struct Shared(Arc<Mutex<Vec<u8>>>);
+/// impl io::Write for Shared {
+/// 	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+/// 		self.0.lock().unwrap().write(buf)
+/// 	}
+/// 	fn flush(&mut self) -> io::Result<()> {
+/// 		Ok(())
+/// 	}
+/// }
+///
+/// let shared = Shared::default();
+/// fmtools::output::set_writer(Box::new(shared.clone()));
+/// fmtools::println!("status: "{"ready"});
+/// fmtools::output::take_writer();
+///
+/// assert_eq!(*shared.0.lock().unwrap(), b"status: ready\n");
+/// ```
+///
+/// Each call of the print macros is formatted before the lock is taken and written with a single `write_all`.
+/// Like the standard print macros, the print macros panic if writing fails.
+pub fn set_writer(writer: Writer) -> Option<Writer> {
+	let mut guard = lock();
+	WRITER_SET.store(true, Ordering::Release);
+	guard.replace(writer)
+}
+
+/// Removes the writer set by [`set_writer`], the print macros write to stdout again.
+///
+/// The writer is flushed before it is returned.
+pub fn take_writer() -> Option<Writer> {
+	let mut guard = lock();
+	WRITER_SET.store(false, Ordering::Release);
+	let mut writer = guard.take();
+	if let Some(writer) = &mut writer {
+		let _ = writer.flush();
+	}
+	writer
+}
+
+/// Captures the output of the print macros in the current thread while running the closure.
+///
+/// ```
+/// let s = fmtools::output::with_capture(|| {
+/// 	fmtools::print!("outer ");
+/// 	let inner = fmtools::output::with_capture(|| fmtools::print!("inner"));
+/// 	assert_eq!(inner, "inner");
+/// 	fmtools::print!("again");
+/// });
+/// assert_eq!(s, "outer again");
+/// ```
+///
+/// Captures nest, output is only captured by the innermost capture.
+/// The capture is removed when the closure panics.
+///
+/// Output printed by other threads, including threads spawned by the closure, is not captured.
+/// Use [`set_writer`] to capture output from other threads.
+pub fn with_capture<F: FnOnce()>(f: F) -> String {
+	struct Guard;
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			CAPTURES.with(|captures| captures.borrow_mut().pop());
+		}
+	}
+
+	CAPTURES.with(|captures| captures.borrow_mut().push(String::new()));
+	let guard = Guard;
+	f();
+	let s = CAPTURES.with(|captures| captures.borrow_mut().last_mut().map(std::mem::take));
+	drop(guard);
+	s.unwrap_or_default()
+}
+
+#[doc(hidden)]
+pub fn __print(args: fmt::Arguments) {
+	let capturing = CAPTURES.with(|captures| !captures.borrow().is_empty());
+	if capturing {
+		// Format before borrowing, the values may print themselves
+		let s = fmt_string(args);
+		CAPTURES.with(|captures| {
+			if let Some(capture) = captures.borrow_mut().last_mut() {
+				capture.push_str(&s);
+			}
+		});
+		return;
+	}
+
+	if WRITER_SET.load(Ordering::Acquire) {
+		// Format before locking, the values may print themselves
+		let s = fmt_string(args);
+		let mut guard = lock();
+		if let Some(writer) = &mut *guard {
+			if let Err(err) = writer.write_all(s.as_bytes()) {
+				drop(guard);
+				panic!("failed printing to output writer: {}", err);
+			}
+			return;
+		}
+		drop(guard);
+		return print_stdout(format_args!("{}", s));
+	}
+
+	print_stdout(args);
+}
+
+fn fmt_string(args: fmt::Arguments) -> String {
+	match args.as_str() {
+		Some(s) => String::from(s),
+		None => args.to_string(),
+	}
+}
+
+fn print_stdout(args: fmt::Arguments) {
+	std::print!("{}", args);
+}
+
+#[test]
+fn tests() {
+	use std::sync::Arc;
+
+	// Nested captures
+	let mut inner = String::new();
+	let outer = with_capture(|| {
+		crate::print!("a");
+		inner = with_capture(|| crate::println!({"b"}{1}));
+		crate::println!("c"{2});
+	});
+	assert_eq!((outer.as_str(), inner.as_str()), ("ac2\n", "b1\n"));
+
+	// Values which print while being formatted
+	let s = with_capture(|| crate::print!("x"{crate::fmt(|f| { crate::print!("y"); f.write_str("z") })}));
+	assert_eq!(s, "yxz");
+
+	// Capture removed on panic
+	let s = with_capture(|| {
+		let result = std::panic::catch_unwind(|| {
+			let _ = with_capture(|| panic!("boom"));
+		});
+		assert!(result.is_err());
+		crate::print!("after");
+	});
+	assert_eq!(s, "after");
+	assert!(CAPTURES.with(|captures| captures.borrow().is_empty()));
+
+	// Other threads are not captured
+	let s = with_capture(|| {
+		std::thread::spawn(|| with_capture(|| crate::print!("thread"))).join().unwrap();
+	});
+	assert_eq!(s, "");
+
+	// Global writer shared with spawned threads
+	#[derive(Clone, Default)]
+	struct Shared(Arc<Mutex<Vec<u8>>>);
+	impl io::Write for Shared {
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+			self.0.lock().unwrap().write(buf)
+		}
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+	let shared = Shared::default();
+	assert!(set_writer(Box::new(shared.clone())).is_none());
+	std::thread::spawn(|| {
+		for i in 0..3 {
+			crate::println!("from thread "{i});
+		}
+	}).join().unwrap();
+	let captured = with_capture(|| crate::print!("captured"));
+	assert!(take_writer().is_some());
+	assert!(take_writer().is_none());
+
+	// Other tests may print in the meantime
+	let written = String::from_utf8(shared.0.lock().unwrap().clone()).unwrap();
+	assert!(written.contains("from thread 0\nfrom thread 1\nfrom thread 2\n"));
+	assert!(!written.contains("captured"));
+	assert_eq!(captured, "captured");
+}
@@ -0,0 +1,273 @@
+use core::fmt;
+
+/// Alignment of content within a column.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Align {
+	/// Content on the left, padding on the right.
+	#[default]
+	Left,
+	/// Content in the center, padding on both sides with the extra on the right.
+	Center,
+	/// Content on the right, padding on the left.
+	Right,
+}
+
+/// Pads the value with spaces to at least the given width.
+///
+/// ```
+/// use fmtools::Align;
+///
+/// let s = fmtools::format!("["{fmtools::pad(8, Align::Right, fmtools::fmt!({1}"."{5}))}"]");
+/// assert_eq!(s, "[     1.5]");
+/// ```
+///
+/// Unlike the standard width specifier this works for any displayable object.
+/// The value is displayed twice: once to measure its width and once to write it.
+///
+/// The width is measured in chars, with the `unicode-width` feature in display columns where wide chars such as CJK and emoji take two columns.
+#[inline]
+pub fn pad<T: fmt::Display>(width: usize, align: Align, value: T) -> Padded<T> {
+	Padded { value, width, align }
+}
+
+/// Centers the value with spaces to at least the given width.
+///
+/// ```
+/// let s = fmtools::format!("["{fmtools::center(7, "abc")}"]");
+/// assert_eq!(s, "[  abc  ]");
+/// ```
+#[inline]
+pub fn center<T: fmt::Display>(width: usize, value: T) -> Padded<T> {
+	Padded { value, width, align: Align::Center }
+}
+
+/// Displayable object returned by [`pad`] and [`center`].
+#[derive(Copy, Clone)]
+pub struct Padded<T> {
+	value: T,
+	width: usize,
+	align: Align,
+}
+impl<T: fmt::Display> fmt::Display for Padded<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let width = width_of(&self.value)?;
+		write_padded(f, &self.value, self.width.saturating_sub(width), self.align)
+	}
+}
+debug_as_display!([T: fmt::Display] Padded<T>);
+
+/// Applies the width, fill, alignment and precision of the formatter to the whole output of the value, like they do for strings.
+///
+/// ```
+/// let x = 42;
+/// let value = fmtools::with_options(fmtools::fmt(|f| write!(f, "[{}]", x)));
+/// assert_eq!(format!("{:>8}|{:^8}|{:.3}|", value, value, value), "    [42]|  [42]  |[42|");
+/// ```
+///
+/// Templates built by [fmt!](crate::fmt!) already do this, wrap closures passed to [`fmt()`](crate::fmt()) and other values which pass the options through.
+///
+/// The default alignment is left, the width is measured in chars and the precision truncates the output to a number of chars.
+/// When these options are set the value is displayed without them, twice if the width is set: once to measure the output and once to write it.
+/// Without these options the value is displayed directly with the formatter.
+#[inline]
+pub fn with_options<T: fmt::Display>(value: T) -> WithOptions<T> {
+	WithOptions { value }
+}
+
+/// Displayable object returned by [`with_options`].
+#[derive(Copy, Clone)]
+pub struct WithOptions<T> {
+	value: T,
+}
+impl<T: fmt::Display> fmt::Display for WithOptions<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if f.width().is_none() && f.precision().is_none() {
+			return self.value.fmt(f);
+		}
+		write_with_options(f, &|f: &mut fmt::Formatter| self.value.fmt(f))
+	}
+}
+debug_as_display!([T: fmt::Display] WithOptions<T>);
+
+// Applies the width, fill, alignment and precision to the output of the templates built by fmt!.
+#[doc(hidden)]
+#[inline]
+pub fn __fmt_options<F: Fn(&mut fmt::Formatter) -> fmt::Result>(f: &mut fmt::Formatter, closure: F) -> fmt::Result {
+	if f.width().is_none() && f.precision().is_none() {
+		return closure(f);
+	}
+	write_with_options(f, &closure)
+}
+
+/// Measures the width of the value's output.
+pub(crate) fn width_of<T: fmt::Display + ?Sized>(value: &T) -> Result<usize, fmt::Error> {
+	struct Measure(usize);
+	impl fmt::Write for Measure {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			self.0 += crate::width::str_width(s);
+			Ok(())
+		}
+	}
+	let mut measure = Measure(0);
+	fmt::write(&mut measure, format_args!("{}", value))?;
+	Ok(measure.0)
+}
+
+/// Writes the value with the padding distributed according to the alignment.
+pub(crate) fn write_padded<T: fmt::Display + ?Sized>(f: &mut fmt::Formatter, value: &T, padding: usize, align: Align) -> fmt::Result {
+	let (pre, post) = match align {
+		Align::Left => (0, padding),
+		Align::Center => (padding / 2, padding - padding / 2),
+		Align::Right => (padding, 0),
+	};
+	write_fill(f, ' ', pre)?;
+	write!(f, "{}", value)?;
+	write_fill(f, ' ', post)
+}
+
+/// Writes the output of the closure with the width, fill, alignment and precision of the formatter, like a string.
+///
+/// The closure is invoked with a formatter without these options, twice if the width is set.
+pub(crate) fn write_with_options<F: Fn(&mut fmt::Formatter) -> fmt::Result + ?Sized>(f: &mut fmt::Formatter, closure: &F) -> fmt::Result {
+	struct Plain<'a, F: ?Sized>(&'a F);
+	impl<F: Fn(&mut fmt::Formatter) -> fmt::Result + ?Sized> fmt::Display for Plain<'_, F> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			(self.0)(f)
+		}
+	}
+	struct Discard;
+	impl fmt::Write for Discard {
+		fn write_str(&mut self, _s: &str) -> fmt::Result {
+			Ok(())
+		}
+	}
+	let plain = Plain(closure);
+	let alternate = f.alternate();
+	let max_chars = f.precision().unwrap_or(usize::MAX);
+	let padding = match f.width() {
+		Some(width) => width.saturating_sub(write_chars(Discard, &plain, alternate, max_chars)?),
+		None => 0,
+	};
+	let (pre, post) = match f.align() {
+		Some(fmt::Alignment::Right) => (padding, 0),
+		Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+		_ => (0, padding),
+	};
+	let fill = f.fill();
+	write_fill(f, fill, pre)?;
+	write_chars(&mut *f, &plain, alternate, max_chars)?;
+	write_fill(f, fill, post)
+}
+
+// Writes at most the number of chars of the value, returns the number of chars written.
+fn write_chars<W: fmt::Write, T: fmt::Display + ?Sized>(inner: W, value: &T, alternate: bool, max_chars: usize) -> Result<usize, fmt::Error> {
+	struct CharsWriter<W> {
+		inner: W,
+		remaining: usize,
+		exceeded: bool,
+	}
+	impl<W: fmt::Write> fmt::Write for CharsWriter<W> {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			if let Some((i, _)) = s.char_indices().nth(self.remaining) {
+				self.inner.write_str(&s[..i])?;
+				self.remaining = 0;
+				self.exceeded = true;
+				return Err(fmt::Error);
+			}
+			self.remaining -= s.chars().count();
+			self.inner.write_str(s)
+		}
+	}
+	let mut writer = CharsWriter { inner, remaining: max_chars, exceeded: false };
+	let result = match alternate {
+		true => fmt::write(&mut writer, format_args!("{:#}", value)),
+		false => fmt::write(&mut writer, format_args!("{}", value)),
+	};
+	match result {
+		Err(_) if writer.exceeded => Ok(max_chars),
+		Err(err) => Err(err),
+		Ok(()) => Ok(max_chars - writer.remaining),
+	}
+}
+
+pub(crate) fn write_fill<W: fmt::Write + ?Sized>(f: &mut W, fill: char, n: usize) -> fmt::Result {
+	for _ in 0..n {
+		f.write_char(fill)?;
+	}
+	Ok(())
+}
+
+#[test]
+fn tests() {
+	assert_eq!(pad(5, Align::Left, "ab").to_string(), "ab   ");
+	assert_eq!(pad(5, Align::Right, "ab").to_string(), "   ab");
+	assert_eq!(pad(5, Align::Center, "ab").to_string(), " ab  ");
+	assert_eq!(pad(2, Align::Right, "abc").to_string(), "abc");
+	assert_eq!(center(4, "€").to_string(), " €  ");
+	assert_eq!(center(4, "").to_string(), "    ");
+
+	// Display columns
+	#[cfg(feature = "unicode-width")]
+	{
+		use unicode_width::UnicodeWidthStr;
+		let rows = ["name", "日本語", "😀 ok", "e\u{301}te\u{301}", ""];
+		for row in rows {
+			let cell = pad(10, Align::Right, row).to_string();
+			assert_eq!(cell.width(), 10, "{:?}", cell);
+		}
+		assert_eq!(pad(6, Align::Left, "日本").to_string(), "日本  ");
+	}
+
+	// Formatting options applied to the whole output of templates
+	let x = 42;
+	let value = crate::fmt!("["{x}"]");
+	assert_eq!(format!("{}", value), "[42]");
+	assert_eq!(format!("{:8}|", value), "[42]    |");
+	assert_eq!(format!("{:<8}|", value), "[42]    |");
+	assert_eq!(format!("{:>8}|", value), "    [42]|");
+	assert_eq!(format!("{:^8}|", value), "  [42]  |");
+	assert_eq!(format!("{:*^9}|", value), "**[42]***|");
+	assert_eq!(format!("{:>3}|", value), "[42]|");
+	assert_eq!(format!("{:?}|{:>6?}", value, value), "[42]|  [42]");
+	assert_eq!(format!("{:.2}|", value), "[4|");
+	assert_eq!(format!("{:.0}|", value), "|");
+	assert_eq!(format!("{:.9}|", value), "[42]|");
+	assert_eq!(format!("{:>6.3}|", value), "   [42|");
+	assert_eq!(format!("{:-<5.1}|", crate::fmt!({"é"}{"€"})), "é----|");
+	assert_eq!(format!("{:>4}|", crate::fmt!("日本")), "  日本|");
+	assert_eq!(format!("{:>8}|", crate::fmt!(move "["{x}"]")), "    [42]|");
+	assert_eq!(format!("{:>8}|", crate::fmt!(dyn "["{x}"]")), "    [42]|");
+	let alternate = crate::fmt!(|f| f.write_str(if f.alternate() { "alt" } else { "plain" })?;);
+	assert_eq!(format!("{:#7}|{:7}|", alternate, alternate), "alt    |plain  |");
+
+	// Options on nested objects are applied to the nested output only
+	let inner = crate::fmt!({x:>4});
+	assert_eq!(format!("{:<7}|", crate::fmt!("<"{inner}">")), "<  42> |");
+
+	// Options are passed through to closures, with_options applies them to the whole output
+	let n = -3;
+	assert_eq!(format!("{:05}", crate::fmt(|f| fmt::Display::fmt(&n, f))), "-0003");
+	assert_eq!(format!("{:8.2}", crate::fmt(|f| fmt::Display::fmt(&core::f64::consts::PI, f))), "    3.14");
+	assert_eq!(format!("{:>8}|", crate::display_only(|f| f.write_str("[42]"))), "[42]|");
+	let raw = crate::fmt(|f| f.write_str("[42]"));
+	assert_eq!(format!("{:>8}|{:.2}|", raw, raw), "[42]|[42]|");
+	assert_eq!(format!("{:>8}|{:.2}|{}", with_options(raw), with_options(raw), with_options(raw)), "    [42]|[4|[42]");
+
+	// Without alloc
+	struct Buf<const N: usize>([u8; N], usize);
+	impl<const N: usize> fmt::Write for Buf<N> {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			let end = self.1 + s.len();
+			self.0.get_mut(self.1..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+			self.1 = end;
+			Ok(())
+		}
+	}
+	let mut buf = Buf([0; 8], 0);
+	fmt::write(&mut buf, format_args!("{:>7.3}|", value)).unwrap();
+	assert_eq!(&buf.0[..buf.1], b"    [42|");
+	let failing = crate::fmt!("abc" |_f| Err(fmt::Error)?;);
+	assert!(fmt::write(&mut Buf([0; 8], 0), format_args!("{:>5}", failing)).is_err());
+	assert!(fmt::write(&mut Buf([0; 8], 0), format_args!("{:.5}", failing)).is_err());
+	assert!(fmt::write(&mut Buf([0; 8], 0), format_args!("{:.2}", failing)).is_ok());
+}
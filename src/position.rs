@@ -0,0 +1,134 @@
+use core::fmt;
+use core::iter::Peekable;
+
+/// Position of an item returned by [`with_position`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Position {
+	/// The first of multiple items.
+	First,
+	/// Neither the first nor the last item.
+	Middle,
+	/// The last of multiple items.
+	Last,
+	/// The only item.
+	Only,
+}
+
+impl Position {
+	/// Returns if the item is the first item, including the only item.
+	#[inline]
+	pub const fn is_first(self) -> bool {
+		matches!(self, Position::First | Position::Only)
+	}
+	/// Returns if the item is the last item, including the only item.
+	#[inline]
+	pub const fn is_last(self) -> bool {
+		matches!(self, Position::Last | Position::Only)
+	}
+}
+
+/// Pairs every item with its position in the iteration.
+///
+/// ```
+/// let items = ["a", "b", "c"];
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	for (pos, x) in fmtools::with_position(&items) {
+/// 		if pos.is_first() { "[" }
+/// 		{x}
+/// 		if !pos.is_last() { ", " } else { "]" }
+/// 	}
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "[a, b, c]");
+/// ```
+///
+/// The resulting string is `[a, b, c]`.
+///
+/// The last item is found by looking one item ahead, the iterator does not need to know its length.
+/// Nested loops bind their own position, name the bindings apart to refer to the outer position.
+#[inline]
+pub fn with_position<T: IntoIterator>(collection: T) -> WithPosition<T::IntoIter> {
+	WithPosition { iter: collection.into_iter().peekable(), first: true }
+}
+
+/// Iterator returned by [`with_position`].
+pub struct WithPosition<I: Iterator> {
+	iter: Peekable<I>,
+	first: bool,
+}
+impl<I: Iterator + Clone> Clone for WithPosition<I> where I::Item: Clone {
+	#[inline]
+	fn clone(&self) -> Self {
+		WithPosition { iter: self.iter.clone(), first: self.first }
+	}
+}
+impl<I: Iterator + fmt::Debug> fmt::Debug for WithPosition<I> where I::Item: fmt::Debug {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("WithPosition").field("iter", &self.iter).field("first", &self.first).finish()
+	}
+}
+
+impl<I: Iterator> Iterator for WithPosition<I> {
+	type Item = (Position, I::Item);
+
+	fn next(&mut self) -> Option<(Position, I::Item)> {
+		let item = self.iter.next()?;
+		let first = self.first;
+		self.first = false;
+		let last = self.iter.peek().is_none();
+		let position = match (first, last) {
+			(true, true) => Position::Only,
+			(true, false) => Position::First,
+			(false, true) => Position::Last,
+			(false, false) => Position::Middle,
+		};
+		Some((position, item))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for WithPosition<I> {}
+
+#[test]
+fn tests() {
+	use Position::*;
+
+	let positions = |n: usize| with_position(0..n).map(|(pos, _)| pos).collect::<Vec<_>>();
+	assert_eq!(positions(0), []);
+	assert_eq!(positions(1), [Only]);
+	assert_eq!(positions(2), [First, Last]);
+	assert_eq!(positions(4), [First, Middle, Middle, Last]);
+	assert!(Only.is_first() && Only.is_last());
+	assert!(First.is_first() && !First.is_last());
+	assert!(!Middle.is_first() && !Middle.is_last());
+	assert!(!Last.is_first() && Last.is_last());
+	assert_eq!(with_position([1, 2, 3]).len(), 3);
+
+	// Nested loops with their own positions
+	let rows = [&[1, 2, 3][..], &[4], &[]];
+	let s = crate::format!(
+		for (row_pos, row) in with_position(&rows) {
+			for (pos, x) in with_position(*row) {
+				if pos.is_first() { "(" }
+				{x}
+				match pos { Middle | First => ",", Last | Only => ")" }
+			}
+			if row.is_empty() { "()" }
+			if !row_pos.is_last() { " " }
+		}
+	);
+	assert_eq!(s, "(1,2,3) (4) ()");
+
+	// Non-Clone iterators
+	let once = with_position(core::iter::from_fn({
+		let mut n = 0;
+		move || { n += 1; (n <= 2).then_some(n) }
+	}));
+	assert_eq!(once.collect::<Vec<_>>(), [(First, 1), (Last, 2)]);
+}
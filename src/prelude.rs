@@ -1,28 +1,52 @@
 /*!
 Replace the standard formatting macros using [fmt syntax](crate::fmt!).
+
+Glob import this module to bring the extension traits in scope:
+
+```
+use fmtools::prelude::*;
+
+let values = [1, 2, 3];
+let s = fmtools::format!("values: "{values.iter().joined(", ")});
+assert_eq!(s, "values: 1, 2, 3");
+```
 */
 
+pub use crate::{DisplayExt, IterDisplayExt};
+
 /// Replaces `print!` using [fmt syntax](crate::fmt!).
+///
+/// The output can be redirected with the [`output`](crate::output) module.
 #[cfg(feature = "std")]
 #[macro_export]
 macro_rules! print {
+	// Literal-only templates skip the formatting closure
+	($($text:literal)+) => {
+		$crate::output::__print(::core::format_args!("{}", $crate::obfstr!(concat!($($text,)*))))
+	};
 	($($tt:tt)*) => {
-		::std::print!("{}", $crate::fmt(|_f| {
+		$crate::output::__print(::core::format_args!("{}", $crate::fmt(|_f| {
 			$crate::__fmt!{_f $($tt)*}
 			Ok(())
-		}))
+		})))
 	};
 }
 
 /// Replaces `println!` using [fmt syntax](crate::fmt!).
+///
+/// The output can be redirected with the [`output`](crate::output) module.
 #[cfg(feature = "std")]
 #[macro_export]
 macro_rules! println {
+	// Literal-only templates skip the formatting closure
+	($($text:literal)*) => {
+		$crate::output::__print(::core::format_args!("{}", $crate::obfstr!(concat!($($text,)* "\n"))))
+	};
 	($($tt:tt)*) => {
-		::std::print!("{}", $crate::fmt(|_f| {
+		$crate::output::__print(::core::format_args!("{}", $crate::fmt(|_f| {
 			$crate::__fmt!{_f $($tt)* "\n"}
 			Ok(())
-		}))
+		})))
 	};
 }
 
@@ -30,6 +54,10 @@ macro_rules! println {
 #[cfg(feature = "std")]
 #[macro_export]
 macro_rules! eprint {
+	// Literal-only templates skip the formatting closure
+	($($text:literal)+) => {
+		::std::eprint!("{}", $crate::obfstr!(concat!($($text,)*)))
+	};
 	($($tt:tt)*) => {
 		::std::eprint!("{}", $crate::fmt(|_f| {
 			$crate::__fmt!{_f $($tt)*}
@@ -42,6 +70,10 @@ macro_rules! eprint {
 #[cfg(feature = "std")]
 #[macro_export]
 macro_rules! eprintln {
+	// Literal-only templates skip the formatting closure
+	($($text:literal)*) => {
+		::std::eprint!("{}", $crate::obfstr!(concat!($($text,)* "\n")))
+	};
 	($($tt:tt)*) => {
 		::std::eprint!("{}", $crate::fmt(|_f| {
 			$crate::__fmt!{_f $($tt)* "\n"}
@@ -77,10 +109,10 @@ macro_rules! writeln {
 #[macro_export]
 macro_rules! format {
 	($($tt:tt)*) => {
-		::std::format!("{}", $crate::fmt(|_f| {
+		$crate::format_with_hint($crate::with_size_hint($crate::__fmt_len!([] $($tt)*), $crate::fmt(|_f| {
 			$crate::__fmt!{_f $($tt)*}
 			Ok(())
-		}))
+		})))
 	};
 }
 
@@ -113,6 +145,10 @@ fn test_prelude() {
 	crate::println!("println");
 	crate::eprint!("eprint");
 	crate::eprintln!("eprintln");
+	crate::print!("{literal} " 'c' "\n");
+	crate::println!();
+	crate::println!("a" "b" {1});
+	crate::eprintln!('{' "}");
 	let mut s = crate::format!("format");
 	let _ = crate::write!(s, "write");
 	let _ = crate::writeln!(s, "writeln");
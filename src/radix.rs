@@ -0,0 +1,176 @@
+use core::{fmt, str};
+
+/// Displays an integer in binary with the digits grouped from the least significant digit.
+///
+/// ```
+/// let s = fmtools::bin_grouped(0xa6u8, 4, '_').to_string();
+/// assert_eq!(s, "1010_0110");
+///
+/// let s = fmtools::bin_grouped(5u16, 4, '_').prefix().zero_pad().to_string();
+/// assert_eq!(s, "0b0000_0000_0000_0101");
+/// ```
+///
+/// A group size of zero disables grouping.
+/// Signed integers are displayed as their two's complement, like the standard `{:b}` formatting.
+#[inline]
+pub fn bin_grouped<T: Bits>(value: T, group: usize, sep: char) -> Grouped<T> {
+	Grouped::new(value, 1, group, sep)
+}
+
+/// Displays an integer in octal with the digits grouped from the least significant digit.
+///
+/// ```
+/// let s = fmtools::oct_grouped(0o755_644u32, 3, ' ').prefix().to_string();
+/// assert_eq!(s, "0o755 644");
+/// ```
+///
+/// See [`bin_grouped`] for more information.
+#[inline]
+pub fn oct_grouped<T: Bits>(value: T, group: usize, sep: char) -> Grouped<T> {
+	Grouped::new(value, 3, group, sep)
+}
+
+/// Displays an integer in hexadecimal with the digits grouped from the least significant digit.
+///
+/// ```
+/// let s = fmtools::hex_grouped(0xdeadbeefu32, 2, ' ').to_string();
+/// assert_eq!(s, "de ad be ef");
+///
+/// let s = fmtools::hex_grouped(0x1234u32, 4, '_').prefix().zero_pad().upper().to_string();
+/// assert_eq!(s, "0x0000_1234");
+/// ```
+///
+/// See [`bin_grouped`] for more information.
+#[inline]
+pub fn hex_grouped<T: Bits>(value: T, group: usize, sep: char) -> Grouped<T> {
+	Grouped::new(value, 4, group, sep)
+}
+
+/// Displayable object returned by [`bin_grouped`], [`oct_grouped`] and [`hex_grouped`].
+#[derive(Copy, Clone)]
+pub struct Grouped<T> {
+	value: T,
+	shift: u32,
+	group: usize,
+	sep: char,
+	prefix: bool,
+	zero_pad: bool,
+	upper: bool,
+}
+
+impl<T> Grouped<T> {
+	#[inline]
+	const fn new(value: T, shift: u32, group: usize, sep: char) -> Grouped<T> {
+		Grouped { value, shift, group, sep, prefix: false, zero_pad: false, upper: false }
+	}
+	/// Writes the `0b`, `0o` or `0x` prefix.
+	#[inline]
+	pub fn prefix(mut self) -> Grouped<T> {
+		self.prefix = true;
+		self
+	}
+	/// Pads with zeros to the full width of the integer type.
+	///
+	/// A `u32` is padded to 32 binary digits, 11 octal digits or 8 hexadecimal digits.
+	#[inline]
+	pub fn zero_pad(mut self) -> Grouped<T> {
+		self.zero_pad = true;
+		self
+	}
+	/// Uses uppercase hexadecimal digits.
+	#[inline]
+	pub fn upper(mut self) -> Grouped<T> {
+		self.upper = true;
+		self
+	}
+}
+
+/// Primitive integers supported by [`bin_grouped`], [`oct_grouped`] and [`hex_grouped`].
+pub trait Bits: Copy + private::Sealed {}
+
+mod private {
+	pub trait Sealed {
+		/// Returns the two's complement bits and the width of the type.
+		fn to_bits(self) -> (u128, u32);
+	}
+}
+
+macro_rules! impl_bits {
+	($($ity:ty => $uty:ty),*) => {
+		$(
+			impl Bits for $ity {}
+			impl private::Sealed for $ity {
+				#[inline]
+				fn to_bits(self) -> (u128, u32) {
+					(self as $uty as u128, <$uty>::BITS)
+				}
+			}
+		)*
+	};
+}
+impl_bits!(
+	u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+	i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+impl<T: Bits> fmt::Display for Grouped<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let (bits, width) = private::Sealed::to_bits(self.value);
+		let used = if self.zero_pad { width } else { u32::max(1, 128 - bits.leading_zeros()) };
+		let len = used.div_ceil(self.shift) as usize;
+
+		let alphabet = if self.upper { b"0123456789ABCDEF" } else { b"0123456789abcdef" };
+		let mask = (1 << self.shift) - 1;
+		let mut buf = [0u8; 128];
+		for (i, digit) in buf[..len].iter_mut().rev().enumerate() {
+			*digit = alphabet[(bits >> (i as u32 * self.shift)) as usize & mask];
+		}
+		let digits = str::from_utf8(&buf[..len]).unwrap_or_default();
+
+		if self.prefix {
+			f.write_str(match self.shift { 1 => "0b", 3 => "0o", _ => "0x" })?;
+		}
+		if self.group == 0 {
+			return f.write_str(digits);
+		}
+		// Groups are counted from the least significant digit, the leading group may be short
+		let mut split = match len % self.group { 0 => self.group, n => n };
+		f.write_str(&digits[..split])?;
+		while split < len {
+			fmt::Write::write_char(f, self.sep)?;
+			f.write_str(&digits[split..split + self.group])?;
+			split += self.group;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([T: Bits] Grouped<T>);
+
+#[test]
+fn tests() {
+	// Binary
+	assert_eq!(bin_grouped(0xa6u8, 4, '_').to_string(), "1010_0110");
+	assert_eq!(bin_grouped(0b10110u8, 4, '_').to_string(), "1_0110");
+	assert_eq!(bin_grouped(5u32, 4, '_').zero_pad().to_string(), "0000_0000_0000_0000_0000_0000_0000_0101");
+	assert_eq!(bin_grouped(-1i8, 4, ' ').prefix().to_string(), "0b1111 1111");
+	assert_eq!(bin_grouped(0xffu8, 0, '_').to_string(), "11111111");
+	assert_eq!(bin_grouped(u128::MAX, 64, '|').to_string(), format!("{0}|{0}", "1".repeat(64)));
+
+	// Octal
+	assert_eq!(oct_grouped(0o1234567u32, 3, '_').to_string(), "1_234_567");
+	assert_eq!(oct_grouped(8u32, 3, '_').zero_pad().prefix().to_string(), "0o00_000_000_010");
+	assert_eq!(oct_grouped(u64::MAX, 0, '_').to_string(), format!("{:o}", u64::MAX));
+
+	// Hexadecimal
+	assert_eq!(hex_grouped(0xdeadbeefu32, 2, ' ').to_string(), "de ad be ef");
+	assert_eq!(hex_grouped(0xbeefu32, 2, ' ').zero_pad().to_string(), "00 00 be ef");
+	assert_eq!(hex_grouped(0xabcdeu32, 4, '_').prefix().upper().to_string(), "0xA_BCDE");
+	assert_eq!(hex_grouped(-2i16, 2, ':').to_string(), "ff:fe");
+	assert_eq!(hex_grouped(u64::MAX, 4, '_').zero_pad().to_string(), "ffff_ffff_ffff_ffff");
+	assert_eq!(hex_grouped(0x1234usize, 2, '·').to_string(), "12·34");
+
+	// Zero
+	assert_eq!(bin_grouped(0u8, 4, '_').to_string(), "0");
+	assert_eq!(bin_grouped(0u8, 4, '_').zero_pad().to_string(), "0000_0000");
+	assert_eq!(oct_grouped(0i32, 3, '_').prefix().to_string(), "0o0");
+	assert_eq!(hex_grouped(0u16, 2, ' ').prefix().zero_pad().to_string(), "0x00 00");
+}
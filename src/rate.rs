@@ -0,0 +1,181 @@
+use core::fmt::{self, Write};
+use core::time::Duration;
+
+/// Formats the number of bytes transferred over the elapsed time as a rate.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let s = fmtools::throughput(25_800_000, Duration::from_secs(2)).to_string();
+/// assert_eq!(s, "12.3 MiB/s");
+///
+/// let s = fmtools::throughput(25_800_000, Duration::from_secs(2)).si().to_string();
+/// assert_eq!(s, "12.9 MB/s");
+/// ```
+///
+/// The rate is written with 3 significant digits in binary units `B`, `KiB`, `MiB`, ... or SI units `B`, `kB`, `MB`, ... with [`si`](Throughput::si).
+/// Rates of 1000 and above move to the next unit, in binary units this writes `0.98 KiB/s` rather than a fourth digit.
+///
+/// An elapsed duration shorter than a microsecond has no meaningful rate and is written as `—`.
+///
+/// The formatting options for width, fill and alignment are respected.
+#[inline]
+pub fn throughput(bytes: u64, elapsed: Duration) -> Throughput {
+	Throughput { bytes, elapsed, si: false }
+}
+
+/// Displayable object returned by [`throughput`].
+#[derive(Copy, Clone)]
+pub struct Throughput {
+	bytes: u64,
+	elapsed: Duration,
+	si: bool,
+}
+impl Throughput {
+	/// Uses SI units with multiples of 1000 instead of binary units with multiples of 1024.
+	#[inline]
+	pub const fn si(self) -> Throughput {
+		Throughput { si: true, ..self }
+	}
+}
+impl fmt::Display for Throughput {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let (base, prefixes) = if self.si {
+			(1000.0, &["", "k", "M", "G", "T", "P", "E"])
+		}
+		else {
+			(1024.0, &["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"])
+		};
+		let Some((value, decimals, i)) = scale(self.bytes as f64, self.elapsed, base, prefixes.len()) else {
+			return f.pad(NO_RATE);
+		};
+		let mut s = crate::FixedString::<32>::new();
+		write!(s, "{:.*} {}B/s", decimals, value, prefixes[i])?;
+		f.pad(&s)
+	}
+}
+debug_as_display!([] Throughput);
+
+/// Formats the count over the elapsed time as a rate.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let s = fmtools::rate(8_400, Duration::from_secs(2), "req").to_string();
+/// assert_eq!(s, "4.20k req/s");
+///
+/// let s = fmtools::format!("done: "{fmtools::rate(3, Duration::from_millis(1500), "files")});
+/// assert_eq!(s, "done: 2.00 files/s");
+/// ```
+///
+/// The rate is written with 3 significant digits and the SI prefixes `k`, `M`, `G`, ...
+///
+/// An elapsed duration shorter than a microsecond has no meaningful rate and is written as `—`.
+///
+/// The formatting options for width, fill and alignment are respected.
+#[inline]
+pub fn rate(count: u64, elapsed: Duration, unit: &str) -> Rate<'_> {
+	Rate { count, elapsed, unit }
+}
+
+/// Displayable object returned by [`rate`].
+#[derive(Copy, Clone)]
+pub struct Rate<'a> {
+	count: u64,
+	elapsed: Duration,
+	unit: &'a str,
+}
+impl fmt::Display for Rate<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		const PREFIXES: [&str; 7] = ["", "k", "M", "G", "T", "P", "E"];
+		let Some((value, decimals, i)) = scale(self.count as f64, self.elapsed, 1000.0, PREFIXES.len()) else {
+			return f.pad(NO_RATE);
+		};
+		let mut s = crate::FixedString::<32>::new();
+		write!(s, "{:.*}{}", decimals, value, PREFIXES[i])?;
+
+		// The unit has arbitrary length, pad around the pieces instead of formatting into a buffer
+		let len = crate::width::str_width(&s) + 1 + crate::width::str_width(self.unit) + 2;
+		let padding = f.width().unwrap_or(0).saturating_sub(len);
+		let (pre, post) = match f.align() {
+			Some(fmt::Alignment::Right) => (padding, 0),
+			Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+			_ => (0, padding),
+		};
+		let fill = f.fill();
+		crate::pad::write_fill(f, fill, pre)?;
+		write!(f, "{} {}/s", s, self.unit)?;
+		crate::pad::write_fill(f, fill, post)
+	}
+}
+debug_as_display!([] Rate<'_>);
+
+const NO_RATE: &str = "—";
+
+// Shorter elapsed durations are measurement noise and would show absurd rates.
+const MIN_ELAPSED: Duration = Duration::from_micros(1);
+
+// Returns the scaled rate, the number of decimals for 3 significant digits and the prefix index.
+fn scale(amount: f64, elapsed: Duration, base: f64, prefixes: usize) -> Option<(f64, usize, usize)> {
+	if elapsed < MIN_ELAPSED {
+		return None;
+	}
+	let mut value = amount / elapsed.as_secs_f64();
+	let mut i = 0;
+	loop {
+		// Account for rounding when choosing the number of decimals and the prefix
+		let decimals = if value < 9.995 { 2 } else if value < 99.95 { 1 } else { 0 };
+		if decimals == 0 && value >= 999.5 && i + 1 < prefixes {
+			value /= base;
+			i += 1;
+			continue;
+		}
+		return Some((value, decimals, i));
+	}
+}
+
+#[test]
+fn tests() {
+	const KIB: u64 = 1024;
+	const MIB: u64 = 1024 * 1024;
+	let sec = Duration::from_secs(1);
+
+	// Binary units
+	assert_eq!(throughput(0, sec).to_string(), "0.00 B/s");
+	assert_eq!(throughput(512, sec).to_string(), "512 B/s");
+	assert_eq!(throughput(999, sec).to_string(), "999 B/s");
+	assert_eq!(throughput(1000, sec).to_string(), "0.98 KiB/s");
+	assert_eq!(throughput(1023, sec).to_string(), "1.00 KiB/s");
+	assert_eq!(throughput(1000 * KIB, sec).to_string(), "0.98 MiB/s");
+	assert_eq!(throughput(KIB, sec).to_string(), "1.00 KiB/s");
+	assert_eq!(throughput(MIB - 1, sec).to_string(), "1.00 MiB/s");
+	assert_eq!(throughput(10 * MIB - 1, sec).to_string(), "10.0 MiB/s");
+	assert_eq!(throughput(u64::MAX, sec).to_string(), "16.0 EiB/s");
+	assert_eq!(throughput(3 * KIB, Duration::from_millis(500)).to_string(), "6.00 KiB/s");
+
+	// SI units
+	assert_eq!(throughput(999, sec).si().to_string(), "999 B/s");
+	assert_eq!(throughput(999_500, sec).si().to_string(), "1.00 MB/s");
+	assert_eq!(throughput(1_234_567_890, sec).si().to_string(), "1.23 GB/s");
+
+	// Zero elapsed
+	assert_eq!(throughput(0, Duration::ZERO).to_string(), "—");
+	assert_eq!(throughput(MIB, Duration::ZERO).to_string(), "—");
+	assert_eq!(rate(10, Duration::ZERO, "req").to_string(), "—");
+
+	// Near zero elapsed
+	assert_eq!(throughput(1, Duration::from_nanos(1)).si().to_string(), "—");
+	assert_eq!(throughput(1, Duration::from_nanos(999)).si().to_string(), "—");
+	assert_eq!(throughput(1, Duration::from_micros(1)).si().to_string(), "1.00 MB/s");
+
+	// Counts
+	assert_eq!(rate(0, sec, "req").to_string(), "0.00 req/s");
+	assert_eq!(rate(42, sec, "req").to_string(), "42.0 req/s");
+	assert_eq!(rate(999_600, sec, "ops").to_string(), "1.00M ops/s");
+	assert_eq!(rate(1, Duration::from_secs(3), "req").to_string(), "0.33 req/s");
+
+	// Padding
+	assert_eq!(format!("[{:>12}]", throughput(2 * MIB, sec)), "[  2.00 MiB/s]");
+	assert_eq!(format!("[{:<12}]", rate(5, sec, "req")), "[5.00 req/s  ]");
+	assert_eq!(format!("[{:^3}]", throughput(1, Duration::ZERO)), "[ — ]");
+}
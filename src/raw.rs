@@ -0,0 +1,106 @@
+use core::fmt;
+
+/// Marks already escaped content at the type level.
+///
+/// The `html` and `json` modifiers of [fmt!](crate::fmt!) write holes of type [`Raw`] without escaping:
+///
+/// ```
+/// let badge = fmtools::raw("<b>new</b>");
+/// let title = "<Fish & Chips>";
+/// let s = fmtools::format!(html "<li>"{title}" "{badge}"</li>");
+/// assert_eq!(s, "<li>&lt;Fish &amp; Chips&gt; <b>new</b></li>");
+///
+/// let list = fmtools::raw("[1,2]");
+/// let s = fmtools::format!(json "{\"list\":"{list}"}");
+/// assert_eq!(s, r#"{"list":[1,2]}"#);
+/// ```
+///
+/// Unlike the `{frag:raw}` specifier the marker travels with the value, eg. through struct fields.
+/// Only holes of type `Raw` are recognized, a `Raw` wrapped in another displayable object is escaped as usual.
+///
+/// Outside the escaping modes it displays the string as is, respecting width, fill, alignment and precision.
+#[inline]
+pub const fn raw(s: &str) -> Raw<'_> {
+	Raw(s)
+}
+
+/// Already escaped content returned by [`raw`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct Raw<'a>(pub &'a str);
+
+impl<'a> Raw<'a> {
+	/// Returns the content.
+	#[inline]
+	pub const fn as_str(&self) -> &'a str {
+		self.0
+	}
+}
+
+impl fmt::Display for Raw<'_> {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(self.0)
+	}
+}
+
+impl crate::JsonValue for Raw<'_> {
+	#[inline]
+	fn fmt_json(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.0)
+	}
+}
+
+// Selects between escaping and writing raw in the holes of the `html` modifier.
+//
+// Autoref specialization: the method of `__HtmlRaw` is found first for `Raw` holes,
+// every other hole requires an autoref to find the method of `__HtmlEscape`.
+#[doc(hidden)]
+pub trait __HtmlRaw<'a> {
+	fn __html(&self) -> Raw<'a>;
+}
+impl<'a> __HtmlRaw<'a> for Raw<'a> {
+	#[inline]
+	fn __html(&self) -> Raw<'a> {
+		*self
+	}
+}
+
+#[doc(hidden)]
+pub trait __HtmlEscape<'a, T: ?Sized> {
+	fn __html(&self) -> crate::EscapeHtml<&'a T>;
+}
+impl<'a, T: fmt::Display + ?Sized> __HtmlEscape<'a, T> for &'a T {
+	#[inline]
+	fn __html(&self) -> crate::EscapeHtml<&'a T> {
+		crate::escape_html(*self)
+	}
+}
+
+#[test]
+fn tests() {
+	let frag = raw("<i>&amp;</i>");
+	assert_eq!(frag.to_string(), "<i>&amp;</i>");
+	assert_eq!(format!("[{:>5.2}]", raw("<br>")), "[   <b]");
+	assert_eq!(frag.as_str(), "<i>&amp;</i>");
+
+	// Escaping modes
+	let text = "<i>";
+	assert_eq!(crate::format!(html {frag}"|"{text}"|"{&text}), "<i>&amp;</i>|&lt;i&gt;|&lt;i&gt;");
+	assert_eq!(crate::format!(html for s in [raw("<a>"), raw("&")] { {s} }), "<a>&");
+	assert_eq!(crate::format!(html {raw("<p>")}{frag.as_str()}), "<p>&lt;i&gt;&amp;amp;&lt;/i&gt;");
+	assert_eq!(crate::format!(json "["{raw("{}")}","{text}"]"), r#"[{},"<i>"]"#);
+
+	// Wrapped markers are escaped
+	let either = crate::either(true, frag, crate::nothing());
+	assert_eq!(crate::format!({either}), "<i>&amp;</i>");
+	assert_eq!(crate::format!(html {either}), "&lt;i&gt;&amp;amp;&lt;/i&gt;");
+	assert_eq!(crate::format!(html {crate::cond(true, frag)}), "&lt;i&gt;&amp;amp;&lt;/i&gt;");
+
+	// Stored in struct fields
+	struct Cell<'a> {
+		html: Raw<'a>,
+	}
+	let cells = [Cell { html: raw("<td>1</td>") }, Cell { html: Raw::default() }];
+	assert_eq!(crate::format!(html for cell in &cells { {cell.html} }), "<td>1</td>");
+}
@@ -0,0 +1,70 @@
+use core::fmt;
+
+/// Displayable object which repeats the output a number of times.
+///
+/// Returned by [`DisplayExt::repeated`](crate::DisplayExt::repeated).
+#[derive(Copy, Clone)]
+pub struct Repeat<T> {
+	value: T,
+	count: usize,
+}
+impl<T: fmt::Display> Repeat<T> {
+	#[inline]
+	pub(crate) fn new(count: usize, value: T) -> Repeat<T> {
+		Repeat { value, count }
+	}
+}
+impl<T: fmt::Display> fmt::Display for Repeat<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for _ in 0..self.count {
+			write!(f, "{}", self.value)?;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([T: fmt::Display] Repeat<T>);
+
+/// Displayable object which wraps the output in double quotes.
+///
+/// Backslashes and double quotes in the output are escaped with a backslash.
+///
+/// Returned by [`DisplayExt::quoted`](crate::DisplayExt::quoted).
+#[derive(Copy, Clone)]
+pub struct Quoted<T> {
+	value: T,
+}
+impl<T: fmt::Display> Quoted<T> {
+	#[inline]
+	pub(crate) fn new(value: T) -> Quoted<T> {
+		Quoted { value }
+	}
+}
+impl<T: fmt::Display> fmt::Display for Quoted<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		struct Escape<'a, 'f>(&'a mut fmt::Formatter<'f>);
+		impl fmt::Write for Escape<'_, '_> {
+			fn write_str(&mut self, mut s: &str) -> fmt::Result {
+				while let Some(i) = s.find(['"', '\\']) {
+					self.0.write_str(&s[..i])?;
+					self.0.write_char('\\')?;
+					self.0.write_str(&s[i..i + 1])?;
+					s = &s[i + 1..];
+				}
+				self.0.write_str(s)
+			}
+		}
+		f.write_str("\"")?;
+		fmt::write(&mut Escape(f), format_args!("{}", self.value))?;
+		f.write_str("\"")
+	}
+}
+debug_as_display!([T: fmt::Display] Quoted<T>);
+
+#[test]
+fn tests() {
+	assert_eq!(Repeat::new(3, "ab").to_string(), "ababab");
+	assert_eq!(Repeat::new(0, "ab").to_string(), "");
+	assert_eq!(Quoted::new("abc").to_string(), "\"abc\"");
+	assert_eq!(Quoted::new(r#"say "hi" \o/"#).to_string(), r#""say \"hi\" \\o/""#);
+	assert_eq!(Quoted::new("").to_string(), "\"\"");
+}
@@ -0,0 +1,442 @@
+/*!
+Templates parsed at runtime.
+
+The [fmt!](crate::fmt!) macro compiles the template with the program.
+Templates loaded from configuration files or translations are only known at runtime, this module parses and renders them:
+
+```
+use fmtools::runtime::Template;
+
+let template = Template::parse("{name} scored {score:>5.1} points, {{wow}}!").unwrap();
+let s = template.render(&[("name", &"Alice" as &dyn fmtools::RuntimeFormat), ("score", &97.25)]).to_string();
+assert_eq!(s, "Alice scored  97.2 points, {wow}!");
+```
+
+Holes are written as `{key}` or `{key:spec}`, the spec is applied with [`FormatSpec`].
+Keys consist of ASCII letters, digits, `_`, `.` and `-`.
+Literal braces are escaped by doubling them: `{{` and `}}`.
+
+The template is validated when parsed and rendered without allocating.
+*/
+
+use core::fmt;
+use crate::{FormatSpec, RuntimeFormat, SpecError};
+
+/// Template parsed at runtime.
+///
+/// See the [module documentation](self) for the syntax.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Template<'a> {
+	source: &'a str,
+	unknown: UnknownKey,
+}
+
+/// Behavior for keys without a value when rendering a [`Template`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum UnknownKey {
+	/// Rendering fails with an error, the default.
+	#[default]
+	Error,
+	/// The hole is rendered as empty.
+	Empty,
+	/// The hole is rendered as written in the template.
+	Keep,
+}
+
+impl<'a> Template<'a> {
+	/// Parses the template.
+	///
+	/// ```
+	/// use fmtools::runtime::{Template, ErrorKind};
+	///
+	/// let err = Template::parse("Hello {name!").unwrap_err();
+	/// assert_eq!((err.offset, err.kind), (6, ErrorKind::Unclosed));
+	/// assert_eq!(err.to_string(), "unclosed `{` at offset 6 in template");
+	/// ```
+	///
+	/// Returns an error for unmatched braces, invalid keys and invalid specs.
+	pub fn parse(source: &'a str) -> Result<Template<'a>, TemplateError> {
+		for segment in Segments::new(source) {
+			if let Segment::Hole { spec, spec_offset, .. } = segment? {
+				FormatSpec::parse(spec).map_err(|err| TemplateError { offset: spec_offset, kind: ErrorKind::Spec(err) })?;
+			}
+		}
+		Ok(Template { source, unknown: UnknownKey::Error })
+	}
+
+	/// Sets the behavior for keys without a value.
+	///
+	/// ```
+	/// use fmtools::runtime::{Template, UnknownKey};
+	///
+	/// let template = Template::parse("[{a}|{b:>3}]").unwrap();
+	/// let values: [(&str, &dyn fmtools::RuntimeFormat); 1] = [("a", &1)];
+	/// assert_eq!(template.unknown_key(UnknownKey::Empty).render(&values).to_string(), "[1|]");
+	/// assert_eq!(template.unknown_key(UnknownKey::Keep).render(&values).to_string(), "[1|{b:>3}]");
+	/// ```
+	#[inline]
+	pub const fn unknown_key(self, unknown: UnknownKey) -> Template<'a> {
+		Template { unknown, ..self }
+	}
+
+	/// Returns the source of the template.
+	#[inline]
+	pub const fn as_str(&self) -> &'a str {
+		self.source
+	}
+
+	/// Returns the keys of the holes in order of appearance.
+	///
+	/// ```
+	/// let template = fmtools::runtime::Template::parse("{a}{b:x}{a}").unwrap();
+	/// assert!(template.keys().eq(["a", "b", "a"]));
+	/// ```
+	pub fn keys(&self) -> impl Iterator<Item = &'a str> + 'a {
+		Segments::new(self.source).filter_map(|segment| match segment {
+			Ok(Segment::Hole { key, .. }) => Some(key),
+			_ => None,
+		})
+	}
+
+	/// Checks if the template can be rendered with the values.
+	///
+	/// ```
+	/// use fmtools::runtime::{Template, ErrorKind};
+	/// use fmtools::SpecError;
+	///
+	/// let template = Template::parse("{name:>8} {name:x}").unwrap();
+	/// let err = template.check(&[("name", &"Bob" as &dyn fmtools::RuntimeFormat)]).unwrap_err();
+	/// assert_eq!((err.offset, err.kind), (16, ErrorKind::Spec(SpecError::UnsupportedType('x'))));
+	///
+	/// let err = template.check(&[]).unwrap_err();
+	/// assert_eq!((err.offset, err.kind), (0, ErrorKind::UnknownKey));
+	/// ```
+	///
+	/// Displaying the [rendered](Template::render) template reports errors as [`fmt::Error`] without details, check the template first or render with [`render_to`](Template::render_to) to find out what went wrong.
+	pub fn check<'v, L: Lookup<'v> + ?Sized>(&self, values: &L) -> Result<(), TemplateError> {
+		for segment in Segments::new(self.source) {
+			if let Segment::Hole { offset, key, spec, spec_offset, .. } = segment? {
+				let spec_error = |err| TemplateError { offset: spec_offset, kind: ErrorKind::Spec(err) };
+				let spec = FormatSpec::parse(spec).map_err(spec_error)?;
+				match values.lookup(key) {
+					Some(value) => drop(spec.apply(value).map_err(spec_error)?),
+					None if self.unknown == UnknownKey::Error => return Err(TemplateError { offset, kind: ErrorKind::UnknownKey }),
+					None => (),
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Renders the template with the values.
+	///
+	/// ```
+	/// use fmtools::RuntimeFormat;
+	///
+	/// let template = fmtools::runtime::Template::parse("{x:#06x} {y:?}").unwrap();
+	/// let s = template.render(&|key: &str| match key {
+	/// 	"x" => Some(&42 as &dyn RuntimeFormat),
+	/// 	"y" => Some(&"why" as &dyn RuntimeFormat),
+	/// 	_ => None,
+	/// }).to_string();
+	/// assert_eq!(s, "0x002a \"why\"");
+	/// ```
+	///
+	/// The values are looked up by key every time the object is displayed, see [`Lookup`].
+	/// Rendering fails if a spec is not supported by its value or a key is unknown, see [`unknown_key`](Template::unknown_key).
+	///
+	/// The failure is reported as [`fmt::Error`], which makes `to_string` and the `format!` macros panic.
+	/// Use [`render_to`](Template::render_to) to handle the error or [`check`](Template::check) the values first.
+	#[inline]
+	pub fn render<'r, 'v, L: Lookup<'v> + ?Sized>(&self, values: &'r L) -> Render<'a, 'r, L> {
+		Render { template: *self, values }
+	}
+
+	/// Renders the template with the values into the writer.
+	///
+	/// ```
+	/// use fmtools::runtime::{Template, ErrorKind};
+	///
+	/// let template = Template::parse("{greeting}, {name}!").unwrap();
+	/// let mut s = String::new();
+	/// let err = template.render_to(&mut s, &[("greeting", &"Hello" as &dyn fmtools::RuntimeFormat)]).unwrap_err();
+	/// assert_eq!((err.offset, err.kind), (12, ErrorKind::UnknownKey));
+	/// assert_eq!(s, "Hello, ");
+	/// ```
+	///
+	/// Returns the error instead of [`fmt::Error`], the output written before the error is left in the writer.
+	pub fn render_to<'v, W: fmt::Write + ?Sized, L: Lookup<'v> + ?Sized>(&self, out: &mut W, values: &L) -> Result<(), TemplateError> {
+		let mut segments = Segments::new(self.source);
+		loop {
+			let offset = segments.pos;
+			let Some(segment) = segments.next() else {
+				return Ok(());
+			};
+			let write_error = |_| TemplateError { offset, kind: ErrorKind::Write };
+			match segment? {
+				Segment::Text(text) => out.write_str(text).map_err(write_error)?,
+				Segment::Hole { offset, end, key, spec, spec_offset } => match values.lookup(key) {
+					Some(value) => {
+						let spec_error = |err| TemplateError { offset: spec_offset, kind: ErrorKind::Spec(err) };
+						let spec = FormatSpec::parse(spec).map_err(spec_error)?;
+						write!(out, "{}", spec.apply(value).map_err(spec_error)?).map_err(write_error)?;
+					},
+					None => match self.unknown {
+						UnknownKey::Error => return Err(TemplateError { offset, kind: ErrorKind::UnknownKey }),
+						UnknownKey::Empty => (),
+						UnknownKey::Keep => out.write_str(&self.source[offset..end]).map_err(write_error)?,
+					},
+				},
+			}
+		}
+	}
+}
+
+/// Values looked up by key when rendering a [`Template`].
+///
+/// Implemented for slices and arrays of key value pairs and for closures.
+/// Values implement [`RuntimeFormat`], any other value can be passed as `&dyn Display` with fill, alignment and width:
+///
+/// ```
+/// use std::fmt::Display;
+/// use std::net::Ipv4Addr;
+/// use fmtools::RuntimeFormat;
+///
+/// let addr = Ipv4Addr::LOCALHOST;
+/// let addr = &addr as &dyn Display;
+/// let values: [(&str, &dyn RuntimeFormat); 2] = [("addr", &addr), ("port", &8080)];
+///
+/// let template = fmtools::runtime::Template::parse("{addr:>12}:{port}").unwrap();
+/// assert_eq!(template.render(&values).to_string(), "   127.0.0.1:8080");
+/// ```
+pub trait Lookup<'v> {
+	/// Returns the value of the key.
+	fn lookup(&self, key: &str) -> Option<&'v dyn RuntimeFormat>;
+}
+
+impl<'v> Lookup<'v> for [(&str, &'v dyn RuntimeFormat)] {
+	#[inline]
+	fn lookup(&self, key: &str) -> Option<&'v dyn RuntimeFormat> {
+		self.iter().find(|&&(k, _)| k == key).map(|&(_, value)| value)
+	}
+}
+impl<'v, const N: usize> Lookup<'v> for [(&str, &'v dyn RuntimeFormat); N] {
+	#[inline]
+	fn lookup(&self, key: &str) -> Option<&'v dyn RuntimeFormat> {
+		self[..].lookup(key)
+	}
+}
+impl<'v, F: Fn(&str) -> Option<&'v dyn RuntimeFormat>> Lookup<'v> for F {
+	#[inline]
+	fn lookup(&self, key: &str) -> Option<&'v dyn RuntimeFormat> {
+		self(key)
+	}
+}
+
+/// Displayable object returned by [`Template::render`].
+pub struct Render<'a, 'r, L: ?Sized> {
+	template: Template<'a>,
+	values: &'r L,
+}
+impl<L: ?Sized> Copy for Render<'_, '_, L> {}
+impl<L: ?Sized> Clone for Render<'_, '_, L> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<'v, L: Lookup<'v> + ?Sized> fmt::Display for Render<'_, '_, L> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.template.render_to(f, self.values).map_err(|_| fmt::Error)
+	}
+}
+debug_as_display!(['v, L: Lookup<'v> + ?Sized] Render<'_, '_, L>);
+
+/// Error returned when parsing, checking or rendering a [`Template`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TemplateError {
+	/// Byte offset of the error in the template.
+	pub offset: usize,
+	/// Kind of the error.
+	pub kind: ErrorKind,
+}
+
+/// Kind of a [`TemplateError`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+	/// A `{` without closing `}`.
+	Unclosed,
+	/// A `}` without opening `{`, write `}}` for a literal brace.
+	Unmatched,
+	/// The key is empty or contains invalid characters.
+	InvalidKey,
+	/// The spec is invalid or not supported by the value, the offset points at the spec.
+	Spec(SpecError),
+	/// The key has no value.
+	UnknownKey,
+	/// The writer or a value returned an error, the offset points at the text or hole being written.
+	Write,
+}
+
+impl fmt::Display for TemplateError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.kind {
+			ErrorKind::Unclosed => f.write_str("unclosed `{`")?,
+			ErrorKind::Unmatched => f.write_str("unmatched `}`")?,
+			ErrorKind::InvalidKey => f.write_str("invalid key")?,
+			ErrorKind::Spec(err) => write!(f, "{}", err)?,
+			ErrorKind::UnknownKey => f.write_str("unknown key")?,
+			ErrorKind::Write => f.write_str("write error")?,
+		}
+		write!(f, " at offset {} in template", self.offset)
+	}
+}
+#[cfg(feature = "std")]
+impl std::error::Error for TemplateError {}
+
+enum Segment<'a> {
+	Text(&'a str),
+	Hole { offset: usize, end: usize, key: &'a str, spec: &'a str, spec_offset: usize },
+}
+
+// Splits the template in text and holes, stops after the first error.
+struct Segments<'a> {
+	source: &'a str,
+	pos: usize,
+}
+impl<'a> Segments<'a> {
+	fn new(source: &'a str) -> Segments<'a> {
+		Segments { source, pos: 0 }
+	}
+	fn error(&mut self, offset: usize, kind: ErrorKind) -> Option<Result<Segment<'a>, TemplateError>> {
+		self.pos = self.source.len();
+		Some(Err(TemplateError { offset, kind }))
+	}
+}
+impl<'a> Iterator for Segments<'a> {
+	type Item = Result<Segment<'a>, TemplateError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let start = self.pos;
+		let rest = &self.source[start..];
+		let bytes = rest.as_bytes();
+		match rest.find(['{', '}']) {
+			None if rest.is_empty() => None,
+			None => {
+				self.pos = self.source.len();
+				Some(Ok(Segment::Text(rest)))
+			},
+			Some(0) if bytes.get(1) == Some(&bytes[0]) => {
+				self.pos += 2;
+				Some(Ok(Segment::Text(&rest[..1])))
+			},
+			Some(0) if bytes[0] == b'}' => self.error(start, ErrorKind::Unmatched),
+			Some(0) => {
+				let Some(end) = rest.find('}')
+				else {
+					return self.error(start, ErrorKind::Unclosed);
+				};
+				let inner = &rest[1..end];
+				let (key, spec) = inner.split_once(':').unwrap_or((inner, ""));
+				if key.is_empty() || !key.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-')) {
+					return self.error(start + 1, ErrorKind::InvalidKey);
+				}
+				self.pos += end + 1;
+				Some(Ok(Segment::Hole { offset: start, end: self.pos, key, spec, spec_offset: start + 2 + key.len() }))
+			},
+			Some(i) => {
+				self.pos += i;
+				Some(Ok(Segment::Text(&rest[..i])))
+			},
+		}
+	}
+}
+
+#[test]
+fn tests() {
+	let render = |template: &str, values: &[(&str, &dyn RuntimeFormat)]| {
+		Template::parse(template).unwrap().render(values).to_string()
+	};
+	let error = |template: &str| {
+		let err = Template::parse(template).unwrap_err();
+		(err.offset, err.kind)
+	};
+
+	// Escaped braces
+	assert_eq!(render("", &[]), "");
+	assert_eq!(render("{{}}", &[]), "{}");
+	assert_eq!(render("{{{a}}}", &[("a", &1)]), "{1}");
+	assert_eq!(render("a{{b}}c{{{{", &[]), "a{b}c{{");
+	assert_eq!(render("é{{€}}ü", &[]), "é{€}ü");
+
+	// Spec'd holes
+	let values: [(&str, &dyn RuntimeFormat); 4] = [("n", &255u8), ("x", &-1.5f64), ("s", &"hi"), ("b", &true)];
+	assert_eq!(render("{n}|{n:#x}|{n:08b}|{n:*^7}", &values), "255|0xff|11111111|**255**");
+	assert_eq!(render("{x:+.3}|{x:>6}|{x:e}", &values), "-1.500|  -1.5|-1.5e0");
+	assert_eq!(render("{s:?}|{s:-<4}|{b:>5}|{s}{s}", &values), "\"hi\"|hi--| true|hihi");
+	assert_eq!(render("{s:}", &values), "hi");
+
+	// Parse errors
+	assert_eq!(error("abc{"), (3, ErrorKind::Unclosed));
+	assert_eq!(error("{a}{b"), (3, ErrorKind::Unclosed));
+	assert_eq!(error("ab}c"), (2, ErrorKind::Unmatched));
+	assert_eq!(error("{a}}"), (3, ErrorKind::Unmatched));
+	assert_eq!(error("{}"), (1, ErrorKind::InvalidKey));
+	assert_eq!(error("{:x}"), (1, ErrorKind::InvalidKey));
+	assert_eq!(error("{a b}"), (1, ErrorKind::InvalidKey));
+	assert_eq!(error("{a{b}"), (1, ErrorKind::InvalidKey));
+	assert_eq!(error("é{a:>5q}"), (5, ErrorKind::Spec(SpecError::Unexpected(2))));
+	assert_eq!(Template::parse("{a}}").unwrap_err().to_string(), "unmatched `}` at offset 3 in template");
+
+	// Unknown keys
+	let template = Template::parse("<{a}{missing:>4}{b}>").unwrap();
+	let values: [(&str, &dyn RuntimeFormat); 2] = [("a", &1), ("b", &2)];
+	let mut s = String::new();
+	assert!(fmt::write(&mut s, format_args!("{}", template.render(&values))).is_err());
+	assert_eq!(template.check(&values), Err(TemplateError { offset: 4, kind: ErrorKind::UnknownKey }));
+	let template = template.unknown_key(UnknownKey::Empty);
+	assert_eq!(template.check(&values), Ok(()));
+	assert_eq!(template.render(&values).to_string(), "<12>");
+	assert_eq!(template.unknown_key(UnknownKey::Keep).render(&values).to_string(), "<1{missing:>4}2>");
+	assert_eq!(Template::parse("{k}").unwrap().unknown_key(UnknownKey::Keep).render(&[]).to_string(), "{k}");
+
+	// Unsupported specs fail when rendering
+	let template = Template::parse("{a:x}").unwrap();
+	let values: [(&str, &dyn RuntimeFormat); 1] = [("a", &"text")];
+	assert_eq!(template.check(&values), Err(TemplateError { offset: 3, kind: ErrorKind::Spec(SpecError::UnsupportedType('x')) }));
+	assert!(fmt::write(&mut s, format_args!("{}", template.render(&values))).is_err());
+
+	// Errors with details when rendering into a writer
+	let template = Template::parse("ab{a}{b:>3}").unwrap();
+	let values: [(&str, &dyn RuntimeFormat); 2] = [("a", &1), ("b", &"x")];
+	let mut s = String::new();
+	assert_eq!(template.render_to(&mut s, &values), Ok(()));
+	assert_eq!(s, "ab1  x");
+	let mut s = String::new();
+	assert_eq!(template.render_to(&mut s, &values[..1]), Err(TemplateError { offset: 5, kind: ErrorKind::UnknownKey }));
+	assert_eq!(s, "ab1");
+	let mut s = String::new();
+	let values: [(&str, &dyn RuntimeFormat); 2] = [("a", &"text"), ("b", &2)];
+	assert_eq!(Template::parse("{b}{a:x}").unwrap().render_to(&mut s, &values), Err(TemplateError { offset: 6, kind: ErrorKind::Spec(SpecError::UnsupportedType('x')) }));
+	struct Limited(usize);
+	impl fmt::Write for Limited {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			self.0 = self.0.checked_sub(s.len()).ok_or(fmt::Error)?;
+			Ok(())
+		}
+	}
+	assert_eq!(template.render_to(&mut Limited(6), &values), Err(TemplateError { offset: 5, kind: ErrorKind::Write }));
+	assert_eq!(template.render_to(&mut Limited(1), &values), Err(TemplateError { offset: 0, kind: ErrorKind::Write }));
+
+	// Closures and the first pair with the key wins
+	let counter = core::cell::Cell::new(0);
+	let lookup = |key: &str| {
+		counter.set(counter.get() + 1);
+		(key == "k").then_some(&7 as &dyn RuntimeFormat)
+	};
+	let template = Template::parse("{k}{k:>3}{z}").unwrap().unknown_key(UnknownKey::Empty);
+	assert_eq!(template.render(&lookup).to_string(), "7  7");
+	assert_eq!(counter.get(), 3);
+	assert_eq!(render("{k}", &[("k", &1), ("k", &2)]), "1");
+	assert!(template.keys().eq(["k", "k", "z"]));
+}
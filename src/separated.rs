@@ -0,0 +1,146 @@
+use core::fmt;
+
+/// Builder for displaying delimited sequences.
+///
+/// ```
+/// use fmtools::Separated;
+///
+/// let names = ["alice", "bob", "carol"];
+/// let s = Separated::new(", ", &names).prefix("[").suffix("]").last_sep(" and ").to_string();
+/// assert_eq!(s, "[alice, bob and carol]");
+///
+/// let s = Separated::new(", ", &names[..0]).prefix("[").suffix("]").when_empty("<none>").to_string();
+/// assert_eq!(s, "<none>");
+/// ```
+///
+/// The separator goes between each item, the last separator (if set) replaces it between the last two items.
+/// The prefix and suffix surround the items, an empty sequence displays only the prefix and suffix unless a fallback is set with [`when_empty`](Separated::when_empty).
+///
+/// The iterator is cloned every time the object is displayed.
+#[derive(Copy, Clone)]
+pub struct Separated<'a, I> {
+	iter: I,
+	sep: &'a str,
+	last_sep: Option<&'a str>,
+	prefix: &'a str,
+	suffix: &'a str,
+	when_empty: Option<&'a str>,
+}
+
+impl<'a, I: Iterator + Clone> Separated<'a, I> where I::Item: fmt::Display {
+	/// Constructs a new builder with the separator between each item.
+	#[inline]
+	pub fn new<T: IntoIterator<IntoIter = I>>(sep: &'a str, collection: T) -> Separated<'a, I> {
+		Separated {
+			iter: collection.into_iter(),
+			sep,
+			last_sep: None,
+			prefix: "",
+			suffix: "",
+			when_empty: None,
+		}
+	}
+}
+
+impl<'a, I> Separated<'a, I> {
+	/// Sets the separator between the last two items.
+	#[inline]
+	pub const fn last_sep(mut self, last_sep: &'a str) -> Separated<'a, I> {
+		self.last_sep = Some(last_sep);
+		self
+	}
+	/// Sets the string written before the first item.
+	#[inline]
+	pub const fn prefix(mut self, prefix: &'a str) -> Separated<'a, I> {
+		self.prefix = prefix;
+		self
+	}
+	/// Sets the string written after the last item.
+	#[inline]
+	pub const fn suffix(mut self, suffix: &'a str) -> Separated<'a, I> {
+		self.suffix = suffix;
+		self
+	}
+	/// Sets the fallback written instead of the prefix and suffix when there are no items.
+	#[inline]
+	pub const fn when_empty(mut self, when_empty: &'a str) -> Separated<'a, I> {
+		self.when_empty = Some(when_empty);
+		self
+	}
+}
+
+impl<I: Iterator + Clone> fmt::Display for Separated<'_, I> where I::Item: fmt::Display {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut iter = self.iter.clone().peekable();
+		let Some(mut item) = iter.next() else {
+			return match self.when_empty {
+				Some(when_empty) => f.write_str(when_empty),
+				None => {
+					f.write_str(self.prefix)?;
+					f.write_str(self.suffix)
+				},
+			};
+		};
+		f.write_str(self.prefix)?;
+		loop {
+			fmt::Display::fmt(&item, f)?;
+			let Some(next) = iter.next() else { break };
+			let sep = match self.last_sep {
+				Some(last_sep) if iter.peek().is_none() => last_sep,
+				_ => self.sep,
+			};
+			f.write_str(sep)?;
+			item = next;
+		}
+		f.write_str(self.suffix)
+	}
+}
+debug_as_display!([I: Iterator + Clone] Separated<'_, I> where I::Item: fmt::Display);
+
+#[test]
+fn tests() {
+	#[track_caller]
+	fn check(f: impl fmt::Display, s: &str) {
+		assert_eq!(f.to_string(), s);
+	}
+
+	let none: [i32; 0] = [];
+	let one = [1];
+	let two = [1, 2];
+	let three = [1, 2, 3];
+
+	// Separator only
+	check(Separated::new(", ", &none), "");
+	check(Separated::new(", ", &one), "1");
+	check(Separated::new(", ", &three), "1, 2, 3");
+
+	// Prefix and suffix
+	check(Separated::new(", ", &none).prefix("[").suffix("]"), "[]");
+	check(Separated::new(", ", &one).prefix("[").suffix("]"), "[1]");
+	check(Separated::new(", ", &three).prefix("(").suffix(")"), "(1, 2, 3)");
+	check(Separated::new(", ", &two).prefix("<"), "<1, 2");
+	check(Separated::new(", ", &two).suffix(">"), "1, 2>");
+
+	// Empty fallback
+	check(Separated::new(", ", &none).when_empty("-"), "-");
+	check(Separated::new(", ", &none).prefix("[").suffix("]").when_empty("-"), "-");
+	check(Separated::new(", ", &one).prefix("[").suffix("]").when_empty("-"), "[1]");
+
+	// Last separator
+	check(Separated::new(", ", &none).last_sep(" or "), "");
+	check(Separated::new(", ", &one).last_sep(" or "), "1");
+	check(Separated::new(", ", &two).last_sep(" or "), "1 or 2");
+	check(Separated::new(", ", &three).last_sep(", or "), "1, 2, or 3");
+
+	// All options
+	let all = |items: &'static [i32]| Separated::new(", ", items).prefix("{").suffix("}").last_sep(" & ").when_empty("{}?");
+	check(all(&[]), "{}?");
+	check(all(&[1]), "{1}");
+	check(all(&[1, 2, 3]), "{1, 2 & 3}");
+
+	// Const builder
+	const fn brackets<I>(s: Separated<'static, I>) -> Separated<'static, I> {
+		s.prefix("[").suffix("]")
+	}
+	check(brackets(Separated::new(" ", "ab".chars())), "[a b]");
+}
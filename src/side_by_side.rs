@@ -0,0 +1,113 @@
+use std::fmt;
+use crate::Align;
+
+/// Renders two multi-line values in adjacent columns.
+///
+/// ```
+/// let before = "a = 1\nb = 2\nc = 3";
+/// let after = "a = 1\nb = 42";
+/// let s = fmtools::side_by_side(before, after, 6, " | ").to_string();
+/// assert_eq!(s, "a = 1  | a = 1\nb = 2  | b = 42\nc = 3  | \n");
+/// ```
+///
+/// Both values are rendered into buffers and split into lines the same way as [`str::lines`].
+/// The lines are zipped into rows, the shorter block is padded with empty lines.
+/// Each row is terminated by a newline.
+///
+/// The left column is padded to the column width.
+/// Lines longer than the column width are truncated with `…`, or wrapped over multiple rows with [`wrap`](SideBySide::wrap).
+pub fn side_by_side<L: fmt::Display, R: fmt::Display>(left: L, right: R, col_width: usize, gutter: &str) -> SideBySide<'_, L, R> {
+	SideBySide { left, right, col_width, gutter, wrap: false }
+}
+
+/// Displayable object returned by [`side_by_side`].
+#[derive(Copy, Clone)]
+pub struct SideBySide<'a, L, R> {
+	left: L,
+	right: R,
+	col_width: usize,
+	gutter: &'a str,
+	wrap: bool,
+}
+impl<'a, L, R> SideBySide<'a, L, R> {
+	/// Wraps long lines over multiple rows instead of truncating them.
+	#[inline]
+	pub fn wrap(self) -> SideBySide<'a, L, R> {
+		SideBySide { wrap: true, ..self }
+	}
+}
+impl<L: fmt::Display, R: fmt::Display> fmt::Display for SideBySide<'_, L, R> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let left = self.left.to_string();
+		let right = self.right.to_string();
+		let left = self.column(&left);
+		let right = self.column(&right);
+		for i in 0..usize::max(left.len(), right.len()) {
+			let l = left.get(i).copied().unwrap_or("");
+			let r = right.get(i).copied().unwrap_or("");
+			if self.wrap {
+				writeln!(f, "{}{}{}", crate::pad(self.col_width, Align::Left, l), self.gutter, r)?;
+			}
+			else {
+				let r = crate::truncate(self.col_width, r);
+				writeln!(f, "{}{}{}", crate::fit(self.col_width, Align::Left, l), self.gutter, r)?;
+			}
+		}
+		Ok(())
+	}
+}
+debug_as_display!([L: fmt::Display, R: fmt::Display] SideBySide<'_, L, R>);
+
+impl<L, R> SideBySide<'_, L, R> {
+	fn column<'s>(&self, s: &'s str) -> Vec<&'s str> {
+		let mut lines = Vec::new();
+		for line in s.lines() {
+			if self.wrap {
+				wrap_line(line, self.col_width, &mut lines);
+			}
+			else {
+				lines.push(line);
+			}
+		}
+		lines
+	}
+}
+
+// Splits the line into pieces of at most the width, every piece has at least one char.
+fn wrap_line<'s>(mut line: &'s str, width: usize, lines: &mut Vec<&'s str>) {
+	loop {
+		let mut used = 0;
+		let mut end = line.len();
+		for (i, chr) in line.char_indices() {
+			let w = crate::width::char_width(chr);
+			if used + w > width && i > 0 {
+				end = i;
+				break;
+			}
+			used += w;
+		}
+		lines.push(&line[..end]);
+		line = &line[end..];
+		if line.is_empty() {
+			break;
+		}
+	}
+}
+
+#[test]
+fn tests() {
+	// Unequal line counts
+	assert_eq!(side_by_side("a\nb\nc", "1", 3, "|").to_string(), "a  |1\nb  |\nc  |\n");
+	assert_eq!(side_by_side("a", "1\n2", 2, " ").to_string(), "a  1\n   2\n");
+	assert_eq!(side_by_side("", "", 2, " ").to_string(), "");
+
+	// Lines exceeding the column width
+	assert_eq!(side_by_side("abcdef", "123456", 4, "|").to_string(), "abc…|1234…\n");
+	assert_eq!(side_by_side("abcdef\nx", "123456", 4, "|").wrap().to_string(), "abcd|1234\nef  |56\nx   |\n");
+	assert_eq!(side_by_side("ab€\r\ncd", "€€€€€", 2, "|").wrap().to_string(), "ab|€€\n€ |€€\ncd|€\n");
+
+	// Displayable values
+	let left = crate::fmt!("x = "{1}"\n""y = "{2});
+	let right = crate::join("\n", [3, 4, 5]);
+	assert_eq!(side_by_side(left, right, 5, " -> ").to_string(), "x = 1 -> 3\ny = 2 -> 4\n      -> 5\n");
+}
@@ -0,0 +1,200 @@
+/// Estimates the length of the output in bytes.
+///
+/// Returns the bounds on the number of bytes written when displayed without formatting options, like [`Iterator::size_hint`].
+/// The hint is only used to reserve capacity up front, a wrong hint does not change the output.
+///
+/// ```
+/// use fmtools::SizeHint;
+///
+/// let names = ["alice", "bob"];
+/// assert_eq!(fmtools::join_slice(", ", &names).size_hint(), (10, Some(10)));
+///
+/// // Only the separators are counted without iterating the items
+/// assert_eq!(fmtools::join(", ", names).size_hint(), (2, None));
+/// ```
+///
+/// The [format!](crate::format!) macro reserves the length of the unconditional literal text of the template.
+/// Text inside control flow may not be written and the holes are not measured.
+/// Objects returned by [fmt!](crate::fmt!) do not carry a hint, wrap them in [`with_size_hint`] to provide one.
+///
+/// The default implementation returns `(0, None)`, any displayable type can opt in with an empty impl:
+///
+/// ```
+/// struct Token;
+/// impl std::fmt::Display for Token {
+/// 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+/// 		f.write_str("token")
+/// 	}
+/// }
+/// impl fmtools::SizeHint for Token {}
+///
+/// assert_eq!(fmtools::format_with_hint(Token), "token");
+/// ```
+pub trait SizeHint {
+	/// Returns the lower bound and the optional upper bound on the length of the output in bytes.
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, None)
+	}
+}
+
+impl<T: SizeHint + ?Sized> SizeHint for &T {
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(**self).size_hint()
+	}
+}
+
+impl SizeHint for str {
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len(), Some(self.len()))
+	}
+}
+
+impl SizeHint for char {
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len_utf8(), Some(self.len_utf8()))
+	}
+}
+
+#[cfg(feature = "std")]
+impl SizeHint for String {
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len(), Some(self.len()))
+	}
+}
+
+/// Displays the value with the lower bound of its [size hint](SizeHint).
+///
+/// ```
+/// use fmtools::SizeHint;
+///
+/// let id = 42;
+/// let s = fmtools::with_size_hint(11, fmtools::fmt(|f| write!(f, "id-{:08}", id)));
+/// assert_eq!(s.size_hint(), (11, None));
+/// assert_eq!(fmtools::format_with_hint(s), "id-00000042");
+/// ```
+///
+/// The value is displayed as is, the formatting options are passed through.
+#[inline]
+pub fn with_size_hint<T: core::fmt::Display>(min_len: usize, value: T) -> WithSizeHint<T> {
+	WithSizeHint { value, min_len }
+}
+
+/// Displayable object returned by [`with_size_hint`].
+#[derive(Copy, Clone)]
+pub struct WithSizeHint<T> {
+	value: T,
+	min_len: usize,
+}
+impl<T: core::fmt::Display> core::fmt::Display for WithSizeHint<T> {
+	#[inline]
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		self.value.fmt(f)
+	}
+}
+debug_as_display!([T: core::fmt::Display] WithSizeHint<T>);
+impl<T> SizeHint for WithSizeHint<T> {
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.min_len, None)
+	}
+}
+
+/// Formats the value into a new string reserving the [size hint](SizeHint) up front.
+///
+/// ```
+/// let names = ["alice", "bob", "carol"];
+/// let s = fmtools::format_with_hint(fmtools::join_slice(", ", &names));
+/// assert_eq!(s, "alice, bob, carol");
+/// assert_eq!(s.capacity(), s.len());
+/// ```
+///
+/// The lower bound is reserved, the string grows as usual if the output is longer.
+/// The [format!](crate::format!) macro formats through this function.
+#[cfg(feature = "std")]
+pub fn format_with_hint<T: core::fmt::Display + SizeHint>(value: T) -> String {
+	let (lower, _) = value.size_hint();
+	let mut s = String::with_capacity(lower);
+	core::fmt::Write::write_fmt(&mut s, format_args!("{}", value)).expect("a Display implementation returned an error unexpectedly");
+	s
+}
+
+// Sums the hints of a separated sequence of items.
+pub(crate) fn join_hint<S: SizeHint + ?Sized, I: Iterator>(sep: &S, iter: I) -> (usize, Option<usize>) where I::Item: SizeHint {
+	let (sep_lower, sep_upper) = sep.size_hint();
+	let mut lower = 0usize;
+	let mut upper = Some(0usize);
+	for (i, item) in iter.enumerate() {
+		let (item_lower, item_upper) = item.size_hint();
+		if i > 0 {
+			lower = lower.saturating_add(sep_lower);
+			upper = upper.zip(sep_upper).and_then(|(a, b)| a.checked_add(b));
+		}
+		lower = lower.saturating_add(item_lower);
+		upper = upper.zip(item_upper).and_then(|(a, b)| a.checked_add(b));
+	}
+	(lower, upper)
+}
+
+#[test]
+fn tests() {
+	use core::fmt;
+
+	// Literal text of the template
+	let x = 42;
+	assert_eq!(crate::__fmt_len!([]), 0);
+	assert_eq!(crate::__fmt_len!([] "x = "{x}), 4);
+	assert_eq!(crate::__fmt_len!([] "a" 'b' "ü" {x} "c" 1), 6);
+	assert_eq!(crate::__fmt_len!([] default ":x"; "("{x}")"), 2);
+
+	// Text inside control flow and escape hatches is not counted
+	let len = crate::__fmt_len! {[]
+		"list:"
+		let label = "unused";
+		if x > 10 { "big" } else if x > 5 { "mid" } else { "small" }
+		for item in &["one", "two"] sep ", " { {item} } else { "none" }
+		match x { 42 => "answer", _ => {{x}} }
+		while false { "never" }
+		|f| f.write_str(label)?;
+		|f| { f.write_str("hatch")?; }
+		glue " " { ("g") ({x}) }
+		("group" {x})
+		"end"
+	};
+	assert_eq!(len, 13);
+
+	// The format macro reserves the literal text
+	assert_eq!(crate::format!("x = "{x}", hex = "{x:#x}), "x = 42, hex = 0x2a");
+	let s = crate::format!("literal only");
+	assert_eq!((s.as_str(), s.capacity()), ("literal only", 12));
+
+	// Template objects are zero sized and carry no hint
+	let tmpl = crate::fmt!("x = "{x});
+	assert_eq!(tmpl.size_hint(), (0, None));
+	assert_eq!(core::mem::size_of_val(&crate::fmt(|f| f.write_str("x"))), 0);
+	assert_eq!(crate::with_size_hint(4, tmpl).size_hint(), (4, None));
+	assert_eq!(format_with_hint(crate::with_size_hint(4, tmpl)), "x = 42");
+
+	// Joins count the separators only, join slices are exact
+	let names = vec![String::from("alice"), String::from("bob")];
+	assert_eq!(crate::join(", ", &names).size_hint(), (2, None));
+	assert_eq!(crate::join('|', ["a", "bc", "def"]).size_hint(), (2, None));
+	assert_eq!(crate::join(", ", [""; 0]).size_hint(), (0, Some(0)));
+	assert_eq!(crate::join_slice("; ", &names).size_hint(), (10, Some(10)));
+	assert_eq!(format_with_hint(crate::join_slice("; ", &names)), "alice; bob");
+
+	// Defaults
+	struct Unknown;
+	impl fmt::Display for Unknown {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			f.write_str("unknown")
+		}
+	}
+	impl SizeHint for Unknown {}
+	assert_eq!(Unknown.size_hint(), (0, None));
+	assert_eq!(format_with_hint(Unknown), "unknown");
+}
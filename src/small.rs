@@ -0,0 +1,218 @@
+use core::{fmt, ops};
+use crate::FixedString;
+
+/// String stored inline up to a fixed capacity which spills to the heap when exceeded.
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut s = fmtools::SmallString::<8>::new();
+/// write!(s, "{}", 42).unwrap();
+/// assert_eq!(s, "42");
+/// assert!(s.is_inline());
+///
+/// write!(s, " is the answer").unwrap();
+/// assert_eq!(s, "42 is the answer");
+/// assert!(!s.is_inline());
+/// ```
+///
+/// See [small_format!](crate::small_format!) to render templates into a small string.
+#[derive(Clone)]
+pub struct SmallString<const N: usize> {
+	repr: Repr<N>,
+}
+
+#[derive(Clone)]
+enum Repr<const N: usize> {
+	Inline(FixedString<N>),
+	Heap(String),
+}
+
+impl<const N: usize> SmallString<N> {
+	/// Constructs a new empty string.
+	#[inline]
+	pub const fn new() -> SmallString<N> {
+		SmallString { repr: Repr::Inline(FixedString::new()) }
+	}
+	/// Constructs a new empty string which can hold at least `capacity` bytes.
+	///
+	/// The string starts on the heap if the capacity does not fit inline.
+	///
+	/// ```
+	/// assert!(fmtools::SmallString::<8>::with_capacity(8).is_inline());
+	/// assert!(!fmtools::SmallString::<8>::with_capacity(9).is_inline());
+	/// ```
+	#[inline]
+	pub fn with_capacity(capacity: usize) -> SmallString<N> {
+		if capacity <= N {
+			SmallString::new()
+		}
+		else {
+			SmallString { repr: Repr::Heap(String::with_capacity(capacity)) }
+		}
+	}
+	/// Returns if the string is stored inline without heap allocation.
+	#[inline]
+	pub const fn is_inline(&self) -> bool {
+		matches!(self.repr, Repr::Inline(_))
+	}
+	/// Returns the string slice.
+	#[inline]
+	pub fn as_str(&self) -> &str {
+		match &self.repr {
+			Repr::Inline(s) => s.as_str(),
+			Repr::Heap(s) => s.as_str(),
+		}
+	}
+	/// Converts into a heap allocated string.
+	#[inline]
+	pub fn into_string(self) -> String {
+		match self.repr {
+			Repr::Inline(s) => String::from(s.as_str()),
+			Repr::Heap(s) => s,
+		}
+	}
+}
+
+impl<const N: usize> Default for SmallString<N> {
+	#[inline]
+	fn default() -> SmallString<N> {
+		SmallString::new()
+	}
+}
+
+impl<const N: usize> fmt::Write for SmallString<N> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		match &mut self.repr {
+			Repr::Inline(inline) if inline.len() + s.len() <= N => inline.write_str(s),
+			Repr::Inline(inline) => {
+				let mut heap = String::with_capacity(usize::max(N * 2, inline.len() + s.len()));
+				heap.push_str(inline.as_str());
+				heap.push_str(s);
+				self.repr = Repr::Heap(heap);
+				Ok(())
+			},
+			Repr::Heap(heap) => {
+				heap.push_str(s);
+				Ok(())
+			},
+		}
+	}
+}
+
+impl<const N: usize> ops::Deref for SmallString<N> {
+	type Target = str;
+	#[inline]
+	fn deref(&self) -> &str {
+		self.as_str()
+	}
+}
+impl<const N: usize> AsRef<str> for SmallString<N> {
+	#[inline]
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl<const N: usize> fmt::Display for SmallString<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self.as_str(), f)
+	}
+}
+impl<const N: usize> fmt::Debug for SmallString<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(self.as_str(), f)
+	}
+}
+
+impl<const N: usize, const M: usize> PartialEq<SmallString<M>> for SmallString<N> {
+	#[inline]
+	fn eq(&self, other: &SmallString<M>) -> bool {
+		self.as_str() == other.as_str()
+	}
+}
+impl<const N: usize> Eq for SmallString<N> {}
+impl<const N: usize> PartialEq<str> for SmallString<N> {
+	#[inline]
+	fn eq(&self, other: &str) -> bool {
+		self.as_str() == other
+	}
+}
+impl<const N: usize> PartialEq<&str> for SmallString<N> {
+	#[inline]
+	fn eq(&self, other: &&str) -> bool {
+		self.as_str() == *other
+	}
+}
+
+impl<const N: usize> From<SmallString<N>> for String {
+	#[inline]
+	fn from(s: SmallString<N>) -> String {
+		s.into_string()
+	}
+}
+
+/// Renders [fmt syntax](crate::fmt!) into a [`SmallString`].
+///
+/// The first argument is the inline capacity in bytes, longer outputs spill to the heap.
+/// Templates whose literal text alone does not fit inline start on the heap, see [`SmallString::with_capacity`].
+///
+/// ```
+/// let id = 42;
+/// let s = fmtools::small_format!(24; "id="{id:#x});
+/// assert_eq!(s, "id=0x2a");
+/// assert!(s.is_inline());
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! small_format {
+	($n:expr; $($tt:tt)*) => {{
+		let mut s = $crate::SmallString::<{ $n }>::with_capacity($crate::__fmt_len!([] $($tt)*));
+		::core::fmt::Write::write_fmt(&mut s, ::core::format_args!("{}", $crate::fmt(|_f| {
+			$crate::__fmt!{_f $($tt)*}
+			Ok(())
+		}))).expect("a Display implementation returned an error unexpectedly");
+		s
+	}};
+}
+
+#[test]
+fn tests() {
+	use std::fmt::Write;
+
+	// Exactly at capacity stays inline
+	let s = small_format!(5; "ab"{123});
+	assert_eq!(s, "ab123");
+	assert!(s.is_inline());
+
+	// Spills in the middle of a multi-byte char
+	let s = small_format!(4; "ab"'€'"cd");
+	assert_eq!(s, "ab€cd");
+	assert!(!s.is_inline());
+
+	// Empty
+	let s = small_format!(0; "");
+	assert!(s.is_inline());
+	let s = small_format!(0; "a");
+	assert_eq!(s, "a");
+	assert!(!s.is_inline());
+
+	// Literal text longer than the inline capacity starts on the heap
+	let s = small_format!(4; "hello "{1});
+	assert_eq!(s, "hello 1");
+	assert!(!s.is_inline());
+
+	// Keeps growing on the heap
+	let mut s = SmallString::<2>::new();
+	for i in 0..100 {
+		write!(s, "{}", i % 10).unwrap();
+	}
+	assert_eq!(s.len(), 100);
+	assert_eq!(&s[..12], "012345678901");
+	assert_eq!(format!("{:>4}|{:?}", small_format!(8; "42"), small_format!(8; "42")), "  42|\"42\"");
+	assert_eq!(String::from(small_format!(2; "hello")), "hello");
+
+	// Display errors panic like format!
+	let error = crate::fmt(|_| Err(core::fmt::Error));
+	assert!(std::panic::catch_unwind(|| small_format!(8; {error})).is_err());
+}
@@ -0,0 +1,528 @@
+use core::fmt;
+
+/// Formats the value with a format spec parsed at runtime.
+///
+/// ```
+/// let columns = [">8.2", "#010x", "*^9"];
+/// let s = fmtools::format!(
+/// 	"["{fmtools::apply_spec(columns[0], &3.14159).unwrap()}"]"
+/// 	"["{fmtools::apply_spec(columns[1], &255).unwrap()}"]"
+/// 	"["{fmtools::apply_spec(columns[2], "mid").unwrap()}"]"
+/// );
+/// assert_eq!(s, "[    3.14][0x000000ff][***mid***]");
+/// ```
+///
+/// The spec is the part after the colon in a compile-time format string: `[[fill]align][sign]['#']['0'][width]['.' precision][type]`.
+/// The supported types are none, `?`, `x`, `X`, `o`, `b`, `e` and `E`.
+/// Width and precision must be literal numbers, arguments like `width$` and `*` are not supported.
+///
+/// The output is the same as the equivalent compile-time spec.
+///
+/// Errors are returned if the spec is invalid or the type is not supported by the value:
+///
+/// ```
+/// use fmtools::SpecError;
+///
+/// assert_eq!(fmtools::apply_spec(">10q", &1).unwrap_err(), SpecError::Unexpected(3));
+/// assert_eq!(fmtools::apply_spec("x", &1.5).unwrap_err(), SpecError::UnsupportedType('x'));
+/// ```
+///
+/// To format the same column many times, parse the spec once with [`FormatSpec::parse`].
+#[inline]
+pub fn apply_spec<'a, T: RuntimeFormat + ?Sized>(spec: &str, value: &'a T) -> Result<ApplySpec<'a, T>, SpecError> {
+	FormatSpec::parse(spec)?.apply(value)
+}
+
+/// Error returned when parsing or applying a format spec.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SpecError {
+	/// Unexpected character at the byte offset in the spec.
+	Unexpected(usize),
+	/// The width or precision is too large.
+	Overflow,
+	/// The type is not supported by the value.
+	UnsupportedType(char),
+	/// The flag is not supported by the value.
+	UnsupportedFlag(char),
+}
+impl fmt::Display for SpecError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			SpecError::Unexpected(offset) => write!(f, "unexpected character at offset {} in format spec", offset),
+			SpecError::Overflow => f.write_str("width or precision too large in format spec"),
+			SpecError::UnsupportedType(ty) => write!(f, "format spec type `{}` not supported by the value", ty),
+			SpecError::UnsupportedFlag(flag) => write!(f, "format spec flag `{}` not supported by the value", flag),
+		}
+	}
+}
+#[cfg(feature = "std")]
+impl std::error::Error for SpecError {}
+
+/// The kind of formatting selected by the type of a [`FormatSpec`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SpecType {
+	/// No type, formats with `Display`.
+	#[default]
+	Display,
+	/// `?`, formats with `Debug`.
+	Debug,
+	/// `x`, formats with `LowerHex`.
+	LowerHex,
+	/// `X`, formats with `UpperHex`.
+	UpperHex,
+	/// `o`, formats with `Octal`.
+	Octal,
+	/// `b`, formats with `Binary`.
+	Binary,
+	/// `e`, formats with `LowerExp`.
+	LowerExp,
+	/// `E`, formats with `UpperExp`.
+	UpperExp,
+}
+impl SpecType {
+	fn as_char(self) -> char {
+		match self {
+			SpecType::Display => ' ',
+			SpecType::Debug => '?',
+			SpecType::LowerHex => 'x',
+			SpecType::UpperHex => 'X',
+			SpecType::Octal => 'o',
+			SpecType::Binary => 'b',
+			SpecType::LowerExp => 'e',
+			SpecType::UpperExp => 'E',
+		}
+	}
+}
+
+/// Format spec parsed at runtime.
+///
+/// ```
+/// let spec = fmtools::FormatSpec::parse("+.1e").unwrap();
+/// assert_eq!(spec.apply(&1234.5).unwrap().to_string(), "+1.2e3");
+/// ```
+///
+/// See [`apply_spec`] for more information.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FormatSpec {
+	/// The fill character, defaults to a space.
+	pub fill: Option<char>,
+	/// The alignment, defaults to right for numbers and left for everything else.
+	pub align: Option<crate::Align>,
+	/// The `+` flag.
+	pub plus: bool,
+	/// The `#` flag.
+	pub alternate: bool,
+	/// The `0` flag.
+	pub zero: bool,
+	/// The minimum width.
+	pub width: Option<usize>,
+	/// The precision.
+	pub precision: Option<usize>,
+	/// The type.
+	pub ty: SpecType,
+}
+
+impl FormatSpec {
+	/// Parses the format spec.
+	pub fn parse(spec: &str) -> Result<FormatSpec, SpecError> {
+		let mut result = FormatSpec::default();
+		let mut s = spec;
+		let offset = |s: &str| spec.len() - s.len();
+
+		// Fill and alignment
+		let mut chars = s.chars();
+		let first = chars.next();
+		let second = chars.next();
+		if let (Some(fill), Some(align)) = (first, second.and_then(parse_align)) {
+			result.fill = Some(fill);
+			result.align = Some(align);
+			s = &s[fill.len_utf8() + 1..];
+		}
+		else if let Some(align) = first.and_then(parse_align) {
+			result.align = Some(align);
+			s = &s[1..];
+		}
+
+		// Flags
+		if let Some(rest) = s.strip_prefix('+') {
+			result.plus = true;
+			s = rest;
+		}
+		else if let Some(rest) = s.strip_prefix('-') {
+			s = rest;
+		}
+		if let Some(rest) = s.strip_prefix('#') {
+			result.alternate = true;
+			s = rest;
+		}
+		if let Some(rest) = s.strip_prefix('0') {
+			result.zero = true;
+			s = rest;
+		}
+
+		// Width and precision
+		result.width = parse_number(&mut s)?;
+		if let Some(rest) = s.strip_prefix('.') {
+			s = rest;
+			result.precision = Some(parse_number(&mut s)?.ok_or(SpecError::Unexpected(offset(s)))?);
+		}
+
+		// Type
+		result.ty = match s {
+			"" => SpecType::Display,
+			"?" => SpecType::Debug,
+			"x" => SpecType::LowerHex,
+			"X" => SpecType::UpperHex,
+			"o" => SpecType::Octal,
+			"b" => SpecType::Binary,
+			"e" => SpecType::LowerExp,
+			"E" => SpecType::UpperExp,
+			_ => return Err(SpecError::Unexpected(offset(s))),
+		};
+		Ok(result)
+	}
+
+	/// Formats the value with this spec.
+	///
+	/// Returns an error if the type or flags are not supported by the value.
+	pub fn apply<'a, T: RuntimeFormat + ?Sized>(&self, value: &'a T) -> Result<ApplySpec<'a, T>, SpecError> {
+		value.check(self)?;
+		Ok(ApplySpec { value, spec: *self })
+	}
+}
+
+// Measures the output in chars like the standard formatting.
+fn char_count<T: fmt::Display + ?Sized>(value: &T) -> Result<usize, fmt::Error> {
+	struct Measure(usize);
+	impl fmt::Write for Measure {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			self.0 += s.chars().count();
+			Ok(())
+		}
+	}
+	let mut measure = Measure(0);
+	fmt::write(&mut measure, format_args!("{}", value))?;
+	Ok(measure.0)
+}
+
+fn parse_align(chr: char) -> Option<crate::Align> {
+	match chr {
+		'<' => Some(crate::Align::Left),
+		'^' => Some(crate::Align::Center),
+		'>' => Some(crate::Align::Right),
+		_ => None,
+	}
+}
+
+fn parse_number(s: &mut &str) -> Result<Option<usize>, SpecError> {
+	let len = s.bytes().take_while(u8::is_ascii_digit).count();
+	if len == 0 {
+		return Ok(None);
+	}
+	let number = s[..len].parse().map_err(|_| SpecError::Overflow)?;
+	*s = &s[len..];
+	Ok(Some(number))
+}
+
+/// Displayable object returned by [`apply_spec`] and [`FormatSpec::apply`].
+pub struct ApplySpec<'a, T: ?Sized> {
+	value: &'a T,
+	spec: FormatSpec,
+}
+impl<T: ?Sized> Copy for ApplySpec<'_, T> {}
+impl<T: ?Sized> Clone for ApplySpec<'_, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<T: RuntimeFormat + ?Sized> fmt::Display for ApplySpec<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let spec = &self.spec;
+		let core = crate::fmt(|f| self.value.write(f, spec));
+		let (padded, numeric) = self.value.layout(spec);
+		let width = match spec.width {
+			Some(width) if padded && !(numeric && spec.zero) => width,
+			_ => return core.fmt(f),
+		};
+
+		// Pad the output with the fill character
+		let len = char_count(&core)?;
+		let padding = width.saturating_sub(len);
+		let default = if numeric { crate::Align::Right } else { crate::Align::Left };
+		let (pre, post) = match spec.align.unwrap_or(default) {
+			crate::Align::Left => (0, padding),
+			crate::Align::Center => (padding / 2, padding - padding / 2),
+			crate::Align::Right => (padding, 0),
+		};
+		let fill = spec.fill.unwrap_or(' ');
+		crate::pad::write_fill(f, fill, pre)?;
+		core.fmt(f)?;
+		crate::pad::write_fill(f, fill, post)
+	}
+}
+
+debug_as_display!([T: RuntimeFormat + ?Sized] ApplySpec<'_, T>);
+
+/// Values which can be formatted with a [`FormatSpec`].
+///
+/// Implemented for the primitive integers and floats with all types, for `str`, `String`, `char` and `bool` with `Display` and `Debug`.
+///
+/// Any other value can be formatted as `dyn Display` with fill, alignment and width:
+///
+/// ```
+/// let value = std::net::Ipv4Addr::LOCALHOST;
+/// let s = fmtools::apply_spec("-^13", &value as &dyn std::fmt::Display).unwrap().to_string();
+/// assert_eq!(s, "--127.0.0.1--");
+/// ```
+pub trait RuntimeFormat: private::Sealed {}
+
+mod private {
+	use core::fmt;
+	use super::{FormatSpec, SpecError};
+
+	pub trait Sealed {
+		/// Checks if the spec is supported by the value.
+		fn check(&self, spec: &FormatSpec) -> Result<(), SpecError>;
+		/// Writes the value with every part of the spec except fill, alignment and width.
+		/// The width is only used for the `0` flag.
+		fn write(&self, f: &mut fmt::Formatter, spec: &FormatSpec) -> fmt::Result;
+		/// Returns if the output is padded to the width and if the value is numeric.
+		fn layout(&self, spec: &FormatSpec) -> (bool, bool);
+	}
+}
+
+// Writes the value with the flags, zero padding and precision of the spec.
+macro_rules! write_spec {
+	($f:expr, $value:expr, $spec:expr, $ty:literal) => {{
+		let spec = $spec;
+		let w = spec.width.unwrap_or(0);
+		let p = spec.precision.unwrap_or(0);
+		match (spec.plus, spec.alternate, spec.zero && spec.width.is_some(), spec.precision.is_some()) {
+			(false, false, false, false) => write!($f, concat!("{:", $ty, "}"), $value),
+			(false, false, false, true) => write!($f, concat!("{:.p$", $ty, "}"), $value, p = p),
+			(false, false, true, false) => write!($f, concat!("{:0w$", $ty, "}"), $value, w = w),
+			(false, false, true, true) => write!($f, concat!("{:0w$.p$", $ty, "}"), $value, w = w, p = p),
+			(false, true, false, false) => write!($f, concat!("{:#", $ty, "}"), $value),
+			(false, true, false, true) => write!($f, concat!("{:#.p$", $ty, "}"), $value, p = p),
+			(false, true, true, false) => write!($f, concat!("{:#0w$", $ty, "}"), $value, w = w),
+			(false, true, true, true) => write!($f, concat!("{:#0w$.p$", $ty, "}"), $value, w = w, p = p),
+			(true, false, false, false) => write!($f, concat!("{:+", $ty, "}"), $value),
+			(true, false, false, true) => write!($f, concat!("{:+.p$", $ty, "}"), $value, p = p),
+			(true, false, true, false) => write!($f, concat!("{:+0w$", $ty, "}"), $value, w = w),
+			(true, false, true, true) => write!($f, concat!("{:+0w$.p$", $ty, "}"), $value, w = w, p = p),
+			(true, true, false, false) => write!($f, concat!("{:+#", $ty, "}"), $value),
+			(true, true, false, true) => write!($f, concat!("{:+#.p$", $ty, "}"), $value, p = p),
+			(true, true, true, false) => write!($f, concat!("{:+#0w$", $ty, "}"), $value, w = w),
+			(true, true, true, true) => write!($f, concat!("{:+#0w$.p$", $ty, "}"), $value, w = w, p = p),
+		}
+	}};
+}
+
+macro_rules! impl_integer {
+	($($ty:ty),*) => {
+		$(
+			impl RuntimeFormat for $ty {}
+			impl private::Sealed for $ty {
+				fn check(&self, _spec: &FormatSpec) -> Result<(), SpecError> {
+					Ok(())
+				}
+				fn write(&self, f: &mut fmt::Formatter, spec: &FormatSpec) -> fmt::Result {
+					match spec.ty {
+						SpecType::Display => write_spec!(f, self, spec, ""),
+						SpecType::Debug => write_spec!(f, self, spec, "?"),
+						SpecType::LowerHex => write_spec!(f, self, spec, "x"),
+						SpecType::UpperHex => write_spec!(f, self, spec, "X"),
+						SpecType::Octal => write_spec!(f, self, spec, "o"),
+						SpecType::Binary => write_spec!(f, self, spec, "b"),
+						SpecType::LowerExp => write_spec!(f, self, spec, "e"),
+						SpecType::UpperExp => write_spec!(f, self, spec, "E"),
+					}
+				}
+				fn layout(&self, _spec: &FormatSpec) -> (bool, bool) {
+					(true, true)
+				}
+			}
+		)*
+	};
+}
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_float {
+	($($ty:ty),*) => {
+		$(
+			impl RuntimeFormat for $ty {}
+			impl private::Sealed for $ty {
+				fn check(&self, spec: &FormatSpec) -> Result<(), SpecError> {
+					match spec.ty {
+						SpecType::Display | SpecType::Debug | SpecType::LowerExp | SpecType::UpperExp => Ok(()),
+						ty => Err(SpecError::UnsupportedType(ty.as_char())),
+					}
+				}
+				fn write(&self, f: &mut fmt::Formatter, spec: &FormatSpec) -> fmt::Result {
+					match spec.ty {
+						SpecType::Debug => write_spec!(f, self, spec, "?"),
+						SpecType::LowerExp => write_spec!(f, self, spec, "e"),
+						SpecType::UpperExp => write_spec!(f, self, spec, "E"),
+						_ => write_spec!(f, self, spec, ""),
+					}
+				}
+				fn layout(&self, _spec: &FormatSpec) -> (bool, bool) {
+					(true, true)
+				}
+			}
+		)*
+	};
+}
+impl_float!(f32, f64);
+
+macro_rules! impl_text {
+	($($ty:ty => $debug_padded:literal),*) => {
+		$(
+			impl RuntimeFormat for $ty {}
+			impl private::Sealed for $ty {
+				fn check(&self, spec: &FormatSpec) -> Result<(), SpecError> {
+					match spec.ty {
+						SpecType::Display | SpecType::Debug => Ok(()),
+						ty => Err(SpecError::UnsupportedType(ty.as_char())),
+					}
+				}
+				fn write(&self, f: &mut fmt::Formatter, spec: &FormatSpec) -> fmt::Result {
+					match spec.ty {
+						SpecType::Debug => write_spec!(f, self, spec, "?"),
+						_ => write_spec!(f, self, spec, ""),
+					}
+				}
+				fn layout(&self, spec: &FormatSpec) -> (bool, bool) {
+					// Debug formatting of strings and chars ignores the width
+					(spec.ty != SpecType::Debug || $debug_padded, false)
+				}
+			}
+		)*
+	};
+}
+impl_text!(str => false, char => false, bool => true);
+#[cfg(feature = "std")]
+impl_text!(String => false);
+
+impl<T: RuntimeFormat + ?Sized> RuntimeFormat for &T {}
+impl<T: RuntimeFormat + ?Sized> private::Sealed for &T {
+	#[inline]
+	fn check(&self, spec: &FormatSpec) -> Result<(), SpecError> {
+		(**self).check(spec)
+	}
+	#[inline]
+	fn write(&self, f: &mut fmt::Formatter, spec: &FormatSpec) -> fmt::Result {
+		(**self).write(f, spec)
+	}
+	#[inline]
+	fn layout(&self, spec: &FormatSpec) -> (bool, bool) {
+		(**self).layout(spec)
+	}
+}
+
+impl RuntimeFormat for dyn fmt::Display + '_ {}
+impl private::Sealed for dyn fmt::Display + '_ {
+	fn check(&self, spec: &FormatSpec) -> Result<(), SpecError> {
+		if spec.ty != SpecType::Display {
+			Err(SpecError::UnsupportedType(spec.ty.as_char()))
+		}
+		else if spec.plus {
+			Err(SpecError::UnsupportedFlag('+'))
+		}
+		else if spec.alternate {
+			Err(SpecError::UnsupportedFlag('#'))
+		}
+		else if spec.zero {
+			Err(SpecError::UnsupportedFlag('0'))
+		}
+		else if spec.precision.is_some() {
+			Err(SpecError::UnsupportedFlag('.'))
+		}
+		else {
+			Ok(())
+		}
+	}
+	fn write(&self, f: &mut fmt::Formatter, _spec: &FormatSpec) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+	fn layout(&self, _spec: &FormatSpec) -> (bool, bool) {
+		(true, false)
+	}
+}
+
+#[test]
+fn tests() {
+	macro_rules! check {
+		($value:expr; $($spec:literal),*) => {
+			$(
+				let value = $value;
+				let expected = format!(concat!("{:", $spec, "}"), value);
+				assert_eq!(apply_spec($spec, &value).unwrap().to_string(), expected, "{:?} {:?}", $spec, value);
+			)*
+		};
+	}
+	macro_rules! check_all {
+		($($value:expr),*) => {
+			$(
+				check!($value; "", ">12", "<12", "^12", "*^12", "0>12", "€<5", "+", "012", "+012", "<012", "-?", "?", "12?");
+			)*
+		};
+	}
+	macro_rules! check_int {
+		($($value:expr),*) => {
+			$(
+				check_all!($value);
+				check!($value; "x", "#x", "#010x", "X", "#X", "o", "#o", "b", "#b", "#018b", "_>10b", "e", "E", ".2e", "+.3E", "12.1e", ".3");
+			)*
+		};
+	}
+	macro_rules! check_float {
+		($($value:expr),*) => {
+			$(
+				check_all!($value);
+				check!($value; ".0", ".2", "8.2", "*^9.2", "+.3", "08.3", "+010.2", "e", "E", ".2e", "08.3e", "#?", ".1?");
+			)*
+		};
+	}
+	check_int!(0u8, 255u8, -128i8, 1234i32, -1234i64, u64::MAX, i128::MIN, 42usize);
+	check_float!(0.0f32, -0.0f64, 1.23456f64, -1234.5f64, 1e-7f32, f64::INFINITY, f64::NAN);
+	check_all!("", "abc", "héllo", 'x', '€', true, false);
+	check!("abcdef"; ".2", "8.3", "*>8.3", "^7.1", "05", "#");
+	check!('c'; ".0", "3?");
+	#[cfg(feature = "std")]
+	check!(String::from("text"); "", ">8", ".2", "?");
+
+	// Any displayable value
+	let value = core::net::Ipv4Addr::LOCALHOST;
+	let value: &dyn fmt::Display = &value;
+	assert_eq!(apply_spec(">12", &value).unwrap().to_string(), format!("{:>12}", value));
+	assert_eq!(apply_spec("", &value).unwrap().to_string(), "127.0.0.1");
+	assert_eq!(apply_spec("x", &value).unwrap_err(), SpecError::UnsupportedType('x'));
+	assert_eq!(apply_spec("+", &value).unwrap_err(), SpecError::UnsupportedFlag('+'));
+	assert_eq!(apply_spec(".3", &value).unwrap_err(), SpecError::UnsupportedFlag('.'));
+
+	// Invalid specs
+	assert_eq!(FormatSpec::parse("q"), Err(SpecError::Unexpected(0)));
+	assert_eq!(FormatSpec::parse(">>>"), Err(SpecError::Unexpected(2)));
+	assert_eq!(FormatSpec::parse("10."), Err(SpecError::Unexpected(3)));
+	assert_eq!(FormatSpec::parse("x?"), Err(SpecError::Unexpected(0)));
+	assert_eq!(FormatSpec::parse("+-5"), Err(SpecError::Unexpected(1)));
+	assert_eq!(FormatSpec::parse("99999999999999999999999"), Err(SpecError::Overflow));
+	assert_eq!(apply_spec("b", &1.0).unwrap_err(), SpecError::UnsupportedType('b'));
+	assert_eq!(apply_spec("e", "s").unwrap_err(), SpecError::UnsupportedType('e'));
+	assert_eq!(SpecError::Unexpected(3).to_string(), "unexpected character at offset 3 in format spec");
+
+	// Parsed fields
+	let spec = FormatSpec::parse("€^+#012.3e").unwrap();
+	assert_eq!(spec, FormatSpec {
+		fill: Some('€'),
+		align: Some(crate::Align::Center),
+		plus: true,
+		alternate: true,
+		zero: true,
+		width: Some(12),
+		precision: Some(3),
+		ty: SpecType::LowerExp,
+	});
+}
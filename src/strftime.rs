@@ -0,0 +1,176 @@
+use core::fmt;
+
+/// Calendar date and time of day with an UTC offset.
+///
+/// A minimal representation to format timestamps with [`strftime`] without depending on a datetime crate.
+/// Convert from your datetime type of choice by filling in the fields.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct CivilTime {
+	/// Proleptic Gregorian year.
+	pub year: i32,
+	/// Month of the year in `1..=12`.
+	pub month: u8,
+	/// Day of the month in `1..=31`.
+	pub day: u8,
+	/// Hour of the day in `0..24`.
+	pub hour: u8,
+	/// Minute of the hour in `0..60`.
+	pub minute: u8,
+	/// Second of the minute in `0..60`.
+	pub second: u8,
+	/// Nanoseconds of the second in `0..1_000_000_000`.
+	pub nanos: u32,
+	/// Offset from UTC in seconds east.
+	pub offset: i32,
+}
+
+impl CivilTime {
+	/// Converts a Unix timestamp to UTC civil time.
+	///
+	/// ```
+	/// let t = fmtools::CivilTime::from_unix(1_000_000_000, 0);
+	/// assert_eq!((t.year, t.month, t.day, t.hour, t.minute, t.second), (2001, 9, 9, 1, 46, 40));
+	/// ```
+	pub const fn from_unix(secs: i64, nanos: u32) -> CivilTime {
+		let days = secs.div_euclid(86400);
+		let time = secs.rem_euclid(86400) as u32;
+		// Howard Hinnant's civil_from_days
+		let z = days + 719468;
+		let era = z.div_euclid(146097);
+		let doe = z.rem_euclid(146097);
+		let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+		let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+		let mp = (5 * doy + 2) / 153;
+		let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+		let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+		let year = (yoe + era * 400 + (month <= 2) as i64) as i32;
+		CivilTime {
+			year, month, day,
+			hour: (time / 3600) as u8,
+			minute: (time / 60 % 60) as u8,
+			second: (time % 60) as u8,
+			nanos,
+			offset: 0,
+		}
+	}
+
+	/// Returns the day of the week, `0` is Sunday.
+	pub const fn weekday(&self) -> u8 {
+		// Howard Hinnant's days_from_civil
+		let y = self.year as i64 - (self.month <= 2) as i64;
+		let era = y.div_euclid(400);
+		let yoe = y.rem_euclid(400);
+		let m = self.month as i64;
+		let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + self.day as i64 - 1;
+		let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+		let days = era * 146097 + doe - 719468;
+		// 1970-01-01 was a Thursday
+		(days + 4).rem_euclid(7) as u8
+	}
+}
+
+/// Formats the time with a strftime-like pattern.
+///
+/// ```
+/// let t = fmtools::CivilTime::from_unix(1_000_000_000, 123_000_000);
+/// let s = fmtools::format!("["{fmtools::strftime("%a %d %b %Y %H:%M:%S.%f %z", &t)}"]");
+/// assert_eq!(s, "[Sun 09 Sep 2001 01:46:40.123000000 +0000]");
+/// ```
+///
+/// | Directive | Meaning | Example |
+/// |-----------|---------|---------|
+/// | `%Y` | Year, at least 4 digits | `2001` |
+/// | `%m` | Month, 2 digits | `09` |
+/// | `%d` | Day of the month, 2 digits | `09` |
+/// | `%H` | Hour, 2 digits | `01` |
+/// | `%M` | Minute, 2 digits | `46` |
+/// | `%S` | Second, 2 digits | `40` |
+/// | `%f` | Nanoseconds, 9 digits | `123000000` |
+/// | `%z` | UTC offset as `+hhmm` | `+0200` |
+/// | `%a` | Abbreviated English weekday | `Sun` |
+/// | `%b` | Abbreviated English month | `Sep` |
+/// | `%%` | Literal `%` | `%` |
+///
+/// The pattern is parsed while formatting and may be configured at runtime.
+/// Unknown directives are written literally including the `%`, as is a `%` at the end of the pattern.
+#[inline]
+pub fn strftime<'a>(pattern: &'a str, time: &'a CivilTime) -> Strftime<'a> {
+	Strftime { pattern, time }
+}
+
+/// Displayable object returned by [`strftime`].
+#[derive(Copy, Clone)]
+pub struct Strftime<'a> {
+	pattern: &'a str,
+	time: &'a CivilTime,
+}
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+impl fmt::Display for Strftime<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let t = self.time;
+		let mut pattern = self.pattern;
+		while let Some(i) = pattern.find('%') {
+			f.write_str(&pattern[..i])?;
+			let mut chars = pattern[i + 1..].chars();
+			let directive = chars.next();
+			match directive {
+				Some('Y') => write!(f, "{:04}", t.year)?,
+				Some('m') => write!(f, "{:02}", t.month)?,
+				Some('d') => write!(f, "{:02}", t.day)?,
+				Some('H') => write!(f, "{:02}", t.hour)?,
+				Some('M') => write!(f, "{:02}", t.minute)?,
+				Some('S') => write!(f, "{:02}", t.second)?,
+				Some('f') => write!(f, "{:09}", t.nanos)?,
+				Some('z') => {
+					let sign = if t.offset < 0 { '-' } else { '+' };
+					let offset = t.offset.unsigned_abs() / 60;
+					write!(f, "{}{:02}{:02}", sign, offset / 60, offset % 60)?;
+				},
+				Some('a') => f.write_str(WEEKDAYS[t.weekday() as usize])?,
+				Some('b') => f.write_str(MONTHS.get((t.month as usize).wrapping_sub(1)).copied().unwrap_or("???"))?,
+				Some('%') => f.write_str("%")?,
+				Some(chr) => f.write_str(&pattern[i..i + 1 + chr.len_utf8()])?,
+				None => f.write_str("%")?,
+			}
+			pattern = chars.as_str();
+		}
+		f.write_str(pattern)
+	}
+}
+debug_as_display!([] Strftime<'_>);
+
+#[test]
+fn tests() {
+	let epoch = CivilTime::from_unix(0, 0);
+	let leap = CivilTime::from_unix(951_782_400 + 3723, 5);
+	let before = CivilTime::from_unix(-1, 999_999_999);
+	let offset = CivilTime { offset: -(5 * 3600 + 30 * 60), ..CivilTime::from_unix(1_700_000_000, 0) };
+
+	let table: &[(&CivilTime, &str, &str)] = &[
+		(&epoch, "%Y-%m-%d %H:%M:%S", "1970-01-01 00:00:00"),
+		(&epoch, "%a %b", "Thu Jan"),
+		(&epoch, "%f %z", "000000000 +0000"),
+		(&leap, "%Y", "2000"),
+		(&leap, "%m", "02"),
+		(&leap, "%d", "29"),
+		(&leap, "%H", "01"),
+		(&leap, "%M", "02"),
+		(&leap, "%S", "03"),
+		(&leap, "%f", "000000005"),
+		(&leap, "%a", "Tue"),
+		(&leap, "%b", "Feb"),
+		(&before, "%Y-%m-%dT%H:%M:%S.%f", "1969-12-31T23:59:59.999999999"),
+		(&before, "%a", "Wed"),
+		(&offset, "%Y-%m-%d %H:%M %z", "2023-11-14 22:13 -0530"),
+		(&offset, "%a", "Tue"),
+		(&epoch, "100%% %q %", "100% %q %"),
+		(&epoch, "", ""),
+		(&epoch, "%ü%", "%ü%"),
+	];
+	for &(time, pattern, expected) in table {
+		assert_eq!(strftime(pattern, time).to_string(), expected, "pattern {:?}", pattern);
+	}
+}
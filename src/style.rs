@@ -0,0 +1,344 @@
+/*!
+Colors and text attributes with ANSI escape sequences.
+
+```
+use fmtools::style::{self, Color, Style};
+
+let warning = Style::new().bold().fg(Color::Yellow);
+let s = fmtools::format!({warning.apply("warning")}": "{style::fg256(244, "disk almost full")});
+assert_eq!(s, "\x1b[1;33mwarning\x1b[0m: \x1b[38;5;244mdisk almost full\x1b[0m");
+```
+
+Every styled value starts with the SGR sequence of its style and ends with a reset.
+Styles nest: when a nested style resets, the outer style is emitted again to restore it.
+
+Styling is enabled by default and can be disabled globally with [`set_enabled`], for example when the output is not a terminal.
+When disabled the values are written without any escape sequences.
+
+See [`ansi`](crate::ansi) to pad and measure styled output.
+*/
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Enables or disables styling globally.
+///
+/// ```
+/// use fmtools::style::{self, Color, Style};
+///
+/// style::set_enabled(false);
+/// assert!(!style::is_enabled());
+/// assert_eq!(style::rgb(255, 0, 0, "red").to_string(), "red");
+///
+/// // Only the styles are left out, the value is written as is
+/// let nested = style::fg(Color::Red, fmtools::fmt!("1"{style::bg(Color::Blue, "2")}"3"));
+/// assert_eq!(nested.to_string(), "123");
+/// assert_eq!(style::rgb(1, 2, 3, "\x1b[1mx").to_string(), "\x1b[1mx");
+/// assert_eq!(Style::new().bold().apply(1.5).to_string(), "1.5");
+///
+/// style::set_enabled(true);
+/// assert_eq!(style::fg256(1, "x").to_string(), "\x1b[38;5;1mx\x1b[0m");
+/// ```
+///
+/// The setting is shared by all threads, prefer setting it once at startup.
+#[inline]
+pub fn set_enabled(enabled: bool) {
+	ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns if styling is enabled.
+#[inline]
+pub fn is_enabled() -> bool {
+	ENABLED.load(Ordering::Relaxed)
+}
+
+/// Terminal colors.
+///
+/// The named colors are the 16 colors of the terminal palette, their exact appearance depends on the terminal theme.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Color {
+	Black,
+	Red,
+	Green,
+	Yellow,
+	Blue,
+	Magenta,
+	Cyan,
+	White,
+	BrightBlack,
+	BrightRed,
+	BrightGreen,
+	BrightYellow,
+	BrightBlue,
+	BrightMagenta,
+	BrightCyan,
+	BrightWhite,
+	/// Color of the 256 color palette.
+	Fixed(u8),
+	/// Truecolor.
+	Rgb(u8, u8, u8),
+}
+
+impl Color {
+	// Writes the SGR parameters, the background parameters are offset by 10.
+	fn write_params(self, f: &mut fmt::Formatter, offset: u8) -> fmt::Result {
+		use Color::*;
+		let named = match self {
+			Black => 30, Red => 31, Green => 32, Yellow => 33, Blue => 34, Magenta => 35, Cyan => 36, White => 37,
+			BrightBlack => 90, BrightRed => 91, BrightGreen => 92, BrightYellow => 93,
+			BrightBlue => 94, BrightMagenta => 95, BrightCyan => 96, BrightWhite => 97,
+			Fixed(n) => return write!(f, "{};5;{}", 38 + offset, n),
+			Rgb(r, g, b) => return write!(f, "{};2;{};{};{}", 38 + offset, r, g, b),
+		};
+		write!(f, "{}", named + offset)
+	}
+}
+
+const BOLD: u8 = 1 << 0;
+const DIM: u8 = 1 << 1;
+const ITALIC: u8 = 1 << 2;
+const UNDERLINE: u8 = 1 << 3;
+const REVERSE: u8 = 1 << 4;
+const STRIKETHROUGH: u8 = 1 << 5;
+
+// SGR parameter of every attribute in the order they are written.
+const ATTRIBUTES: [(u8, &str); 6] = [(BOLD, "1"), (DIM, "2"), (ITALIC, "3"), (UNDERLINE, "4"), (REVERSE, "7"), (STRIKETHROUGH, "9")];
+
+/// Colors and text attributes applied to a value.
+///
+/// ```
+/// use fmtools::style::Style;
+///
+/// let style = Style::new().bold().fg_rgb(200, 120, 0).bg256(236);
+/// assert_eq!(style.apply("hot").to_string(), "\x1b[1;38;2;200;120;0;48;5;236mhot\x1b[0m");
+/// ```
+///
+/// Styles are plain values, build them at runtime from configuration and apply them many times.
+/// The plain style writes the value without escape sequences.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Style {
+	fg: Option<Color>,
+	bg: Option<Color>,
+	attributes: u8,
+}
+
+impl Style {
+	/// Returns the plain style.
+	#[inline]
+	pub const fn new() -> Style {
+		Style { fg: None, bg: None, attributes: 0 }
+	}
+	/// Sets the foreground color.
+	#[inline]
+	pub const fn fg(self, color: Color) -> Style {
+		Style { fg: Some(color), ..self }
+	}
+	/// Sets the background color.
+	#[inline]
+	pub const fn bg(self, color: Color) -> Style {
+		Style { bg: Some(color), ..self }
+	}
+	/// Sets the foreground color from the 256 color palette.
+	#[inline]
+	pub const fn fg256(self, n: u8) -> Style {
+		self.fg(Color::Fixed(n))
+	}
+	/// Sets the background color from the 256 color palette.
+	#[inline]
+	pub const fn bg256(self, n: u8) -> Style {
+		self.bg(Color::Fixed(n))
+	}
+	/// Sets the foreground truecolor.
+	#[inline]
+	pub const fn fg_rgb(self, r: u8, g: u8, b: u8) -> Style {
+		self.fg(Color::Rgb(r, g, b))
+	}
+	/// Sets the background truecolor.
+	#[inline]
+	pub const fn bg_rgb(self, r: u8, g: u8, b: u8) -> Style {
+		self.bg(Color::Rgb(r, g, b))
+	}
+	/// Makes the text bold.
+	#[inline]
+	pub const fn bold(self) -> Style {
+		Style { attributes: self.attributes | BOLD, ..self }
+	}
+	/// Makes the text dim.
+	#[inline]
+	pub const fn dim(self) -> Style {
+		Style { attributes: self.attributes | DIM, ..self }
+	}
+	/// Makes the text italic.
+	#[inline]
+	pub const fn italic(self) -> Style {
+		Style { attributes: self.attributes | ITALIC, ..self }
+	}
+	/// Underlines the text.
+	#[inline]
+	pub const fn underline(self) -> Style {
+		Style { attributes: self.attributes | UNDERLINE, ..self }
+	}
+	/// Swaps the foreground and background colors.
+	#[inline]
+	pub const fn reverse(self) -> Style {
+		Style { attributes: self.attributes | REVERSE, ..self }
+	}
+	/// Strikes through the text.
+	#[inline]
+	pub const fn strikethrough(self) -> Style {
+		Style { attributes: self.attributes | STRIKETHROUGH, ..self }
+	}
+	/// Returns if the style has no colors or attributes.
+	#[inline]
+	pub const fn is_plain(&self) -> bool {
+		self.fg.is_none() && self.bg.is_none() && self.attributes == 0
+	}
+	/// Applies the style to the value.
+	#[inline]
+	pub const fn apply<T: fmt::Display>(self, value: T) -> Styled<T> {
+		Styled { value, style: self }
+	}
+
+	// Writes the SGR sequence of the style.
+	fn write_sgr(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("\x1b[")?;
+		let mut sep = "";
+		for (flag, param) in ATTRIBUTES {
+			if self.attributes & flag != 0 {
+				f.write_str(sep)?;
+				f.write_str(param)?;
+				sep = ";";
+			}
+		}
+		if let Some(fg) = self.fg {
+			f.write_str(sep)?;
+			fg.write_params(f, 0)?;
+			sep = ";";
+		}
+		if let Some(bg) = self.bg {
+			f.write_str(sep)?;
+			bg.write_params(f, 10)?;
+		}
+		f.write_str("m")
+	}
+}
+
+/// Styles the value with the foreground color.
+///
+/// ```
+/// use fmtools::style::{self, Color};
+///
+/// let s = style::fg(Color::Red, fmtools::fmt!("error "{style::fg(Color::Cyan, "E042")}" occurred")).to_string();
+/// assert_eq!(s, "\x1b[31merror \x1b[36mE042\x1b[0m\x1b[31m occurred\x1b[0m");
+/// ```
+#[inline]
+pub const fn fg<T: fmt::Display>(color: Color, value: T) -> Styled<T> {
+	Style::new().fg(color).apply(value)
+}
+
+/// Styles the value with the background color.
+#[inline]
+pub const fn bg<T: fmt::Display>(color: Color, value: T) -> Styled<T> {
+	Style::new().bg(color).apply(value)
+}
+
+/// Styles the value with the foreground color from the 256 color palette.
+///
+/// ```
+/// assert_eq!(fmtools::style::fg256(208, "orange").to_string(), "\x1b[38;5;208morange\x1b[0m");
+/// ```
+#[inline]
+pub const fn fg256<T: fmt::Display>(n: u8, value: T) -> Styled<T> {
+	Style::new().fg256(n).apply(value)
+}
+
+/// Styles the value with the background color from the 256 color palette.
+#[inline]
+pub const fn bg256<T: fmt::Display>(n: u8, value: T) -> Styled<T> {
+	Style::new().bg256(n).apply(value)
+}
+
+/// Styles the value with the foreground truecolor.
+///
+/// ```
+/// assert_eq!(fmtools::style::rgb(255, 128, 0, "orange").to_string(), "\x1b[38;2;255;128;0morange\x1b[0m");
+/// ```
+#[inline]
+pub const fn rgb<T: fmt::Display>(r: u8, g: u8, b: u8, value: T) -> Styled<T> {
+	Style::new().fg_rgb(r, g, b).apply(value)
+}
+
+/// Displayable object returned by [`Style::apply`].
+///
+/// The formatting options are ignored, use [`ansi::pad`](crate::ansi::pad) to pad styled output.
+#[derive(Copy, Clone)]
+pub struct Styled<T> {
+	value: T,
+	style: Style,
+}
+impl<T: fmt::Display> fmt::Display for Styled<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		// Restores the style after every reset in the nested output
+		struct RestoreWriter<'a, 'f> {
+			f: &'a mut fmt::Formatter<'f>,
+			style: &'a Style,
+			parser: crate::ansi::Parser,
+		}
+		impl fmt::Write for RestoreWriter<'_, '_> {
+			fn write_str(&mut self, mut s: &str) -> fmt::Result {
+				while let Some((escape, chunk)) = self.parser.split(&mut s) {
+					self.f.write_str(chunk)?;
+					if escape && self.parser.take_reset() {
+						self.style.write_sgr(self.f)?;
+					}
+				}
+				Ok(())
+			}
+		}
+		if self.style.is_plain() || !is_enabled() {
+			return write!(f, "{}", self.value);
+		}
+		self.style.write_sgr(f)?;
+		let mut writer = RestoreWriter { f, style: &self.style, parser: crate::ansi::Parser::new() };
+		fmt::write(&mut writer, format_args!("{}", self.value))?;
+		f.write_str("\x1b[0m")
+	}
+}
+debug_as_display!([T: fmt::Display] Styled<T>);
+
+#[test]
+fn tests() {
+	#[track_caller]
+	fn check(t: impl fmt::Display, s: &str) {
+		assert_eq!(t.to_string(), s);
+	}
+
+	// Exact escape sequences
+	check(fg(Color::Black, "x"), "\x1b[30mx\x1b[0m");
+	check(fg(Color::BrightWhite, "x"), "\x1b[97mx\x1b[0m");
+	check(bg(Color::Blue, "x"), "\x1b[44mx\x1b[0m");
+	check(bg(Color::BrightRed, "x"), "\x1b[101mx\x1b[0m");
+	check(fg256(0, "x"), "\x1b[38;5;0mx\x1b[0m");
+	check(bg256(255, "x"), "\x1b[48;5;255mx\x1b[0m");
+	check(rgb(1, 22, 255, "x"), "\x1b[38;2;1;22;255mx\x1b[0m");
+	check(Style::new().bg_rgb(0, 0, 0).apply(1), "\x1b[48;2;0;0;0m1\x1b[0m");
+	check(Style::new().strikethrough().reverse().underline().italic().dim().bold().apply("x"), "\x1b[1;2;3;4;7;9mx\x1b[0m");
+	check(Style::new().fg(Color::Red).fg256(9).apply("x"), "\x1b[38;5;9mx\x1b[0m");
+	check(Style::new().apply("plain"), "plain");
+	check(Style::default().apply(""), "");
+	check(fg(Color::Green, ""), "\x1b[32m\x1b[0m");
+
+	// Nested styles restore the outer style
+	let inner = Style::new().bold().fg(Color::Cyan);
+	let nested = fg256(208, crate::fmt!("a"{inner.apply("b")}"c"));
+	check(nested, "\x1b[38;5;208ma\x1b[1;36mb\x1b[0m\x1b[38;5;208mc\x1b[0m");
+	let deep = fg(Color::Red, crate::fmt!("1"{bg(Color::Blue, crate::fmt!("2"{fg(Color::Green, "3")}"4"))}"5"));
+	check(deep, "\x1b[31m1\x1b[44m2\x1b[32m3\x1b[0m\x1b[31m\x1b[44m4\x1b[0m\x1b[31m5\x1b[0m");
+	check(fg(Color::Red, crate::fmt!("a\x1b[m"{"b\x1b["}"0m"{"c\x1b[1mc"})), "\x1b[31ma\x1b[m\x1b[31mb\x1b[0m\x1b[31mc\x1b[1mc\x1b[0m");
+	check(fg(Color::Red, "\x1b[0;1mx"), "\x1b[31m\x1b[0;1mx\x1b[0m");
+	assert_eq!(format!("[{:>6}]", fg(Color::Red, 42)), "[\x1b[31m42\x1b[0m]");
+
+	// Disabling is tested in the doctest of set_enabled, the global would race with the other tests
+}
@@ -110,8 +110,133 @@
 /// * 5 x 5 = 25
 /// ```
 ///
+/// ```
+/// struct Node { name: &'static str, next: Option<Box<Node>> }
+/// let list = Node { name: "a", next: Some(Box::new(Node { name: "b", next: None })) };
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	let mut cur = Some(&list);
+/// 	while let Some(node) = cur {
+/// 		"["{node.name}"]"
+/// 		|_| cur = node.next.as_deref();
+/// 	}
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "[a][b]");
+/// ```
+///
+/// The resulting string is `[a][b]`.
+///
+/// ```
+/// let values = [1, 2, 3];
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	"odd: "
+/// 	for val in values.iter().filter(|&val| val % 2 != 0) { {val}" " }
+/// 	else { "(none)" }
+/// 	"| even: "
+/// 	for val in values.iter().filter(|&val| val % 2 == 0) { {val}" " }
+/// 	"| big: "
+/// 	for val in values.iter().filter(|&&val| val > 10) { {val}" " }
+/// 	else { "(none)" }
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "odd: 1 3 | even: 2 | big: (none)");
+/// ```
+///
+/// The resulting string is `odd: 1 3 | even: 2 | big: (none)`.
+///
+/// The `else` block of a `for` loop is formatted only if the loop body did not run.
+///
+/// ```
+/// let rows = [[1, 2], [3, 4]];
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	for row in &rows sep "; " {
+/// 		for (i, val) in (row.iter().enumerate()) sep (" "{i}":") { {val} }
+/// 	}
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "1 1:2; 3 1:4");
+/// ```
+///
+/// The resulting string is `1 1:2; 3 1:4`.
+///
+/// The `sep` of a `for` loop is written between the iterations, not before the first or after the last.
+/// The separator is a string literal, or a template in parentheses when the iterator expression is in parentheses as well.
+/// The separator template is formatted before the body and can use the bindings of the current item.
+/// Use [`with_position`](crate::with_position()) to find the first and last items in the body.
+///
+/// The `while` and `while let` loops are supported as well, the loop state is updated with the escape hatch.
+///
+/// ```
+/// let log = ["boot", "load", "run", "halt"];
+/// let grid = [[1, 2, 3], [4, 5, 6]];
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	for (i, line) in log.iter().enumerate() {
+/// 		if i == 2 { "…" break; }
+/// 		{line}" "
+/// 	}
+/// 	"| "
+/// 	'rows: for row in &grid {
+/// 		for &x in row {
+/// 			if x == 2 { continue; }
+/// 			if x == 5 { break 'rows; }
+/// 			{x}
+/// 		}
+/// 	}
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "boot load …| 134");
+/// ```
+///
+/// The resulting string is `boot load …| 134`.
+///
+/// The `break` and `continue` statements, optionally with a loop label, end the iteration where they are written.
+/// Loops accept labels as in Rust, eg. `'outer: for ...`.
+/// The `sep` of a `for` loop is written before the body, a `continue` in the body still leaves the separator.
+/// Sections of a `glue` are closures, a `break` or `continue` inside them cannot reach a loop around the `glue`.
+///
 /// Control flow really shows the added value of the extended formatting syntax.
 ///
+/// ### Glue
+///
+/// ```
+/// let (city, zip) = ("", Some(12345));
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	glue ", " {
+/// 		("Main St 1")
+/// 		({city})
+/// 		(if let Some(zip) = zip { "zip "{zip} })
+/// 	}
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "Main St 1, zip 12345");
+/// ```
+///
+/// The resulting string is `Main St 1, zip 12345`.
+///
+/// The `glue` construct writes the separator only between parenthesized sections which produce output, see [`glue`](crate::glue()).
+///
+/// Every section is rendered by its own closure, the sections can read the variables of the template but not mutate them.
+/// An escape hatch like `|_| count += 1;` does not compile inside a section and `break` or `continue` cannot reach a loop around the `glue`.
+///
+/// ```compile_fail
+/// let _ = fmtools::fmt! {
+/// 	let mut count = 0;
+/// 	glue ", " {
+/// 		("a" |_| count += 1;)
+/// 	}
+/// };
+/// ```
+///
 /// ### Capture by value
 ///
 /// ```
@@ -146,19 +271,190 @@
 ///
 /// Closure syntax provides an escape hatch to inject code if needed.
 /// The argument's type is [`&mut Formatter`](std::fmt::Formatter).
+///
+/// ```
+/// let words = ["alpha", "beta", "gamma"];
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	let mut col = 0;
+/// 	for word in &words {
+/// 		|f| if col > 0 { f.write_str(" ")?; };
+/// 		{word}
+/// 		|_| col += word.len() + 1;
+/// 		if col > 8 { "\n" |_| col = 0; }
+/// 	}
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "alpha beta\ngamma");
+/// ```
+///
+/// The resulting string is `alpha beta\ngamma`.
+///
+/// The escape hatches are expanded in place as statements of the template, not as separate closures.
+/// State declared with `let mut` earlier in the template can be mutated by any number of later escape hatches and read by the holes in between.
+/// The state is declared anew every time the object is displayed.
+/// The displayable object only borrows the variables outside the template immutably, mutate those through a [`Cell`](core::cell::Cell) or similar.
+/// The sections of a `glue` are closures and cannot mutate the state of the template either, see [Glue](#glue).
+///
+/// Pass the formatter on to helper functions taking `&mut Formatter`, they can in turn delegate to other displayable objects with [`fmt_into`](crate::fmt_into):
+///
+/// ```
+/// use std::fmt;
+///
+/// fn row(f: &mut fmt::Formatter, key: &str, value: u32) -> fmt::Result {
+/// 	fmtools::fmt_into(f, fmtools::fmt!({key:<6}{value:>4}"\n"))
+/// }
+///
+/// # let s =
+/// fmtools::fmt! {
+/// 	|f| row(f, "width", 80)?;
+/// 	|f| row(f, "height", 24)?;
+/// }
+/// # .to_string();
+/// # assert_eq!(s, "width   80\nheight  24\n");
+/// ```
+///
+/// ### Send + Sync
+///
+/// ```
+/// let a = 42;
+/// let s = fmtools::fmt!(send move "a = "{a});
+/// # let s =
+/// std::thread::spawn(move || s.to_string()).join().unwrap()
+/// # ;
+/// # assert_eq!(s, "a = 42");
+/// ```
+///
+/// The resulting string is `a = 42`.
+///
+/// The `send` modifier checks that the displayable object is `Send + Sync` where it is defined, see [`fmt_send`](crate::fmt_send()).
+///
+/// ```compile_fail
+/// let a = std::rc::Rc::new(42);
+/// let _ = fmtools::fmt!(send move "a = "{a});
+/// ```
+///
+/// ### Type erasure
+///
+/// ```
+/// let a = 42;
+/// let s = fmtools::fmt!(dyn "a = "{a}).to_string();
+/// # assert_eq!(s, "a = 42");
+/// ```
+///
+/// The resulting string is `a = 42`.
+///
+/// The `dyn` modifier creates the displayable object through [`fmt_dyn`](crate::fmt_dyn()), sharing a single instantiation between all call sites.
+/// The displayable object can only be used within the statement it is created in.
+///
+/// ### Debug mode
+///
+/// ```
+/// let (a, b) = ("x", Some(1));
+///
+/// # let s =
+/// fmtools::fmt!(? "state "{a}" / "{b}" / "{a:}" / "{b:#?})
+/// # .to_string();
+/// # assert_eq!(s, "state \"x\" / Some(1) / x / Some(\n    1,\n)");
+/// ```
+///
+/// The resulting string is `state "x" / Some(1) / x / Some(\n    1,\n)`.
+///
+/// The `?` modifier formats the holes without specifiers with `Debug` instead of `Display`, including inside control flow.
+/// Explicit specifiers still apply, use an empty specifier `{x:}` to force `Display`.
+/// The modifier goes after the other modifiers, eg. `fmt!(move ? ...)`, and is also accepted by the replacement macros in the prelude.
+///
+/// ### HTML mode
+///
+/// ```
+/// let name = "<b>bob</b>";
+/// let badge = "<i>admin</i>";
+///
+/// # let s =
+/// fmtools::fmt!(html "<li title=\""{name}"\">"{name:>12}" "{badge:raw}"</li>")
+/// # .to_string();
+/// # assert_eq!(s, "<li title=\"&lt;b&gt;bob&lt;/b&gt;\">  &lt;b&gt;bob&lt;/b&gt; <i>admin</i></li>");
+/// ```
+///
+/// The resulting string is `<li title="&lt;b&gt;bob&lt;/b&gt;">  &lt;b&gt;bob&lt;/b&gt; <i>admin</i></li>`.
+///
+/// The `html` modifier escapes the output of every hole with [`escape_html`](crate::escape_html()), including inside control flow.
+/// String literals are trusted and written as is.
+/// Use the `raw` specifier `{frag:raw}` to write trusted or already escaped fragments without escaping,
+/// or mark the value itself with [`raw`](crate::raw()).
+///
+/// ### JSON mode
+///
+/// ```
+/// let msg = "say \"hi\"\n";
+/// let (n, ok) = (42, true);
+/// let path = std::path::Path::new("a/b");
+///
+/// # let s =
+/// fmtools::fmt!(json "{\"msg\":"{msg}",\"n\":"{n}",\"ok\":"{ok}",\"path\":"{path.display():s}",\"hex\":"{n:#x}",\"list\":"{"[1,2]":raw}"}")
+/// # .to_string();
+/// # assert_eq!(s, r#"{"msg":"say \"hi\"\n","n":42,"ok":true,"path":"a/b","hex":"0x2a","list":[1,2]}"#);
+/// ```
+///
+/// The resulting string is `{"msg":"say \"hi\"\n","n":42,"ok":true,"path":"a/b","hex":"0x2a","list":[1,2]}`.
+///
+/// The `json` modifier writes every hole as a JSON value with [`JsonValue`](crate::JsonValue), including inside control flow.
+/// String literals are written as is.
+///
+/// * `{expr}` writes the value as JSON: strings are quoted and escaped, numbers and booleans are bare, `None` is `null`.
+/// * `{expr:s}` writes any displayable object as a JSON string.
+/// * `{expr:raw}` writes already serialized JSON fragments as is, as do values marked with [`raw`](crate::raw()).
+/// * Any other specifier formats the value and writes the result as a JSON string.
+///
+/// ### Default specifier
+///
+/// ```
+/// let (base, end) = (0x1000, 0x1f00);
+///
+/// # let s =
+/// fmtools::fmt!(default ":#010x"; {base}"-"{end}" len "{end - base:}" "{end - base:#x})
+/// # .to_string();
+/// # assert_eq!(s, "0x00001000-0x00001f00 len 3840 0xf00");
+/// ```
+///
+/// The resulting string is `0x00001000-0x00001f00 len 3840 0xf00`.
+///
+/// The `default` modifier applies the specifier to the holes without specifiers, including inside control flow.
+/// The specifier is a string literal starting with `:`.
+/// Explicit specifiers override it, use an empty specifier `{x:}` to opt back to `Display`.
+/// Like the `?` modifier it goes after the other modifiers: `fmt!(move default ":x"; ...)`.
 #[macro_export]
 macro_rules! fmt {
+	(dyn $($tt:tt)*) => {
+		$crate::fmt_dyn(&|_f| $crate::__fmt_options(_f, |_f| {
+			$crate::__fmt!{_f $($tt)*}
+			Ok(())
+		}))
+	};
+	(send move $($tt:tt)*) => {
+		$crate::fmt_send(move |_f| $crate::__fmt_options(_f, |_f| {
+			$crate::__fmt!{_f $($tt)*}
+			Ok(())
+		}))
+	};
+	(send $($tt:tt)*) => {
+		$crate::fmt_send(|_f| $crate::__fmt_options(_f, |_f| {
+			$crate::__fmt!{_f $($tt)*}
+			Ok(())
+		}))
+	};
 	(move $($tt:tt)*) => {
-		$crate::fmt(move |_f| {
+		$crate::fmt(move |_f| $crate::__fmt_options(_f, |_f| {
 			$crate::__fmt!{_f $($tt)*}
 			Ok(())
-		})
+		}))
 	};
 	($($tt:tt)*) => {
-		$crate::fmt(|_f| {
+		$crate::fmt(|_f| $crate::__fmt_options(_f, |_f| {
 			$crate::__fmt!{_f $($tt)*}
 			Ok(())
-		})
+		}))
 	};
 }
 
@@ -166,105 +462,345 @@ macro_rules! fmt {
 #[doc(hidden)]
 macro_rules! __fmt {
 	// text
-	($f:ident $text1:literal $text2:literal $($tail:tt)*) => {
-		$crate::__fmt!{$f @concat($text1, $text2) $($tail)*}
+	($f:ident [$($m:tt)*] $text1:literal $text2:literal $($tail:tt)*) => {
+		$crate::__fmt!{$f [$($m)*] @concat($text1, $text2) $($tail)*}
 	};
-	($f:ident $text:literal $($tail:tt)*) => {
-		$f.write_str($crate::obfstr!(concat!($text)))?;
-		$crate::__fmt!{$f $($tail)*}
+	($f:ident [$($m:tt)*] $text:literal $($tail:tt)*) => {
+		$crate::__fmt_text!{$f $text}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
-	($f:ident @concat($($texts:literal),+) $text:literal $($tail:tt)*) => {
-		$crate::__fmt!{$f @concat($($texts,)+ $text) $($tail)*}
+	($f:ident [$($m:tt)*] @concat($($texts:literal),+) $text:literal $($tail:tt)*) => {
+		$crate::__fmt!{$f [$($m)*] @concat($($texts,)+ $text) $($tail)*}
 	};
-	($f:ident @concat($($texts:literal),+) $($tail:tt)*) => {
-		$f.write_str($crate::obfstr!(concat!($($texts),+)))?;
-		$crate::__fmt!{$f $($tail)*}
+	($f:ident [$($m:tt)*] @concat($($texts:literal),+) $($tail:tt)*) => {
+		$crate::__fmt_text!{$f $($texts),+}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
 
 	// format
-	($f:ident {$($e:tt)*} $($tail:tt)*) => {
-		$f.write_fmt($crate::__fmt_format!([] $($e)*))?;
-		$crate::__fmt!{$f $($tail)*}
+	($f:ident [$($m:tt)*] {$($e:tt)*} $($tail:tt)*) => {
+		$crate::__fmt_trace!(::core::stringify!({$($e)*}); $f.write_fmt($crate::__fmt_format!([$($m)*] [] $($e)*)));
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
 
 	// escape hatch
-	($f:ident |$ff:pat_param| $block:block $($tail:tt)*) => {
+	($f:ident [$($m:tt)*] |$ff:pat_param| $block:block $($tail:tt)*) => {
 		let $ff = &mut *$f;
 		$block
-		$crate::__fmt!{$f $($tail)*}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
-	($f:ident |$ff:pat_param| $stmt:stmt; $($tail:tt)*) => {
+	($f:ident [$($m:tt)*] |$ff:pat_param| $stmt:stmt; $($tail:tt)*) => {
 		let $ff = &mut *$f;
 		$stmt
-		$crate::__fmt!{$f $($tail)*}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
 
 	// let
-	($f:ident let $p:pat = $e:expr; $($tail:tt)*) => {
+	($f:ident [$($m:tt)*] let $p:pat = $e:expr; $($tail:tt)*) => {
 		let $p = $e;
-		$crate::__fmt!{$f $($tail)*}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
 
 	// if
-	($f:ident if $($tail:tt)*) => {
-		$crate::__fmt_if!{$f [] if $($tail)*}
+	($f:ident [$($m:tt)*] if $($tail:tt)*) => {
+		$crate::__fmt_if!{$f [$($m)*] [] if $($tail)*}
 	};
 
 	// match
-	($f:ident match ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
-		$crate::__fmt_match!{$f match ($e) {} $($body)*}
-		$crate::__fmt!{$f $($tail)*}
+	($f:ident [$($m:tt)*] match ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
+		$crate::__fmt_match!{$f [$($m)*] match ($e) {} $($body)*}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
+	};
+	($f:ident [$($m:tt)*] match $($tail:tt)*) => {
+		$crate::__with_parens!{__fmt! [$f [$($m)*] match] () $($tail)*}
 	};
-	($f:ident match $($tail:tt)*) => {
-		$crate::__with_parens!{__fmt! [$f match] () $($tail)*}
+
+	// for sep
+	($f:ident [$($m:tt)*] $($l:lifetime:)? for $p:pat in ($e:expr) sep $sep:literal $($tail:tt)*) => {
+		$crate::__fmt!{$f [$($m)*] $($l:)? for $p in ($e) sep ($sep) $($tail)*}
+	};
+	($f:ident [$($m:tt)*] $($l:lifetime:)? for $p:pat in ($e:expr) sep ($($sep:tt)*) { $($body:tt)* } else { $($else:tt)* } $($tail:tt)*) => {
+		let mut first = true;
+		$($l:)? for $p in $e {
+			if !first {
+				$crate::__fmt!{$f [$($m)*] $($sep)*}
+			}
+			first = false;
+			$crate::__fmt!{$f [$($m)*] $($body)*}
+		}
+		if first {
+			$crate::__fmt!{$f [$($m)*] $($else)*}
+		}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
+	};
+	($f:ident [$($m:tt)*] $($l:lifetime:)? for $p:pat in ($e:expr) sep ($($sep:tt)*) { $($body:tt)* } $($tail:tt)*) => {
+		let mut first = true;
+		$($l:)? for $p in $e {
+			if !first {
+				$crate::__fmt!{$f [$($m)*] $($sep)*}
+			}
+			first = false;
+			$crate::__fmt!{$f [$($m)*] $($body)*}
+		}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
+	};
+
+	// for else
+	($f:ident [$($m:tt)*] $($l:lifetime:)? for $p:pat in ($e:expr) { $($body:tt)* } else { $($else:tt)* } $($tail:tt)*) => {
+		let mut empty = true;
+		$($l:)? for $p in $e {
+			empty = false;
+			$crate::__fmt!{$f [$($m)*] $($body)*}
+		}
+		if empty {
+			$crate::__fmt!{$f [$($m)*] $($else)*}
+		}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
 
 	// for
-	($f:ident for $p:pat in ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
-		for $p in $e {
-			$crate::__fmt!{$f $($body)*}
+	($f:ident [$($m:tt)*] $($l:lifetime:)? for $p:pat in ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
+		$($l:)? for $p in $e {
+			$crate::__fmt!{$f [$($m)*] $($body)*}
 		}
-		$crate::__fmt!{$f $($tail)*}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
-	($f:ident for $p:pat in $($tail:tt)*) => {
-		$crate::__with_parens!{__fmt! [$f for $p in] () $($tail)*}
+	($f:ident [$($m:tt)*] $($l:lifetime:)? for $p:pat in $($tail:tt)*) => {
+		$crate::__with_parens!{__fmt! [$f [$($m)*] $($l:)? for $p in] () $($tail)*}
+	};
+
+	// while let
+	($f:ident [$($m:tt)*] $($l:lifetime:)? while let $p:pat = ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
+		$($l:)? while let $p = $e {
+			$crate::__fmt!{$f [$($m)*] $($body)*}
+		}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
+	};
+	($f:ident [$($m:tt)*] $($l:lifetime:)? while let $p:pat = $($tail:tt)*) => {
+		$crate::__with_parens!{__fmt! [$f [$($m)*] $($l:)? while let $p =] () $($tail)*}
+	};
+
+	// while
+	($f:ident [$($m:tt)*] $($l:lifetime:)? while ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
+		$($l:)? while $e {
+			$crate::__fmt!{$f [$($m)*] $($body)*}
+		}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
+	};
+	($f:ident [$($m:tt)*] $($l:lifetime:)? while $($tail:tt)*) => {
+		$crate::__with_parens!{__fmt! [$f [$($m)*] $($l:)? while] () $($tail)*}
+	};
+
+	// break and continue
+	($f:ident [$($m:tt)*] break $($l:lifetime)?; $($tail:tt)*) => {
+		break $($l)?;
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
+	};
+	($f:ident [$($m:tt)*] continue $($l:lifetime)?; $($tail:tt)*) => {
+		continue $($l)?;
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
+	};
+
+	// glue
+	($f:ident [$($m:tt)*] glue $sep:literal { $($body:tt)* } $($tail:tt)*) => {
+		$crate::__fmt_glue!{$f [$($m)*] $sep $($body)*}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
 
 	// optimization
-	($f:ident ($($tt:tt)*) $($tail:tt)*) => {
-		$crate::__fmt!{$f $($tt)*}
-		$crate::__fmt!{$f $($tail)*}
+	($f:ident [$($m:tt)*] ($($tt:tt)*) $($tail:tt)*) => {
+		$crate::__fmt!{$f [$($m)*] $($tt)*}
+		$crate::__fmt!{$f [$($m)*] $($tail)*}
 	};
 
 	// term
-	($f:ident) => {};
+	($f:ident [$($m:tt)*]) => {};
+	($f:ident [$($m:tt)*] $($tail:tt)*) => {
+		compile_error!(concat!("unexpected tokens in template: ", stringify!($($tail)*)));
+	};
+
+	// entry: the template modifiers are threaded through as the mode
+	($f:ident ? $($tail:tt)*) => {
+		$crate::__fmt!{$f [?] $($tail)*}
+	};
+	($f:ident html $($tail:tt)*) => {
+		$crate::__fmt!{$f [html] $($tail)*}
+	};
+	($f:ident json $($tail:tt)*) => {
+		$crate::__fmt!{$f [json] $($tail)*}
+	};
+	($f:ident default $spec:literal; $($tail:tt)*) => {
+		$crate::__fmt!{$f [default $spec] $($tail)*}
+	};
+	($f:ident $($tail:tt)*) => {
+		$crate::__fmt!{$f [] $($tail)*}
+	};
+}
+
+// Length of the unconditional literal text of the template, see `SizeHint`.
+// Literals inside control flow, escape hatches and let statements are skipped as they may not be written.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fmt_len {
+	// skip until the end of the statement
+	(@stmt [$($texts:literal)*] ; $($tail:tt)*) => {
+		$crate::__fmt_len!{[$($texts)*] $($tail)*}
+	};
+	(@stmt [$($texts:literal)*] $nom:tt $($tail:tt)*) => {
+		$crate::__fmt_len!{@stmt [$($texts)*] $($tail)*}
+	};
+	// skip until the end of the block
+	(@block [$($texts:literal)*] {$($body:tt)*} $($tail:tt)*) => {
+		$crate::__fmt_len!{[$($texts)*] $($tail)*}
+	};
+	(@block [$($texts:literal)*] $nom:tt $($tail:tt)*) => {
+		$crate::__fmt_len!{@block [$($texts)*] $($tail)*}
+	};
+	// skip the escape hatch block or statement
+	(@hatch [$($texts:literal)*] {$($body:tt)*} $($tail:tt)*) => {
+		$crate::__fmt_len!{[$($texts)*] $($tail)*}
+	};
+	(@hatch [$($texts:literal)*] $($tail:tt)*) => {
+		$crate::__fmt_len!{@stmt [$($texts)*] $($tail)*}
+	};
+	(@$state:ident [$($texts:literal)*]) => {
+		::core::concat!($($texts),*).len()
+	};
+
+	([$($texts:literal)*] let $($tail:tt)*) => {
+		$crate::__fmt_len!{@stmt [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] default $($tail:tt)*) => {
+		$crate::__fmt_len!{@stmt [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] |$ff:pat_param| $($tail:tt)*) => {
+		$crate::__fmt_len!{@hatch [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] if $($tail:tt)*) => {
+		$crate::__fmt_len!{@block [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] else $($tail:tt)*) => {
+		$crate::__fmt_len!{@block [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] match $($tail:tt)*) => {
+		$crate::__fmt_len!{@block [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] for $($tail:tt)*) => {
+		$crate::__fmt_len!{@block [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] while $($tail:tt)*) => {
+		$crate::__fmt_len!{@block [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] glue $($tail:tt)*) => {
+		$crate::__fmt_len!{@block [$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*] ($($tt:tt)*) $($tail:tt)*) => {
+		($crate::__fmt_len!{[] $($tt)*} + $crate::__fmt_len!{[$($texts)*] $($tail)*})
+	};
+	([$($texts:literal)*] $text:literal $($tail:tt)*) => {
+		$crate::__fmt_len!{[$($texts)* $text] $($tail)*}
+	};
+	([$($texts:literal)*] $nom:tt $($tail:tt)*) => {
+		$crate::__fmt_len!{[$($texts)*] $($tail)*}
+	};
+	([$($texts:literal)*]) => {
+		::core::concat!($($texts),*).len()
+	};
+}
+
+// Write the concatenated literals.
+// Single byte texts use write_char which skips the str handling in some writers.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fmt_text {
+	($f:ident $($texts:literal),+) => {{
+		const TEXT: &str = concat!($($texts),+);
+		if TEXT.len() == 1 && !$crate::__OBFSTR {
+			$crate::__fmt_trace!(::core::stringify!($($texts)+); ::core::fmt::Write::write_char($f, TEXT.as_bytes()[0] as char));
+		}
+		else {
+			$crate::__fmt_trace!(::core::stringify!($($texts)+); $f.write_str($crate::obfstr!(TEXT)));
+		}
+	}};
 }
 
+// Propagate the error of a write in the template.
+// With the `trace-errors` feature the failing segment is recorded, see `last_format_error`.
+#[cfg(not(feature = "trace-errors"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fmt_trace {
+	($segment:expr; $write:expr) => {
+		$write?
+	};
+}
+#[cfg(feature = "trace-errors")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fmt_trace {
+	($segment:expr; $write:expr) => {
+		if let ::core::result::Result::Err(err) = $write {
+			$crate::__trace_error(::core::concat!(::core::file!(), ":", ::core::line!(), ":", ::core::column!()), $segment);
+			return ::core::result::Result::Err(err);
+		}
+	};
+}
 
 // Parse the formatting inside formatting braces.
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __fmt_format {
-	([$($e:tt)*] : $($tail:tt)*) => {
-		$crate::__fmt_expr!([$($e)*] : $($tail)*)
+	($m:tt [$($e:tt)*] : $($tail:tt)*) => {
+		$crate::__fmt_expr!($m [$($e)*] : $($tail)*)
 	};
-	([$($e:tt)*] ; $($tail:tt)*) => {
-		$crate::__fmt_expr!([$($e)*] : $($tail)*)
+	($m:tt [$($e:tt)*] ; $($tail:tt)*) => {
+		$crate::__fmt_expr!($m [$($e)*] : $($tail)*)
 	};
-	([$($e:tt)*] $nom:tt $($tail:tt)*) => {
-		$crate::__fmt_format!([$($e)* $nom] $($tail)*)
+	($m:tt [$($e:tt)*] $nom:tt $($tail:tt)*) => {
+		$crate::__fmt_format!($m [$($e)* $nom] $($tail)*)
 	};
-	([$($e:tt)*]) => {
-		$crate::__fmt_expr!([$($e)*])
+	($m:tt [$($e:tt)*]) => {
+		$crate::__fmt_expr!($m [$($e)*])
 	};
 }
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __fmt_expr {
-	([$e:expr]) => {
+	([?] [$e:expr]) => {
+		::core::format_args!("{:?}", $e)
+	};
+	([default $spec:literal] [$e:expr]) => {
+		::core::format_args!(concat!("{", $spec, "}"), $e)
+	};
+	([html] [$e:expr]) => {
+		::core::format_args!("{}", match &$e { __e => {
+			#[allow(unused_imports)]
+			use $crate::{__HtmlRaw as _, __HtmlEscape as _};
+			__e.__html()
+		} })
+	};
+	([html] [$e:expr] : raw) => {
+		::core::format_args!("{}", $e)
+	};
+	([html] [$e:expr $(, $w:expr)?] $($s:tt)*) => {
+		::core::format_args!("{}", $crate::escape_html(::core::format_args!(concat!("{", $(::core::stringify!($s),)* "}"), $e $(,$w)?)))
+	};
+	([json] [$e:expr]) => {
+		::core::format_args!("{}", $crate::json(&$e))
+	};
+	([json] [$e:expr] : raw) => {
 		::core::format_args!("{}", $e)
 	};
-	([$e:expr $(, $w:expr)?] $($s:tt)*) => {
+	([json] [$e:expr] : s) => {
+		::core::format_args!("{}", $crate::json_string(&$e))
+	};
+	([json] [$e:expr $(, $w:expr)?] $($s:tt)*) => {
+		::core::format_args!("{}", $crate::json_string(::core::format_args!(concat!("{", $(::core::stringify!($s),)* "}"), $e $(,$w)?)))
+	};
+	([$($m:tt)*] [$e:expr]) => {
+		::core::format_args!("{}", $e)
+	};
+	($m:tt [$e:expr $(, $w:expr)?] $($s:tt)*) => {
 		::core::format_args!(concat!("{", $(::core::stringify!($s),)* "}"), $e $(,$w)?)
 	};
 }
@@ -273,50 +809,66 @@ macro_rules! __fmt_expr {
 #[macro_export]
 macro_rules! __fmt_if {
 	// if let
-	($f:ident [$($c:tt)*] if let $p:pat = ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
-		$crate::__fmt_if!{$f [$($c)* if let $p = $e { $crate::__fmt!{$f $($body)*} }] $($tail)*}
+	($f:ident $m:tt [$($c:tt)*] if let $p:pat = ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
+		$crate::__fmt_if!{$f $m [$($c)* if let $p = $e { $crate::__fmt!{$f $m $($body)*} }] $($tail)*}
 	};
-	($f:ident [$($c:tt)*] if let $p:pat = $($tail:tt)*) => {
-		$crate::__with_parens!{__fmt_if! [$f [$($c)*] if let $p =] () $($tail)*}
+	($f:ident $m:tt [$($c:tt)*] if let $p:pat = $($tail:tt)*) => {
+		$crate::__with_parens!{__fmt_if! [$f $m [$($c)*] if let $p =] () $($tail)*}
 	};
 
 	// if
-	($f:ident [$($c:tt)*] if ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
-		$crate::__fmt_if!{$f [$($c)* if $e { $crate::__fmt!{$f $($body)*} }] $($tail)*}
+	($f:ident $m:tt [$($c:tt)*] if ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
+		$crate::__fmt_if!{$f $m [$($c)* if $e { $crate::__fmt!{$f $m $($body)*} }] $($tail)*}
 	};
-	($f:ident [$($c:tt)*] if $($tail:tt)*) => {
-		$crate::__with_parens!{__fmt_if! [$f [$($c)*] if] () $($tail)*}
+	($f:ident $m:tt [$($c:tt)*] if $($tail:tt)*) => {
+		$crate::__with_parens!{__fmt_if! [$f $m [$($c)*] if] () $($tail)*}
 	};
 
 	// else if let
-	($f:ident [$($c:tt)*] else if let $p:pat = ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
-		$crate::__fmt_if!{$f [$($c)* else if let $p = $e { $crate::__fmt!{$f $($body)*} }] $($tail)*}
+	($f:ident $m:tt [$($c:tt)*] else if let $p:pat = ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
+		$crate::__fmt_if!{$f $m [$($c)* else if let $p = $e { $crate::__fmt!{$f $m $($body)*} }] $($tail)*}
 	};
-	($f:ident [$($c:tt)*] else if let $p:pat = $($tail:tt)*) => {
-		$crate::__with_parens!{__fmt_if! [$f [$($c)*] else if let $p =] () $($tail)*}
+	($f:ident $m:tt [$($c:tt)*] else if let $p:pat = $($tail:tt)*) => {
+		$crate::__with_parens!{__fmt_if! [$f $m [$($c)*] else if let $p =] () $($tail)*}
 	};
 
 	// else if
-	($f:ident [$($c:tt)*] else if ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
-		$crate::__fmt_if!{$f [$($c)* else if $e { $crate::__fmt!{$f $($body)*} }] $($tail)*}
+	($f:ident $m:tt [$($c:tt)*] else if ($e:expr) { $($body:tt)* } $($tail:tt)*) => {
+		$crate::__fmt_if!{$f $m [$($c)* else if $e { $crate::__fmt!{$f $m $($body)*} }] $($tail)*}
 	};
-	($f:ident [$($c:tt)*] else if $($tail:tt)*) => {
-		$crate::__with_parens!{__fmt_if! [$f [$($c)*] else if] () $($tail)*}
+	($f:ident $m:tt [$($c:tt)*] else if $($tail:tt)*) => {
+		$crate::__with_parens!{__fmt_if! [$f $m [$($c)*] else if] () $($tail)*}
 	};
 
 	// else
-	($f:ident [$($c:tt)*] else { $($body:tt)* } $($tail:tt)*) => {
+	($f:ident $m:tt [$($c:tt)*] else { $($body:tt)* } $($tail:tt)*) => {
 		$($c)*
 		else {
-			$crate::__fmt!{$f $($body)*}
+			$crate::__fmt!{$f $m $($body)*}
 		}
-		$crate::__fmt!{$f $($tail)*}
+		$crate::__fmt!{$f $m $($tail)*}
 	};
 
 	// term
-	($f:ident [$($c:tt)*] $($tail:tt)*) => {
+	($f:ident $m:tt [$($c:tt)*] $($tail:tt)*) => {
 		$($c)*
-		$crate::__fmt!{$f $($tail)*}
+		$crate::__fmt!{$f $m $($tail)*}
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fmt_glue {
+	($f:ident $m:tt $sep:literal $(($($section:tt)*))*) => {
+		$crate::__fmt_trace!(::core::stringify!(glue $sep); $f.write_fmt(::core::format_args!("{}", $crate::glue($sep, &[$(
+			&$crate::fmt(|$f| {
+				$crate::__fmt!{$f $m $($section)*}
+				Ok(())
+			}) as &dyn ::core::fmt::Display
+		),*]))));
+	};
+	($f:ident $m:tt $sep:literal $($tail:tt)*) => {
+		compile_error!(concat!("expected parenthesized sections in glue: ", stringify!($($tail)*)));
 	};
 }
 
@@ -326,6 +878,10 @@ macro_rules! __with_parens {
 	($next:ident! [$($prefix:tt)*] ($($tt:tt)*) { $($body:tt)* } $($tail:tt)*) => {
 		$crate::$next!{$($prefix)* ($($tt)*) { $($body)* } $($tail)*}
 	};
+	// Separator of for loops
+	($next:ident! [$($prefix:tt)*] ($($tt:tt)+) sep $sep:literal { $($body:tt)* } $($tail:tt)*) => {
+		$crate::$next!{$($prefix)* ($($tt)*) sep $sep { $($body)* } $($tail)*}
+	};
 	($next:ident! [$($prefix:tt)*] ($($tt:tt)*) $nom:tt $($tail:tt)*) => {
 		$crate::__with_parens!{$next! [$($prefix)*] ($($tt)* $nom) $($tail)*}
 	};
@@ -339,16 +895,16 @@ macro_rules! __with_parens {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __fmt_match {
-	($f:ident match ($e:expr) {$($arms:tt)*} $pat:pat $(if $guard:expr)? => { $($body:tt)* }, $($tail:tt)*) => {
-		$crate::__fmt_match!{$f match ($e) {$($arms)* $pat $(if $guard)? => { $crate::__fmt!{$f $($body)*} }} $($tail)*}
+	($f:ident $m:tt match ($e:expr) {$($arms:tt)*} $pat:pat $(if $guard:expr)? => { $($body:tt)* }, $($tail:tt)*) => {
+		$crate::__fmt_match!{$f $m match ($e) {$($arms)* $pat $(if $guard)? => { $crate::__fmt!{$f $m $($body)*} }} $($tail)*}
 	};
-	($f:ident match ($e:expr) {$($arms:tt)*} $pat:pat $(if $guard:expr)? => { $($body:tt)* } $($tail:tt)*) => {
-		$crate::__fmt_match!{$f match ($e) {$($arms)* $pat $(if $guard)? => { $crate::__fmt!{$f $($body)*} }} $($tail)*}
+	($f:ident $m:tt match ($e:expr) {$($arms:tt)*} $pat:pat $(if $guard:expr)? => { $($body:tt)* } $($tail:tt)*) => {
+		$crate::__fmt_match!{$f $m match ($e) {$($arms)* $pat $(if $guard)? => { $crate::__fmt!{$f $m $($body)*} }} $($tail)*}
 	};
-	($f:ident match ($e:expr) {$($arms:tt)*} $pat:pat $(if $guard:expr)? => $($tail:tt)*) => {
-		$crate::__until_comma!{__fmt_match! [$f match ($e) {$($arms)*} $pat $(if $guard)? =>] {} $($tail)*}
+	($f:ident $m:tt match ($e:expr) {$($arms:tt)*} $pat:pat $(if $guard:expr)? => $($tail:tt)*) => {
+		$crate::__until_comma!{__fmt_match! [$f $m match ($e) {$($arms)*} $pat $(if $guard)? =>] {} $($tail)*}
 	};
-	($f:ident match ($e:expr) {$($pat:pat $(if $guard:expr)? => $block:block)*}) => {
+	($f:ident $m:tt match ($e:expr) {$($pat:pat $(if $guard:expr)? => $block:block)*}) => {
 		match $e {
 			$($pat $(if $guard)? => $block)*
 		}
@@ -390,10 +946,66 @@ fn tests() {
 	check(fmt!(|_| let name = "world"; |f| f.write_str("Hello ")?; |f| f.write_str(name)?;), "Hello world");
 	check(fmt!(|_| let name = "world"; |f| { f.write_str("Hello ")?; f.write_str(name)?; }), "Hello world");
 
+	// Shared state between escape hatches
+	let words = ["alpha", "beta", "gamma"];
+	let columns = fmt!(
+		let mut col = 0;
+		let mut total = 0;
+		for word in &words {
+			|f| { if col > 0 { f.write_str(" ")?; } col += word.len() + 1; }
+			{word}
+			"@"{col}
+			|_| total += col;
+			if col > 8 { "\n" |_| col = 0; }
+		}
+		"total="{total}
+	);
+	check(columns, "alpha@6 beta@11\ngamma@6total=23");
+	check(columns, "alpha@6 beta@11\ngamma@6total=23");
+	let outside = core::cell::Cell::new(0);
+	let counted = fmt!(|_| outside.set(outside.get() + 1); "n="{outside.get()} |_| outside.set(outside.get() * 10););
+	check(counted, "n=1");
+	check(counted, "n=11");
+
 	// Move ownership
 	check(fmt!("{"{fmt!("a = "{42})}"}"), "{a = 42}");
 	check(fmt!("{"{{let a = 42; fmt!(move "a = "{a})}}"}"), "{a = 42}");
 
+	// Send + Sync
+	fn assert_send_sync<T: Send + Sync>(t: T) -> T { t }
+	let a = String::from("send");
+	let s = assert_send_sync(fmt!(send move "a = "{a}));
+	check(std::thread::spawn(move || s.to_string()).join().unwrap(), "a = send");
+	check(assert_send_sync(fmt!(send "b = "{42})), "b = 42");
+
+	// Type erasure
+	let a = 42;
+	check(fmt!(dyn "a = "{a}), "a = 42");
+	assert_eq!(format!("{:?}", fmt!(dyn "a = "{a})), format!("{:?}", fmt!("a = "{a})));
+	assert_eq!(crate::fmt_dyn(&|f| f.write_str("dyn")).to_string(), "dyn");
+	let boxed = crate::fmt_boxed(move |f| write!(f, "a = {}", a));
+	check(&boxed, "a = 42");
+	check(boxed, "a = 42");
+
+	// Named holder
+	struct Holder<F: Fn(&mut std::fmt::Formatter) -> std::fmt::Result> {
+		value: crate::FmtFn<F>,
+	}
+	let holder = Holder { value: fmt!(move "a = "{a}) };
+	let copy = holder.value;
+	check(copy, "a = 42");
+	let mut s = String::new();
+	holder.value.fmt_to(&mut s).unwrap();
+	assert_eq!(s, "a = 42");
+
+	// Writer adapter
+	fn render(w: &mut dyn std::fmt::Write, a: i32) -> std::fmt::Result {
+		write!(w, "a = {}", a)
+	}
+	check(fmt!("["{crate::fmt_write(|w| render(w, a))}"]"), "[a = 42]");
+	let error = crate::fmt_write(|w| { w.write_str("a")?; Err(std::fmt::Error) });
+	assert!(std::fmt::write(&mut String::new(), format_args!("{}", error)).is_err());
+
 	// Control flow
 	let _ = fmt!(if false {});
 	let _ = fmt!(if false {} if true {});
@@ -409,6 +1021,143 @@ fn tests() {
 	let _ = fmt!(match false { _ if false => {} _ => {}});
 	let _ = fmt!(for _ in 0..4 {});
 	let _ = fmt!(for _ in &[1, 2, 3, 4] {});
+	let _ = fmt!(while false {});
+	let _ = fmt!(while let Some(_) = None::<i32> {});
+
+	// For else
+	let (empty, items) = ([0i32; 0], [1, 2, 3]);
+	check(fmt!(for x in &empty { {x}", " } else { "(none)" }), "(none)");
+	check(fmt!(for x in &items { {x}" " } else { "(none)" }), "1 2 3 ");
+	check(fmt!(for x in items.iter().filter(|&&x| x > 5) { {x} } else { "none > 5" } for x in items.iter().filter(|&&x| x > 2) { {x} } else { "none > 2" }), "none > 53");
+	check(fmt!(for x in &items { for y in &empty { {x}{y} } else { "-" } } else { "outer" }), "---");
+	check(fmt!(? for x in &empty { {x} } else { {"empty"} }), "\"empty\"");
+	check(fmt!(let iter = std::iter::from_fn(|| None::<i32>); for x in iter { {x} } else { "done" }), "done");
+
+	// For sep
+	check(fmt!(for x in &items sep ", " { {x} }), "1, 2, 3");
+	check(fmt!(for x in &empty sep ", " { {x} }), "");
+	check(fmt!(for x in &empty sep ", " { {x} } else { "-" }), "-");
+	check(fmt!(for x in (&items) sep (if *x == 3 { " and " } else { ", " }) { {x} } else { "-" }), "1, 2 and 3");
+	check(fmt!(for row in &[[1, 2], [3, 4]] sep "\n" { for x in row sep " " { {x} } }), "1 2\n3 4");
+	check(fmt!(for x in items.iter().filter(|&&x| x != 2) sep "|" { "<"{x}">" }), "<1>|<3>");
+	check(fmt!(? for x in (0..3) sep (", "{x}"=") { {x} }), "0, 1=1, 2=2");
+	let (sep, once) = (3, [7]);
+	check(fmt!(for x in &once sep "," { {x}{sep} }), "73");
+
+	// While loops
+	struct Node { value: i32, next: Option<Box<Node>> }
+	let list = Node { value: 1, next: Some(Box::new(Node { value: 2, next: Some(Box::new(Node { value: 3, next: None })) })) };
+	check(fmt!(
+		let mut cur = Some(&list);
+		while let Some(node) = cur {
+			let double = node.value * 2;
+			{node.value}"="{double}
+			|_| cur = node.next.as_deref();
+			if cur.is_some() { ", " }
+		}
+	), "1=2, 2=4, 3=6");
+	check(fmt!(
+		let mut i = 0;
+		while i < 4 {
+			match i % 2 { 0 => "even", _ => "odd" }
+			|_| i += 1;
+			while (i == 2) { "!" |_| break; }
+		}
+	), "evenodd!evenodd");
+	check(crate::format!(? let mut iter = [1, 2].iter(); while let Some(x) = (iter.next()) { {x} }), "12");
+
+	// Break and continue
+	let log = ["a", "b", "c", "d", "e"];
+	check(fmt!(let mut count = 0; for line in &log { if count >= 3 { "…" break; } {line}"\n" |_| count += 1; }), "a\nb\nc\n…");
+	check(fmt!(for line in &log { if *line == "b" { continue; } {line} }), "acde");
+	check(fmt!(for (i, line) in (log.iter().enumerate()) sep "," { if i == 2 { break; } {line} } else { "-" }), "a,b,");
+	check(fmt!(for _line in &log[..0] { break; } else { "empty" }), "empty");
+	check(fmt!(
+		'outer: for i in 0..3 {
+			for j in 0..3 {
+				if i == 1 && j == 1 { break 'outer; }
+				if j > i { continue 'outer; }
+				{i}{j}" "
+			}
+		}
+		"done"
+	), "00 10 done");
+	check(fmt!('lines: for line in &log sep "|" { if *line == "c" { break 'lines; } {line} }), "a|b|");
+	check(fmt!(let mut i = 0; 'w: while (i < 10) { |_| i += 1; if i % 2 == 0 { continue 'w; } if i > 5 { break 'w; } {i} }), "135");
+	check(fmt!(let mut it = log.iter(); 'w: while let Some(x) = it.next() { if *x == "c" { break 'w; } {x} }), "ab");
+
+	// Debug mode
+	let (a, b) = ("a", Some('b'));
+	check(fmt!(? {a}" "{b}), "\"a\" Some('b')");
+	check(fmt!(? {a:}" "{b:?}" "{b:#?}), "a Some('b') Some(\n    'b',\n)");
+	check(fmt!(move ? {a}), "\"a\"");
+	check(fmt!(? if let Some(b) = b { {b} } for x in 0..2 { {x:} {x} } match b { Some(b) => { {b} }, None => {}, }), "'b'0011'b'");
+	check(crate::format!(? (({a}))), "\"a\"");
+	check(fmt!(? "lit"), "lit");
+	check(fmt!(?), "");
+
+	// Default spec
+	let (a, b) = (10, 255);
+	check(fmt!(default ":04x"; {a}" "{b}), "000a 00ff");
+	check(fmt!(default ":04x"; {a:}" "{b:#x}" "{b:>5}), "10 0xff   255");
+	check(fmt!(default ":?"; {"a"}" "{a}), "\"a\" 10");
+	check(fmt!(move default ":>3"; if a > 0 { {a} } for i in 0..2 { "|"{i} } match b { _ => { {b:} } }), " 10|  0|  1255");
+	check(crate::format!(default ":+"; {a}), "+10");
+
+	// HTML mode
+	let evil = "<script>alert(\"x\" + 'y' & 1)</script>";
+	let escaped = "&lt;script&gt;alert(&quot;x&quot; + &#39;y&#39; &amp; 1)&lt;/script&gt;";
+	check(fmt!(html "<p>"{evil}"</p>"), &format!("<p>{}</p>", escaped));
+	check(fmt!(html {"<b>"}{"<b>":raw}{'&':?}), "&lt;b&gt;<b>&#39;&amp;&#39;");
+	check(fmt!(move html for s in ["<a>", "&"] { if s.len() > 1 { "<li>"{s}"</li>" } else { {s:raw} } }), "<li>&lt;a&gt;</li>&");
+	check(crate::format!(html match Some(evil) { Some(s) => { {s} }, None => {} }), escaped);
+
+	// JSON mode
+	let hostile = "\"},{\"admin\":true,\"x\":\"\\\n\u{1}";
+	let n = -1.5;
+	let s = fmt!(json "{\"msg\":"{hostile}",\"n\":"{n}",\"id\":"{None::<i32>}",\"v\":["{"\"a\"":raw}","{'b':s}"]}").to_string();
+	let value: serde_json::Value = serde_json::from_str(&s).unwrap();
+	assert_eq!(value["msg"], hostile);
+	assert_eq!(value["n"], -1.5);
+	assert_eq!(value["id"], serde_json::Value::Null);
+	assert!(value.get("admin").is_none());
+	assert_eq!(value["v"], serde_json::json!(["a", "b"]));
+	let s = fmt!(json "[" for (i, x) in [1, 2].iter().enumerate() { if i > 0 { "," } {x} "," {x:>3} } "]").to_string();
+	assert_eq!(s, r#"[1,"  1",2,"  2"]"#);
+
+	// Literal writes
+	#[derive(Default)]
+	struct Counter { strs: usize, chars: usize, out: String }
+	impl std::fmt::Write for Counter {
+		fn write_str(&mut self, s: &str) -> std::fmt::Result {
+			self.strs += 1;
+			self.out.push_str(s);
+			Ok(())
+		}
+		fn write_char(&mut self, c: char) -> std::fmt::Result {
+			self.chars += 1;
+			self.out.push(c);
+			Ok(())
+		}
+	}
+	#[track_caller]
+	fn count(t: impl std::fmt::Display, s: &str, strs: usize, chars: usize) {
+		let mut counter = Counter::default();
+		std::fmt::write(&mut counter, format_args!("{}", t)).unwrap();
+		assert_eq!(counter.out, s);
+		if !crate::__OBFSTR {
+			assert_eq!((counter.strs, counter.chars), (strs, chars));
+		}
+	}
+	count(fmt!("a" 'b' "c"), "abc", 1, 0);
+	count(fmt!('a' "b" 'c'), "abc", 1, 0);
+	count(fmt!('a' 'b'), "ab", 1, 0);
+	count(fmt!(':'), ":", 0, 1);
+	count(fmt!("\n"), "\n", 0, 1);
+	count(fmt!('€'), "€", 1, 0);
+	count(fmt!(""), "", 1, 0);
+	count(fmt!({"a"} ':' {"b"} "::" {"c"}), "a:b::c", 4, 1);
+	count(fmt!(if true { '#' } for i in 0..2 { {i} ',' }), "#0,1,", 2, 3);
 
 	// Optimize large fmt invocations
 	check(fmt!(
@@ -418,4 +1167,32 @@ fn tests() {
 		(0 {1} 2 3 {4} 5 6 {7} 8 9 {0} 1 2 {3} 4 5 6 {7} 8 9 {0} 1 2 {3} 4 5 {6} 7 8 {9} 0 1)
 	), concat!("01234567890123456789012345678901", "01234567890123456789012345678901",
 		"01234567890123456789012345678901", "01234567890123456789012345678901"));
+
+	// Formatting traits other than Display
+	let bytes = [1u8, 0xab];
+	let hex = crate::fmt!(for b in &bytes { {b:02x} });
+	assert_eq!(format!("{:x}|{:X}|{:o}|{:b}|{:e}|{:E}|{:p}", hex, hex, hex, hex, hex, hex, hex), "01ab|01ab|01ab|01ab|01ab|01ab|01ab");
+	assert_eq!(format!("{:>6x}|{:#b}|{:.2o}", hex, crate::fmt(|f| f.write_str(if f.alternate() { "alt" } else { "" })), hex), "  01ab|alt|01");
+	let nested = crate::format!("["{hex:x}"] ["{hex:>5b}"] ["{crate::fmt!({hex:#X}):<6}"]");
+	assert_eq!(nested, "[01ab] [ 01ab] [01ab  ]");
+
+	// Delegating from escape hatches through helper functions
+	fn cell(f: &mut std::fmt::Formatter, value: u32) -> std::fmt::Result {
+		crate::fmt_into(f, crate::fmt!("["{value:>3}"]"))
+	}
+	fn line(f: &mut std::fmt::Formatter, values: &[u32]) -> std::fmt::Result {
+		for &value in values {
+			cell(f, value)?;
+		}
+		crate::fmt_into(f, '\n')
+	}
+	let table = [[1, 20], [300, 4]];
+	let s = crate::format!(for values in &table { |f| line(f, values)?; });
+	assert_eq!(s, "[  1][ 20]\n[300][  4]\n");
+	let inner = crate::fmt!({"x"}|f| crate::fmt_into(&mut *f, 1.5)?;);
+	assert_eq!(format!("{:>6}", crate::fmt!(|f| crate::fmt_into(f, inner)?; "!")), " x1.5!");
+	let fails = crate::fmt(|f| { f.write_str("abc")?; Err(std::fmt::Error) });
+	let mut s = String::new();
+	assert!(std::fmt::write(&mut s, format_args!("{}", crate::fmt!(|f| crate::fmt_into(f, fails)?; "unreachable"))).is_err());
+	assert_eq!(s, "abc");
 }
@@ -0,0 +1,266 @@
+#[cfg(not(feature = "portable-atomic"))]
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+#[cfg(feature = "portable-atomic")]
+use portable_atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Prints a line using [fmt syntax](crate::fmt!) at most once per interval.
+///
+/// Evaluates to `true` if the line was printed.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let mut printed = 0;
+/// for i in 0..1000 {
+/// 	if fmtools::println_throttled!(Duration::from_secs(60), "progress: "{i}) {
+/// 		printed += 1;
+/// 	}
+/// }
+/// assert_eq!(printed, 1);
+/// ```
+///
+/// Every call site keeps its own state in a static, the first call always prints.
+/// The template is not evaluated when the call is skipped.
+///
+/// The state is shared between threads, when multiple threads race to print only one of them wins.
+/// On targets without native 64-bit atomics enable the `portable-atomic` feature.
+#[macro_export]
+macro_rules! println_throttled {
+	($interval:expr, $($tt:tt)*) => {{
+		static THROTTLE: $crate::__Throttle = $crate::__Throttle::new();
+		let fired = THROTTLE.ready($interval);
+		if fired {
+			$crate::println!($($tt)*);
+		}
+		fired
+	}};
+}
+
+/// Prints a line using [fmt syntax](crate::fmt!) once every `n` calls.
+///
+/// Evaluates to `true` if the line was printed.
+///
+/// ```
+/// let mut printed = 0;
+/// for i in 0..2500 {
+/// 	if fmtools::every_n!(1000, "item "{i}) {
+/// 		printed += 1;
+/// 	}
+/// }
+/// // Prints items 0, 1000 and 2000
+/// assert_eq!(printed, 3);
+/// ```
+///
+/// Every call site keeps its own counter in a static, the first call always prints.
+/// The template is not evaluated when the call is skipped.
+/// A count of zero is treated as one, printing every call.
+#[macro_export]
+macro_rules! every_n {
+	($n:expr, $($tt:tt)*) => {{
+		static COUNTER: $crate::__EveryN = $crate::__EveryN::new();
+		let fired = COUNTER.ready($n);
+		if fired {
+			$crate::println!($($tt)*);
+		}
+		fired
+	}};
+}
+
+/// Prints using [fmt syntax](crate::fmt!) only the first time the call site is reached.
+///
+/// Evaluates to `true` if the output was printed.
+///
+/// ```
+/// fn legacy_api() {
+/// 	if fmtools::println_once!("legacy_api is deprecated, use "{"new_api"}" instead") {
+/// 		// First call only
+/// 	}
+/// }
+/// for _ in 0..3 {
+/// 	legacy_api();
+/// }
+/// ```
+///
+/// Every call site keeps its own flag in a static, shared between all threads for the lifetime of the process.
+/// The template is not evaluated after the first call.
+#[macro_export]
+macro_rules! print_once {
+	($($tt:tt)*) => {{
+		static ONCE: $crate::__Once = $crate::__Once::new();
+		let fired = ONCE.ready();
+		if fired {
+			$crate::print!($($tt)*);
+		}
+		fired
+	}};
+}
+
+/// Prints a line using [fmt syntax](crate::fmt!) only the first time the call site is reached.
+///
+/// See [`print_once!`] for more information.
+#[macro_export]
+macro_rules! println_once {
+	($($tt:tt)*) => {{
+		static ONCE: $crate::__Once = $crate::__Once::new();
+		let fired = ONCE.ready();
+		if fired {
+			$crate::println!($($tt)*);
+		}
+		fired
+	}};
+}
+
+/// Prints a line to stderr using [fmt syntax](crate::fmt!) only the first time the call site is reached.
+///
+/// ```
+/// let mut warned = 0;
+/// for _ in 0..3 {
+/// 	if fmtools::eprintln_once!("warning: falling back to "{"the slow path"}) {
+/// 		warned += 1;
+/// 	}
+/// }
+/// assert_eq!(warned, 1);
+/// ```
+///
+/// See [`print_once!`] for more information.
+#[macro_export]
+macro_rules! eprintln_once {
+	($($tt:tt)*) => {{
+		static ONCE: $crate::__Once = $crate::__Once::new();
+		let fired = ONCE.ready();
+		if fired {
+			$crate::eprintln!($($tt)*);
+		}
+		fired
+	}};
+}
+
+// Origin of the timestamps, timestamps are stored offset by one to reserve zero for never.
+fn now() -> u64 {
+	static START: OnceLock<Instant> = OnceLock::new();
+	START.get_or_init(Instant::now).elapsed().as_nanos() as u64 + 1
+}
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct __Throttle {
+	last: AtomicU64,
+}
+impl __Throttle {
+	#[inline]
+	pub const fn new() -> __Throttle {
+		__Throttle { last: AtomicU64::new(0) }
+	}
+	pub fn ready(&self, interval: Duration) -> bool {
+		let last = self.last.load(Ordering::Relaxed);
+		let now = now();
+		if last != 0 && u128::from(now - last) < interval.as_nanos() {
+			return false;
+		}
+		self.last.compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed).is_ok()
+	}
+}
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct __EveryN {
+	count: AtomicU64,
+}
+impl __EveryN {
+	#[inline]
+	pub const fn new() -> __EveryN {
+		__EveryN { count: AtomicU64::new(0) }
+	}
+	#[inline]
+	pub fn ready(&self, n: u64) -> bool {
+		self.count.fetch_add(1, Ordering::Relaxed) % u64::max(n, 1) == 0
+	}
+}
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct __Once {
+	fired: AtomicBool,
+}
+impl __Once {
+	#[inline]
+	pub const fn new() -> __Once {
+		__Once { fired: AtomicBool::new(false) }
+	}
+	#[inline]
+	pub fn ready(&self) -> bool {
+		!self.fired.load(Ordering::Relaxed) && !self.fired.swap(true, Ordering::Relaxed)
+	}
+}
+
+#[test]
+fn tests() {
+	use crate::output::with_capture;
+
+	// Throttled by time
+	let mut fired = 0;
+	let s = with_capture(|| for i in 0..100 {
+		fired += crate::println_throttled!(Duration::from_secs(3600), "throttled "{i}) as i32;
+	});
+	assert_eq!((fired, s.as_str()), (1, "throttled 0\n"));
+
+	let mut fired = 0;
+	let s = with_capture(|| for i in 0..3 {
+		fired += crate::println_throttled!(Duration::from_millis(10), "throttled "{i}) as i32;
+		std::thread::sleep(Duration::from_millis(20));
+	});
+	assert_eq!((fired, s.as_str()), (3, "throttled 0\nthrottled 1\nthrottled 2\n"));
+
+	let mut fired = 0;
+	let s = with_capture(|| for _ in 0..100 {
+		fired += crate::println_throttled!(Duration::ZERO, "") as i32;
+	});
+	assert_eq!((fired, s.len()), (100, 100));
+
+	// Throttled by count
+	let mut fired = 0;
+	let s = with_capture(|| for i in 0..25 {
+		fired += crate::every_n!(10, "every "{i}) as i32;
+	});
+	assert_eq!((fired, s.as_str()), (3, "every 0\nevery 10\nevery 20\n"));
+
+	let mut fired = 0;
+	let s = with_capture(|| for i in 0..5 {
+		fired += crate::every_n!(0, {i}) as i32;
+	});
+	assert_eq!((fired, s.as_str()), (5, "0\n1\n2\n3\n4\n"));
+
+	// Shared between threads
+	static THROTTLE: __Throttle = __Throttle::new();
+	static EVERY: __EveryN = __EveryN::new();
+	let (throttled, every) = std::thread::scope(|s| {
+		let handles: Vec<_> = (0..4).map(|_| s.spawn(|| {
+			(0..100).fold((0, 0), |(a, b), _| (a + THROTTLE.ready(Duration::from_secs(3600)) as i32, b + EVERY.ready(50) as i32))
+		})).collect();
+		handles.into_iter().map(|h| h.join().unwrap()).fold((0, 0), |(a, b), (c, d)| (a + c, b + d))
+	});
+	assert_eq!((throttled, every), (1, 8));
+
+	// Once per call site
+	let mut fired = Vec::new();
+	let s = with_capture(|| for i in 0..3 {
+		fired.push(crate::print_once!("once "{i}";"));
+		fired.push(crate::println_once!("line "{i}));
+	});
+	assert_eq!((fired.as_slice(), s.as_str()), (&[true, true, false, false, false, false][..], "once 0;line 0\n"));
+
+	// The template is not evaluated after the first call
+	let evaluated = std::cell::Cell::new(0);
+	let hole = || { evaluated.set(evaluated.get() + 1); "" };
+	let fired: Vec<bool> = (0..3).map(|_| crate::eprintln_once!({hole()})).collect();
+	assert_eq!((fired.as_slice(), evaluated.get()), (&[true, false, false][..], 1));
+
+	static ONCE: __Once = __Once::new();
+	let once = std::thread::scope(|s| {
+		let handles: Vec<_> = (0..4).map(|_| s.spawn(|| (0..100).filter(|_| ONCE.ready()).count())).collect();
+		handles.into_iter().map(|h| h.join().unwrap()).sum::<usize>()
+	});
+	assert_eq!(once, 1);
+}
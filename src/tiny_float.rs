@@ -0,0 +1,233 @@
+use core::fmt;
+
+/// Maximum number of decimals supported by [`f32_compact`] and [`f64_compact`].
+pub const MAX_DECIMALS: u32 = 9;
+
+/// Displays a float with a fixed number of decimals without using the standard float formatting.
+///
+/// ```
+/// let temperature = 23.4691_f32;
+/// let s = fmtools::format!("T="{fmtools::f32_compact(temperature, 2)}"C");
+/// assert_eq!(s, "T=23.47C");
+/// ```
+///
+/// See [`f64_compact`] for more information.
+#[inline]
+pub fn f32_compact(value: f32, decimals: u32) -> Compact {
+	f64_compact(value as f64, decimals)
+}
+
+/// Displays a float with a fixed number of decimals without using the standard float formatting.
+///
+/// ```
+/// assert_eq!(fmtools::f64_compact(9.999, 2).to_string(), "10.00");
+/// assert_eq!(fmtools::f64_compact(-0.5, 0).to_string(), "-1");
+/// assert_eq!(fmtools::f64_compact(1.25, 4).trim_zeros().to_string(), "1.25");
+/// ```
+///
+/// The float formatting in `core::fmt` costs several KiB of code size on small embedded targets.
+/// This implementation scales the fractional part, rounds it to an integer and emits the digits with integer math only.
+/// It is small and does not allocate, but the output is not the shortest representation which round-trips:
+///
+/// * The number of decimals is clamped to [`MAX_DECIMALS`].
+/// * The output is within half a unit of the last decimal of the exact value, ties are rounded away from zero.
+/// * Only the leading digits of values larger than `1e19` are accurate, the remaining integer digits are written as zeros.
+/// * Not a number is written as `NaN`, infinities as `inf` and `-inf`.
+/// * Negative values keep their sign when rounded to zero, including negative zero: `-0.00`.
+///
+/// The width, fill and alignment specifiers are supported.
+#[inline]
+pub fn f64_compact(value: f64, decimals: u32) -> Compact {
+	let decimals = if decimals > MAX_DECIMALS { MAX_DECIMALS } else { decimals };
+	Compact { value, decimals, trim: false }
+}
+
+/// Displayable object returned by [`f32_compact`] and [`f64_compact`].
+#[derive(Copy, Clone, PartialEq)]
+pub struct Compact {
+	value: f64,
+	decimals: u32,
+	trim: bool,
+}
+
+impl Compact {
+	/// Removes trailing zeros from the decimals, and the decimal point if no decimals remain.
+	///
+	/// ```
+	/// assert_eq!(fmtools::f64_compact(2.50, 3).trim_zeros().to_string(), "2.5");
+	/// assert_eq!(fmtools::f64_compact(2.0001, 3).trim_zeros().to_string(), "2");
+	/// ```
+	#[inline]
+	pub const fn trim_zeros(self) -> Compact {
+		Compact { trim: true, ..self }
+	}
+}
+
+// Sign, integer digits, decimal point and decimals
+const BUF_LEN: usize = 1 + 20 + 1 + MAX_DECIMALS as usize;
+
+struct Rendered {
+	buf: [u8; BUF_LEN],
+	// Length of the sign and integer digits
+	head: usize,
+	// Integer digits written as zeros after the head
+	zeros: usize,
+	len: usize,
+}
+impl Rendered {
+	fn head(&self) -> &str {
+		// Only ASCII is written to the buffer
+		core::str::from_utf8(&self.buf[..self.head]).unwrap_or_default()
+	}
+	fn tail(&self) -> &str {
+		core::str::from_utf8(&self.buf[self.head..self.len]).unwrap_or_default()
+	}
+}
+
+impl Compact {
+	fn render(&self) -> Rendered {
+		let mut r = Rendered { buf: [0; BUF_LEN], head: 0, zeros: 0, len: 0 };
+		let push = |r: &mut Rendered, s: &[u8]| {
+			r.buf[r.len..r.len + s.len()].copy_from_slice(s);
+			r.len += s.len();
+		};
+
+		let value = self.value;
+		if value.is_nan() {
+			push(&mut r, b"NaN");
+			r.head = r.len;
+			return r;
+		}
+		if value.is_sign_negative() {
+			push(&mut r, b"-");
+		}
+		let mut abs = if value.is_sign_negative() { -value } else { value };
+		if abs == f64::INFINITY {
+			push(&mut r, b"inf");
+			r.head = r.len;
+			return r;
+		}
+
+		// Scale down until the integer part fits
+		while abs >= 1e19 {
+			abs /= 10.0;
+			r.zeros += 1;
+		}
+		let mut int = abs as u64;
+		let scale = 10u64.pow(self.decimals);
+		let mut frac = ((abs - int as f64) * scale as f64 + 0.5) as u64;
+		if frac >= scale {
+			frac -= scale;
+			int += 1;
+		}
+		if r.zeros > 0 {
+			frac = 0;
+		}
+
+		r.len += digits(&mut r.buf[r.len..], int, 0);
+		r.head = r.len;
+		if self.decimals > 0 {
+			let mut decimals = self.decimals as usize;
+			if self.trim {
+				while decimals > 0 && frac % 10 == 0 {
+					frac /= 10;
+					decimals -= 1;
+				}
+			}
+			if decimals > 0 {
+				push(&mut r, b".");
+				r.len += digits(&mut r.buf[r.len..], frac, decimals);
+			}
+		}
+		r
+	}
+}
+
+// Writes the decimal digits of the value, padded with leading zeros to at least `min` digits.
+fn digits(buf: &mut [u8], mut value: u64, min: usize) -> usize {
+	let mut tmp = [b'0'; 20];
+	let mut n = 0;
+	while value > 0 || n == 0 {
+		tmp[19 - n] = b'0' + (value % 10) as u8;
+		value /= 10;
+		n += 1;
+	}
+	let n = usize::max(n, min);
+	buf[..n].copy_from_slice(&tmp[20 - n..]);
+	n
+}
+
+impl fmt::Display for Compact {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let r = self.render();
+		let padding = f.width().unwrap_or(0).saturating_sub(r.len + r.zeros);
+		let (pre, post) = match f.align() {
+			Some(fmt::Alignment::Left) => (0, padding),
+			Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+			_ => (padding, 0),
+		};
+		let fill = f.fill();
+		crate::pad::write_fill(f, fill, pre)?;
+		f.write_str(r.head())?;
+		crate::pad::write_fill(f, '0', r.zeros)?;
+		f.write_str(r.tail())?;
+		crate::pad::write_fill(f, fill, post)
+	}
+}
+debug_as_display!([] Compact);
+
+#[test]
+fn tests() {
+	// Compare against the standard float formatting within the precision
+	for decimals in 0..=6 {
+		let unit = 10f64.powi(-(decimals as i32));
+		for i in -2000..2000 {
+			let value = i as f64 * 0.01371 + i as f64 * i as f64 * 1.7e-5;
+			let s = f64_compact(value, decimals).to_string();
+			let expected = format!("{:.*}", decimals as usize, value);
+			assert_eq!(s.len(), expected.len(), "{} vs {}", s, expected);
+			let diff = (s.parse::<f64>().unwrap() - expected.parse::<f64>().unwrap()).abs();
+			assert!(diff <= unit * 1.000001, "{} vs {}", s, expected);
+
+			let value = value as f32;
+			let s = f32_compact(value, decimals).to_string();
+			let expected = format!("{:.*}", decimals as usize, value as f64);
+			let diff = (s.parse::<f64>().unwrap() - expected.parse::<f64>().unwrap()).abs();
+			assert!(diff <= unit * 1.000001, "{} vs {}", s, expected);
+		}
+	}
+	for &value in &[1e-9, 0.5, 123456.789, 4294967296.5, 1e18, 9.5e18, 1.7e19, 3.4e38f32 as f64, 1.5e300] {
+		let s = f64_compact(value, 3).to_string();
+		let expected = format!("{:.3}", value);
+		assert_eq!(s.len(), expected.len(), "{} vs {}", s, expected);
+		let a = s.parse::<f64>().unwrap();
+		let b = expected.parse::<f64>().unwrap();
+		assert!((a - b).abs() <= f64::max(b * 1e-12, 1e-3), "{} vs {}", s, expected);
+	}
+
+	// Rounding carry
+	assert_eq!(f64_compact(9.999, 2).to_string(), "10.00");
+	assert_eq!(f64_compact(99.96, 1).to_string(), "100.0");
+	assert_eq!(f64_compact(-0.9999, 3).to_string(), "-1.000");
+	assert_eq!(f32_compact(0.0996, 2).to_string(), "0.10");
+
+	// Special values
+	assert_eq!(f64_compact(f64::NAN, 2).to_string(), "NaN");
+	assert_eq!(f64_compact(f64::INFINITY, 2).to_string(), "inf");
+	assert_eq!(f32_compact(f32::NEG_INFINITY, 2).to_string(), "-inf");
+	assert_eq!(f64_compact(-0.0, 2).to_string(), "-0.00");
+	assert_eq!(f64_compact(0.0, 0).to_string(), "0");
+	assert_eq!(f64_compact(-0.001, 2).to_string(), "-0.00");
+
+	// Decimals clamped and trimmed
+	assert_eq!(f64_compact(0.5, 20).to_string(), "0.500000000");
+	assert_eq!(f64_compact(0.5, 20).trim_zeros().to_string(), "0.5");
+	assert_eq!(f64_compact(10.0, 2).trim_zeros().to_string(), "10");
+	assert_eq!(f64_compact(0.0, 2).trim_zeros().to_string(), "0");
+
+	// Padding
+	assert_eq!(format!("[{:8}]", f64_compact(-1.5, 1)), "[    -1.5]");
+	assert_eq!(format!("[{:<8}]", f64_compact(-1.5, 1)), "[-1.5    ]");
+	assert_eq!(format!("[{:*^7}]", f64_compact(f64::NAN, 1)), "[**NaN**]");
+	assert_eq!(format!("{:>25}", f64_compact(1e20, 1)).len(), 25);
+}
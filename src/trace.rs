@@ -0,0 +1,149 @@
+use std::cell::RefCell;
+use std::fmt;
+
+/// Returns and clears the segments of the templates which failed to format on the current thread.
+///
+/// Requires the `trace-errors` feature.
+///
+/// ```
+/// struct Broken;
+/// impl std::fmt::Display for Broken {
+/// 	fn fmt(&self, _f: &mut std::fmt::Formatter) -> std::fmt::Result {
+/// 		Err(std::fmt::Error)
+/// 	}
+/// }
+///
+/// let (id, broken) = (42, Broken);
+/// let result = std::fmt::write(&mut String::new(), format_args!("{}", fmtools::fmt!("id="{id}" "{broken})));
+/// assert!(result.is_err());
+///
+/// let err = fmtools::last_format_error().unwrap();
+/// assert_eq!(err.segments()[0].segment.replace(' ', ""), "{broken}");
+/// ```
+///
+/// With the feature every literal and hole of [fmt!](crate::fmt!) records the failing segment before propagating the error.
+/// A failure inside a nested template records a segment for every level, the innermost segment first.
+/// Errors returned by the escape hatch are not recorded.
+///
+/// Without the feature the templates propagate the errors without overhead.
+///
+/// The trace is kept in a thread-local until it is taken.
+/// Only the most recent segments are kept, segments of older unhandled failures are eventually dropped.
+pub fn last_format_error() -> Option<TracedError> {
+	TRACE.with(|trace| {
+		let segments = std::mem::take(&mut *trace.borrow_mut());
+		if segments.is_empty() { None } else { Some(TracedError { segments }) }
+	})
+}
+
+/// Segments of the templates which failed to format.
+///
+/// Returned by [`last_format_error`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TracedError {
+	segments: Vec<TracedSegment>,
+}
+
+impl TracedError {
+	/// Returns the failing segments, the innermost segment first.
+	#[inline]
+	pub fn segments(&self) -> &[TracedSegment] {
+		&self.segments
+	}
+}
+
+/// Failing segment of a template.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TracedSegment {
+	/// Source location of the template as `file:line:column`.
+	pub location: &'static str,
+	/// Literal text or hole of the template, as written in the source.
+	pub segment: &'static str,
+}
+
+impl fmt::Display for TracedError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("formatting failed")?;
+		for (i, segment) in self.segments.iter().enumerate() {
+			let sep = if i == 0 { " at " } else { "\n  in " };
+			write!(f, "{}{} ({})", sep, segment.segment, segment.location)?;
+		}
+		Ok(())
+	}
+}
+
+impl std::error::Error for TracedError {}
+
+const MAX_SEGMENTS: usize = 32;
+
+thread_local! {
+	static TRACE: RefCell<Vec<TracedSegment>> = const { RefCell::new(Vec::new()) };
+}
+
+#[doc(hidden)]
+#[cold]
+pub fn __trace_error(location: &'static str, segment: &'static str) {
+	TRACE.with(|trace| {
+		let mut trace = trace.borrow_mut();
+		if trace.len() >= MAX_SEGMENTS {
+			trace.remove(0);
+		}
+		trace.push(TracedSegment { location, segment });
+	});
+}
+
+#[test]
+fn tests() {
+	struct Fails;
+	impl fmt::Display for Fails {
+		fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+			Err(fmt::Error)
+		}
+	}
+	fn render<T: fmt::Display>(value: T) -> fmt::Result {
+		fmt::write(&mut String::new(), format_args!("{}", value))
+	}
+
+	// Failing hole
+	let _ = last_format_error();
+	let fails = Fails;
+	assert!(render(crate::fmt!("a"{1}"b"{fails}"c")).is_err());
+	let err = last_format_error().unwrap();
+	assert_eq!(err.segments().len(), 1);
+	assert_eq!(err.segments()[0].segment.replace(' ', ""), "{fails}");
+	assert!(err.segments()[0].location.starts_with(file!()));
+	assert_eq!(last_format_error(), None);
+
+	// Nested templates, innermost first
+	let inner = crate::fmt!("x="{fails:>4});
+	assert!(render(crate::fmt!(for i in 0..3 { if i == 1 { {inner} } })).is_err());
+	let err = last_format_error().unwrap();
+	let segments: Vec<_> = err.segments().iter().map(|s| s.segment.replace(' ', "")).collect();
+	assert_eq!(segments, ["{fails:>4}", "{inner}"]);
+	let (a, b) = (err.segments()[0], err.segments()[1]);
+	assert_eq!(err.to_string(), format!("formatting failed at {} ({})\n  in {} ({})", a.segment, a.location, b.segment, b.location));
+
+	// Failing writer
+	struct Full(usize);
+	impl fmt::Write for Full {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			self.0 = self.0.checked_sub(s.len()).ok_or(fmt::Error)?;
+			Ok(())
+		}
+	}
+	assert!(fmt::write(&mut Full(4), format_args!("{}", crate::fmt!({12}"abc"))).is_err());
+	assert_eq!(last_format_error().unwrap().segments()[0].segment, "\"abc\"");
+	assert!(fmt::write(&mut Full(4), format_args!("{}", crate::fmt!({12} glue ", " { ("a") ("b") }))).is_err());
+	let segments: Vec<_> = last_format_error().unwrap().segments().iter().map(|s| s.segment.replace(' ', "")).collect();
+	assert_eq!(segments, ["\"b\"", "glue\",\""]);
+
+	// Successful formatting leaves no trace
+	assert_eq!(crate::fmt!("ok "{1}).to_string(), "ok 1");
+	assert_eq!(last_format_error(), None);
+
+	// Old segments are dropped
+	for _ in 0..MAX_SEGMENTS + 5 {
+		let _ = render(crate::fmt!({fails}));
+	}
+	assert_eq!(last_format_error().unwrap().segments().len(), MAX_SEGMENTS);
+}
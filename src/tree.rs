@@ -0,0 +1,186 @@
+use std::fmt;
+
+/// Displays a tree with guide lines, one node per line.
+///
+/// ```
+/// struct Node(&'static str, Vec<Node>);
+///
+/// let root = Node("root", vec![
+/// 	Node("a", vec![Node("b", vec![])]),
+/// 	Node("c", vec![]),
+/// ]);
+/// let s = fmtools::tree(&root, |node| &node.1, |node| node.0).to_string();
+/// assert_eq!(s, "\
+/// root
+/// ├── a
+/// │   └── b
+/// └── c");
+/// ```
+///
+/// The `children` closure returns the children of a node, the `label` closure returns its displayable label.
+/// Lines are separated by newlines, there is no trailing newline.
+///
+/// The tree is walked iteratively with an explicit stack and a single guide prefix reused for every line.
+/// Arbitrarily deep trees do not overflow the stack, use [`max_depth`](Tree::max_depth) to keep the output of deep trees reasonable.
+#[inline]
+pub fn tree<N, I, D, F, L>(root: N, children: F, label: L) -> Tree<N, F, L>
+	where I: IntoIterator<Item = N>,
+		D: fmt::Display,
+		F: Fn(&N) -> I,
+		L: Fn(&N) -> D,
+{
+	Tree { root, children, label, max_depth: usize::MAX }
+}
+
+/// Displayable object returned by [`tree`].
+#[derive(Copy, Clone)]
+pub struct Tree<N, F, L> {
+	root: N,
+	children: F,
+	label: L,
+	max_depth: usize,
+}
+
+const BRANCH: &str = "├── ";
+const LAST: &str = "└── ";
+const GUIDE: &str = "│   ";
+const SPACE: &str = "    ";
+
+impl<N, F, L> Tree<N, F, L> {
+	/// Limits the depth of the rendered nodes, the root is at depth zero.
+	///
+	/// The children of nodes at the maximum depth are summarized on a single line.
+	///
+	/// ```
+	/// // A chain of 100 nodes
+	/// let chain = fmtools::tree(0, |&i| (i < 99).then_some(i + 1), |&i| i).max_depth(2);
+	/// assert_eq!(chain.to_string(), "\
+	/// 0
+	/// └── 1
+	///     └── 2
+	///         └── … (97 more levels)");
+	/// ```
+	#[inline]
+	pub fn max_depth(mut self, depth: usize) -> Self {
+		self.max_depth = depth;
+		self
+	}
+}
+
+impl<N, I, D, F, L> Tree<N, F, L>
+	where I: IntoIterator<Item = N>,
+		D: fmt::Display,
+		F: Fn(&N) -> I,
+		L: Fn(&N) -> D,
+{
+	// Number of levels below the node.
+	fn height(&self, node: &N) -> usize {
+		let mut height = 0;
+		let mut stack = vec![(self.children)(node).into_iter()];
+		while let Some(iter) = stack.last_mut() {
+			match iter.next() {
+				Some(child) => {
+					height = usize::max(height, stack.len());
+					stack.push((self.children)(&child).into_iter());
+				},
+				None => {
+					stack.pop();
+				},
+			}
+		}
+		height
+	}
+
+	fn write_summary(&self, f: &mut fmt::Formatter, prefix: &str, node: &N) -> fmt::Result {
+		let levels = self.height(node);
+		if levels > 0 {
+			let s = if levels == 1 { "" } else { "s" };
+			write!(f, "\n{}{}… ({} more level{})", prefix, LAST, levels, s)?;
+		}
+		Ok(())
+	}
+}
+
+impl<N, I, D, F, L> fmt::Display for Tree<N, F, L>
+	where I: IntoIterator<Item = N>,
+		D: fmt::Display,
+		F: Fn(&N) -> I,
+		L: Fn(&N) -> D,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(self.label)(&self.root).fmt(f)?;
+		let mut prefix = String::new();
+		if self.max_depth == 0 {
+			return self.write_summary(f, &prefix, &self.root);
+		}
+
+		// Children of every node on the path to the current node, and whether more siblings follow at every level
+		let mut stack = vec![(self.children)(&self.root).into_iter().peekable()];
+		let mut more = Vec::new();
+		while let Some(iter) = stack.last_mut() {
+			let Some(node) = iter.next() else {
+				stack.pop();
+				if let Some(more) = more.pop() {
+					prefix.truncate(prefix.len() - if more { GUIDE.len() } else { SPACE.len() });
+				}
+				continue;
+			};
+			let last = iter.peek().is_none();
+			f.write_str("\n")?;
+			f.write_str(&prefix)?;
+			f.write_str(if last { LAST } else { BRANCH })?;
+			(self.label)(&node).fmt(f)?;
+
+			prefix.push_str(if last { SPACE } else { GUIDE });
+			if stack.len() >= self.max_depth {
+				self.write_summary(f, &prefix, &node)?;
+				prefix.truncate(prefix.len() - if last { SPACE.len() } else { GUIDE.len() });
+			}
+			else {
+				more.push(!last);
+				stack.push((self.children)(&node).into_iter().peekable());
+			}
+		}
+		Ok(())
+	}
+}
+debug_as_display!([N, I, D, F, L] Tree<N, F, L>
+	where I: IntoIterator<Item = N>,
+		D: fmt::Display,
+		F: Fn(&N) -> I,
+		L: Fn(&N) -> D,
+);
+
+#[test]
+fn tests() {
+	struct Node(&'static str, Vec<Node>);
+	let root = Node("root", vec![
+		Node("a", vec![Node("a1", vec![Node("x", vec![])]), Node("a2", vec![])]),
+		Node("b", vec![Node("b1", vec![])]),
+	]);
+	let t = tree(&root, |node| &node.1, |node| node.0);
+	assert_eq!(t.to_string(), "root\n├── a\n│   ├── a1\n│   │   └── x\n│   └── a2\n└── b\n    └── b1");
+	assert_eq!(t.max_depth(1).to_string(), "root\n├── a\n│   └── … (2 more levels)\n└── b\n    └── … (1 more level)");
+	assert_eq!(t.max_depth(0).to_string(), "root\n└── … (3 more levels)");
+	assert_eq!(t.max_depth(3).to_string(), t.to_string());
+
+	// Leaf root
+	let leaf = Node("leaf", vec![]);
+	assert_eq!(tree(&leaf, |node| &node.1, |node| node.0).to_string(), "leaf");
+	assert_eq!(tree(&leaf, |node| &node.1, |node| node.0).max_depth(0).to_string(), "leaf");
+
+	// Owned nodes need not be cloneable
+	struct Owned(u32);
+	let owned = tree(Owned(0), |node| (node.0 < 2).then(|| Owned(node.0 + 1)), |node| node.0);
+	assert_eq!(owned.to_string(), "0\n└── 1\n    └── 2");
+
+	// Deep chains do not overflow the stack
+	let chain = |n: usize| tree(0, move |&i| (i < n).then_some(i + 1), |&i| i);
+	let s = chain(10_000).max_depth(3).to_string();
+	assert_eq!(s, "0\n└── 1\n    └── 2\n        └── 3\n            └── … (9997 more levels)");
+	let lines = chain(10_000).max_depth(100).to_string().lines().count();
+	assert_eq!(lines, 102);
+	let s = chain(1000).to_string();
+	assert_eq!(s.lines().count(), 1001);
+	assert!(s.ends_with(&format!("{}└── 1000", SPACE.repeat(999))));
+}
@@ -0,0 +1,204 @@
+use core::fmt;
+
+/// Removes leading and trailing whitespace from the output.
+///
+/// ```
+/// let verbose = false;
+/// let s = fmtools::trim(fmtools::fmt!(
+/// 	if verbose { "verbose " } "mode "
+/// )).to_string();
+/// assert_eq!(s, "mode");
+/// ```
+///
+/// Whitespace is defined by [`char::is_whitespace`], interior whitespace is preserved.
+#[inline]
+pub fn trim<T: fmt::Display>(value: T) -> Trim<T> {
+	Trim { value, start: true, end: true }
+}
+
+/// Removes leading whitespace from the output.
+///
+/// ```
+/// let s = fmtools::trim_start(fmtools::fmt!("\n  "{"text"}" ")).to_string();
+/// assert_eq!(s, "text ");
+/// ```
+///
+/// The output is streamed, whitespace is skipped until the first non-whitespace char.
+#[inline]
+pub fn trim_start<T: fmt::Display>(value: T) -> Trim<T> {
+	Trim { value, start: true, end: false }
+}
+
+/// Removes trailing whitespace from the output.
+///
+/// ```
+/// let s = fmtools::trim_end(fmtools::fmt!(" "{"text"}" \n")).to_string();
+/// assert_eq!(s, " text");
+/// ```
+///
+/// The output is streamed while runs of whitespace are held back until more non-whitespace follows.
+/// The held back whitespace is stored run-length encoded without allocation.
+/// When there are too many different runs to hold back, the value is rendered a second time to write the remaining output.
+#[inline]
+pub fn trim_end<T: fmt::Display>(value: T) -> Trim<T> {
+	Trim { value, start: false, end: true }
+}
+
+/// Displayable object returned by [`trim`], [`trim_start`] and [`trim_end`].
+#[derive(Copy, Clone)]
+pub struct Trim<T> {
+	value: T,
+	start: bool,
+	end: bool,
+}
+impl<T: fmt::Display> fmt::Display for Trim<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut writer = TrimWriter {
+			f,
+			skip_start: self.start,
+			hold_end: self.end,
+			runs: [(' ', 0); RUNS],
+			len: 0,
+			offset: 0,
+			pending_start: 0,
+			overflow: false,
+			content_end: 0,
+		};
+		fmt::write(&mut writer, format_args!("{}", self.value))?;
+		let (overflow, start, end) = (writer.overflow, writer.pending_start, writer.content_end);
+		if overflow && end > start {
+			let mut slice = SliceWriter { f, start, end, offset: 0 };
+			fmt::write(&mut slice, format_args!("{}", self.value))?;
+		}
+		Ok(())
+	}
+}
+debug_as_display!([T: fmt::Display] Trim<T>);
+
+const RUNS: usize = 16;
+
+struct TrimWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	skip_start: bool,
+	hold_end: bool,
+	// Held back whitespace as runs of the same char
+	runs: [(char, usize); RUNS],
+	len: usize,
+	// Byte offset in the output of the current write
+	offset: usize,
+	// Byte offset where the held back whitespace starts
+	pending_start: usize,
+	// Too much whitespace to hold back, only track where the non-whitespace ends
+	overflow: bool,
+	content_end: usize,
+}
+impl TrimWriter<'_, '_> {
+	fn hold(&mut self, ws: &str) {
+		for chr in ws.chars() {
+			if self.len > 0 && self.runs[self.len - 1].0 == chr {
+				self.runs[self.len - 1].1 += 1;
+			}
+			else if self.len < RUNS {
+				self.runs[self.len] = (chr, 1);
+				self.len += 1;
+			}
+			else {
+				self.overflow = true;
+				return;
+			}
+		}
+	}
+	fn flush(&mut self) -> fmt::Result {
+		for &(chr, n) in &self.runs[..self.len] {
+			crate::pad::write_fill(self.f, chr, n)?;
+		}
+		self.len = 0;
+		Ok(())
+	}
+}
+impl fmt::Write for TrimWriter<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let offset = self.offset;
+		self.offset += s.len();
+
+		let mut s = s;
+		let mut start = offset;
+		if self.skip_start {
+			let trimmed = s.trim_start();
+			if trimmed.is_empty() {
+				return Ok(());
+			}
+			self.skip_start = false;
+			start += s.len() - trimmed.len();
+			s = trimmed;
+		}
+		if !self.hold_end {
+			return self.f.write_str(s);
+		}
+
+		let content = s.trim_end();
+		if self.overflow {
+			if !content.is_empty() {
+				self.content_end = start + content.len();
+			}
+			return Ok(());
+		}
+		if !content.is_empty() {
+			self.flush()?;
+			self.f.write_str(content)?;
+			self.pending_start = start + content.len();
+		}
+		else if self.len == 0 {
+			self.pending_start = start;
+		}
+		self.hold(&s[content.len()..]);
+		Ok(())
+	}
+}
+
+// Writes the output in the byte range `start..end`.
+struct SliceWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	start: usize,
+	end: usize,
+	offset: usize,
+}
+impl fmt::Write for SliceWriter<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let offset = self.offset;
+		self.offset += s.len();
+		let from = usize::clamp(self.start, offset, self.offset) - offset;
+		let to = usize::clamp(self.end, offset, self.offset) - offset;
+		self.f.write_str(&s[from..to])
+	}
+}
+
+#[test]
+fn tests() {
+	// Whitespace split across writes
+	let chunked = crate::fmt!({" "}{"\n"}{"\t a"}{"  "}{"b "}{" "}{"\u{3000}"});
+	assert_eq!(trim(chunked).to_string(), "a  b");
+	assert_eq!(trim_start(chunked).to_string(), "a  b  \u{3000}");
+	assert_eq!(trim_end(chunked).to_string(), " \n\t a  b");
+
+	// All whitespace
+	let blank = crate::fmt!({" "}{"\n\n"}{" \t"});
+	assert_eq!(trim(blank).to_string(), "");
+	assert_eq!(trim_start(blank).to_string(), "");
+	assert_eq!(trim_end(blank).to_string(), "");
+	assert_eq!(trim("").to_string(), "");
+
+	// Interior whitespace preserved
+	assert_eq!(trim(" a \t b\n\nc ").to_string(), "a \t b\n\nc");
+	assert_eq!(trim(crate::fmt!({"a"}{" \t "}{" "}{"b"})).to_string(), "a \t  b");
+
+	// Too many runs to hold back
+	let many = " \t".repeat(20);
+	let spaced = crate::fmt!({many}"a"{many}"b"{many});
+	assert_eq!(trim(spaced).to_string(), format!("a{}b", many));
+	assert_eq!(trim_end(spaced).to_string(), format!("{0}a{0}b", many));
+	let spaced = crate::fmt!("a"{many}{many});
+	assert_eq!(trim_end(spaced).to_string(), "a");
+	let spaced = crate::fmt!("a"{many}"b"{many}"c");
+	assert_eq!(trim_end(spaced).to_string(), format!("a{0}b{0}c", many));
+}
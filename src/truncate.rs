@@ -0,0 +1,264 @@
+use core::fmt;
+
+/// Truncates the output to at most the given number of chars.
+///
+/// ```
+/// let path = "/var/log/application/server.log";
+/// let s = fmtools::format!("file: "{fmtools::truncate(12, path)});
+/// assert_eq!(s, "file: /var/log/app…");
+///
+/// let cell = fmtools::truncate(8, fmtools::fmt!("id="{1234567}" ok")).suffix("...");
+/// assert_eq!(cell.to_string(), "id=12345...");
+/// ```
+///
+/// When the output is truncated a suffix is appended, `…` by default.
+/// Output which fits within the limit is written as is without suffix.
+/// With the `unicode-width` feature the output is truncated to a number of display columns instead.
+///
+/// The output is streamed to the formatter without allocating and never splits a char.
+/// Writing the value is aborted as soon as the output is known to exceed the limit.
+#[inline]
+pub fn truncate<T: fmt::Display>(max_chars: usize, value: T) -> Truncate<'static, T> {
+	Truncate { value, max_chars, suffix: "…" }
+}
+
+/// Displayable object returned by [`truncate`] and [`DisplayExt::truncated`](crate::DisplayExt::truncated).
+#[derive(Copy, Clone)]
+pub struct Truncate<'a, T> {
+	value: T,
+	max_chars: usize,
+	suffix: &'a str,
+}
+impl<T> Truncate<'_, T> {
+	/// Sets the suffix appended when the output is truncated.
+	#[inline]
+	pub fn suffix(self, suffix: &str) -> Truncate<'_, T> {
+		Truncate { value: self.value, max_chars: self.max_chars, suffix }
+	}
+}
+impl<T: fmt::Display> fmt::Display for Truncate<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match write_truncated(f, &self.value, self.max_chars)? {
+			Some(_) => f.write_str(self.suffix),
+			None => Ok(()),
+		}
+	}
+}
+debug_as_display!([T: fmt::Display] Truncate<'_, T>);
+
+/// Truncates the output to at most the given number of bytes without splitting a char.
+///
+/// ```
+/// let s = fmtools::truncate_bytes(5, "héllo").to_string();
+/// assert_eq!(s, "héll");
+/// assert_eq!(s.len(), 5);
+/// ```
+///
+/// When a multi-byte char straddles the limit the output ends before that char and may be shorter than the limit.
+/// The output is not padded, the formatting options are ignored.
+#[inline]
+pub fn truncate_bytes<T: fmt::Display>(max_bytes: usize, value: T) -> TruncateBytes<'static, T> {
+	TruncateBytes { value, max_bytes, suffix: "" }
+}
+
+/// Truncates the output to at most the given number of bytes including the suffix appended when truncated.
+///
+/// ```
+/// let message = fmtools::fmt!("user "{"alice"}" logged in from "{"10.0.0.1"});
+/// let s = fmtools::truncate_bytes_with(24, "...", message).to_string();
+/// assert_eq!(s, "user alice logged in ...");
+/// assert_eq!(s.len(), 24);
+/// ```
+///
+/// The byte length of the suffix is reserved out of the limit, the total output never exceeds `max_bytes`.
+/// Output which fits within the limit is written as is without suffix.
+///
+/// If the suffix itself exceeds the limit, only the suffix is written truncated to the limit without splitting a char:
+///
+/// ```
+/// assert_eq!(fmtools::truncate_bytes_with(2, "...", "abcdef").to_string(), "..");
+/// assert_eq!(fmtools::truncate_bytes_with(2, "...", "ab").to_string(), "ab");
+/// ```
+///
+/// The value is written twice when truncated, the first time to find out if it exceeds the limit.
+/// Writing is aborted as soon as the output is known to exceed the limit.
+#[inline]
+pub fn truncate_bytes_with<'a, T: fmt::Display>(max_bytes: usize, suffix: &'a str, value: T) -> TruncateBytes<'a, T> {
+	TruncateBytes { value, max_bytes, suffix }
+}
+
+/// Displayable object returned by [`truncate_bytes`] and [`truncate_bytes_with`].
+#[derive(Copy, Clone)]
+pub struct TruncateBytes<'a, T> {
+	value: T,
+	max_bytes: usize,
+	suffix: &'a str,
+}
+impl<T: fmt::Display> fmt::Display for TruncateBytes<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		struct Discard;
+		impl fmt::Write for Discard {
+			fn write_str(&mut self, _s: &str) -> fmt::Result {
+				Ok(())
+			}
+		}
+		// Check if the output fits before reserving space for the suffix
+		if write_bytes(Discard, &self.value, self.max_bytes)? {
+			return write!(f, "{}", self.value);
+		}
+		let budget = self.max_bytes.saturating_sub(self.suffix.len());
+		write_bytes(&mut *f, &self.value, budget)?;
+		f.write_str(floor_str(self.suffix, self.max_bytes))
+	}
+}
+debug_as_display!([T: fmt::Display] TruncateBytes<'_, T>);
+
+// Returns the longest prefix of at most the given number of bytes ending on a char boundary.
+fn floor_str(s: &str, max_bytes: usize) -> &str {
+	if max_bytes >= s.len() {
+		return s;
+	}
+	let mut end = max_bytes;
+	while !s.is_char_boundary(end) {
+		end -= 1;
+	}
+	&s[..end]
+}
+
+// Writes the value truncated to the number of bytes, returns if the output fits.
+fn write_bytes<W: fmt::Write, T: fmt::Display + ?Sized>(inner: W, value: &T, max_bytes: usize) -> Result<bool, fmt::Error> {
+	struct BytesWriter<W> {
+		inner: W,
+		remaining: usize,
+		exceeded: bool,
+	}
+	impl<W: fmt::Write> fmt::Write for BytesWriter<W> {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			if s.len() > self.remaining {
+				self.inner.write_str(floor_str(s, self.remaining))?;
+				self.exceeded = true;
+				return Err(fmt::Error);
+			}
+			self.remaining -= s.len();
+			self.inner.write_str(s)
+		}
+	}
+	let mut writer = BytesWriter { inner, remaining: max_bytes, exceeded: false };
+	match fmt::write(&mut writer, format_args!("{}", value)) {
+		Err(_) if writer.exceeded => Ok(false),
+		Err(err) => Err(err),
+		Ok(()) => Ok(true),
+	}
+}
+
+/// Writes the value truncated to the width.
+///
+/// Returns the unused width if the output was truncated.
+/// The value is aborted as soon as the output is known to exceed the width.
+pub(crate) fn write_truncated<T: fmt::Display + ?Sized>(f: &mut fmt::Formatter, value: &T, width: usize) -> Result<Option<usize>, fmt::Error> {
+	let mut writer = TruncateWriter { f, remaining: width, exceeded: false };
+	match fmt::write(&mut writer, format_args!("{}", value)) {
+		Err(_) if writer.exceeded => Ok(Some(writer.remaining)),
+		Err(err) => Err(err),
+		Ok(()) => Ok(None),
+	}
+}
+
+struct TruncateWriter<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	remaining: usize,
+	exceeded: bool,
+}
+impl fmt::Write for TruncateWriter<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for (i, chr) in s.char_indices() {
+			let width = crate::width::char_width(chr);
+			if width > self.remaining {
+				self.f.write_str(&s[..i])?;
+				self.exceeded = true;
+				return Err(fmt::Error);
+			}
+			self.remaining -= width;
+		}
+		self.f.write_str(s)
+	}
+}
+
+#[test]
+fn tests() {
+	#[track_caller]
+	fn check(t: impl fmt::Display, s: &str) {
+		assert_eq!(t.to_string(), s);
+	}
+
+	check(truncate(3, "abc"), "abc");
+	check(truncate(3, "abcd"), "abc…");
+	check(truncate(3, crate::fmt!("a"{"b"}"c"{""})), "abc");
+	check(truncate(3, crate::fmt!("a"{"b"}"c"{"d"})), "abc…");
+	#[cfg(not(feature = "unicode-width"))]
+	check(truncate(2, "é€😀").suffix("..."), "é€...");
+	#[cfg(feature = "unicode-width")]
+	check(truncate(3, "日本語").suffix("..."), "日...");
+	check(truncate(0, "a"), "…");
+	check(truncate(0, ""), "");
+
+	// Exact fit boundary
+	check(truncate(5, "hello"), "hello");
+	check(truncate(4, "hello"), "hell…");
+	check(truncate(5, crate::fmt!("hel"{"lo"}{""})), "hello");
+	check(truncate(5, crate::fmt!("hel"{"lo"}{"!"})), "hello…");
+
+	// Multibyte chars straddling the limit
+	#[cfg(not(feature = "unicode-width"))]
+	{
+		check(truncate(3, "aé😀b"), "aé😀…");
+		check(truncate(3, "aé😀"), "aé😀");
+		check(truncate(2, crate::fmt!("a"{"é😀"}"b")), "aé…");
+		check(truncate(1, "éa"), "é…");
+	}
+
+	// Empty input
+	check(truncate(3, crate::fmt!()), "");
+	check(truncate(3, "").suffix("..."), "");
+
+	// Nesting inside fmt!
+	let name = "abcdefgh";
+	check(crate::fmt!("["{truncate(3, name)}"]"), "[abc…]");
+	check(crate::fmt!({truncate(6, crate::fmt!("<"{truncate(2, name).suffix("")}">" "tail"))}), "<ab>ta…");
+
+	// Writing stops as soon as the limit is exceeded
+	let writes = std::cell::Cell::new(0);
+	let endless = crate::fmt(|f| loop {
+		writes.set(writes.get() + 1);
+		f.write_str("ab")?;
+	});
+	check(truncate(5, endless), "ababa…");
+	assert_eq!(writes.get(), 3);
+
+	// Limits landing on every byte of a 4-byte char
+	let s = "ab😀cd";
+	for (max_bytes, expected) in [(0, ""), (1, "a"), (2, "ab"), (3, "ab"), (4, "ab"), (5, "ab"), (6, "ab😀"), (7, "ab😀c"), (8, "ab😀cd"), (9, "ab😀cd")] {
+		check(truncate_bytes(max_bytes, s), expected);
+		check(truncate_bytes(max_bytes, crate::fmt!("a"{'b'}{"😀"}"c"{"d"})), expected);
+	}
+	check(truncate_bytes(3, crate::fmt!({"é"}{"é"})), "é");
+
+	// Suffix reserved out of the limit
+	for (max_bytes, expected) in [(1, "~"), (3, "ab~"), (6, "ab~"), (7, "ab😀~"), (8, "ab😀cd"), (9, "ab😀cd")] {
+		let t = truncate_bytes_with(max_bytes, "~", s).to_string();
+		assert_eq!(t, expected);
+		assert!(t.len() <= max_bytes);
+	}
+	check(truncate_bytes_with(4, "…", "abcdef"), "a…");
+	check(truncate_bytes_with(6, "…", "abcdef"), "abcdef");
+	check(truncate_bytes_with(5, "…", "ab😀cd"), "ab…");
+	check(truncate_bytes_with(7, "…", "ab😀cd"), "ab…");
+	check(truncate_bytes_with(3, "…", "abcd"), "…");
+
+	// Suffix longer than the limit
+	check(truncate_bytes_with(2, "…", "abcd"), "");
+	check(truncate_bytes_with(4, "[cut]", "abcdef"), "[cut");
+	check(truncate_bytes_with(4, "[cut]", "abcd"), "abcd");
+	check(truncate_bytes_with(0, "…", ""), "");
+	check(truncate_bytes_with(0, "…", "a"), "");
+}
@@ -0,0 +1,105 @@
+use core::cell::Cell;
+use core::fmt;
+
+/// Returns a displayable object with a typed error channel.
+///
+/// The `fmt::Error` type carries no information, the closure receives an [`ErrorSlot`] to record the actual cause and abort formatting.
+/// After formatting fails the caller recovers the error with [`take_error`](TryFmt::take_error).
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum RenderError { Poisoned }
+///
+/// let value = fmtools::try_fmt(|f, slot| {
+/// 	f.write_str("value = ")?;
+/// 	Err(slot.fail(RenderError::Poisoned))
+/// });
+///
+/// let mut s = String::new();
+/// assert!(write!(s, "{}", value).is_err());
+/// assert_eq!(value.take_error(), Some(RenderError::Poisoned));
+/// assert_eq!(value.take_error(), None);
+/// ```
+///
+/// The slot holds the error of the most recent failed render, a later failure overwrites an earlier one.
+/// Successful renders leave the slot untouched, take the error after every render which may fail.
+/// Formatting errors not recorded through the slot (eg. returned by the underlying writer) leave it empty.
+///
+/// The object is not `Sync` as rendering records the error through a shared reference.
+#[inline]
+pub fn try_fmt<E, F: Fn(&mut fmt::Formatter, &ErrorSlot<E>) -> fmt::Result>(closure: F) -> TryFmt<E, F> {
+	TryFmt { closure, error: ErrorSlot { error: Cell::new(None) } }
+}
+
+/// Displayable object returned by [`try_fmt`].
+pub struct TryFmt<E, F> {
+	closure: F,
+	error: ErrorSlot<E>,
+}
+impl<E, F> TryFmt<E, F> {
+	/// Takes the error recorded by the last failed render.
+	#[inline]
+	pub fn take_error(&self) -> Option<E> {
+		self.error.error.take()
+	}
+}
+impl<E, F: Fn(&mut fmt::Formatter, &ErrorSlot<E>) -> fmt::Result> fmt::Display for TryFmt<E, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(self.closure)(f, &self.error)
+	}
+}
+impl<E, F: Fn(&mut fmt::Formatter, &ErrorSlot<E>) -> fmt::Result> fmt::Debug for TryFmt<E, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(self.closure)(f, &self.error)
+	}
+}
+
+/// Records the error of a [`try_fmt`] closure.
+pub struct ErrorSlot<E> {
+	error: Cell<Option<E>>,
+}
+impl<E> ErrorSlot<E> {
+	/// Records the error and returns `fmt::Error` to abort formatting.
+	#[inline]
+	pub fn fail(&self, error: E) -> fmt::Error {
+		self.error.set(Some(error));
+		fmt::Error
+	}
+}
+
+#[test]
+fn tests() {
+	use std::fmt::Write;
+
+	#[derive(Debug, PartialEq)]
+	struct Invalid(u32);
+
+	let value = try_fmt(|f, slot| {
+		for i in 0..4 {
+			if i == 2 {
+				return Err(slot.fail(Invalid(i)));
+			}
+			write!(f, "{} ", i)?;
+		}
+		Ok(())
+	});
+
+	// Recover the error after a failed render
+	let mut s = String::new();
+	assert!(write!(s, "{}", value).is_err());
+	assert_eq!(s, "0 1 ");
+	assert_eq!(value.take_error(), Some(Invalid(2)));
+	assert_eq!(value.take_error(), None);
+
+	// Success leaves the slot empty
+	let ok = try_fmt(|f, _: &ErrorSlot<Invalid>| f.write_str("ok"));
+	assert_eq!(ok.to_string(), "ok");
+	assert_eq!(ok.take_error(), None);
+
+	// Errors not recorded through the slot
+	let other = try_fmt(|_, _: &ErrorSlot<Invalid>| Err(fmt::Error));
+	assert!(write!(s, "{:?}", other).is_err());
+	assert_eq!(other.take_error(), None);
+}
@@ -0,0 +1,157 @@
+use core::fmt;
+
+/// Adapts a displayable object to implement `ufmt::uDisplay`.
+///
+/// Requires the `ufmt` feature.
+///
+/// ```
+/// struct Sink(String);
+/// impl ufmt::uWrite for Sink {
+/// 	type Error = std::convert::Infallible;
+/// 	fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+/// 		self.0.push_str(s);
+/// 		Ok(())
+/// 	}
+/// }
+///
+/// let value = 42;
+/// let mut sink = Sink(String::new());
+/// ufmt::uwrite!(sink, "{}", fmtools::as_udisplay(&fmtools::fmt!("value = "{value}))).unwrap();
+/// assert_eq!(sink.0, "value = 42");
+/// ```
+///
+/// Errors returned by the writer are passed through.
+/// Errors returned by the displayable object itself cannot be represented and end the output early without error.
+#[inline]
+pub fn as_udisplay<T: fmt::Display + ?Sized>(value: &T) -> AsUDisplay<'_, T> {
+	AsUDisplay(value)
+}
+
+/// Displayable object adapted for ufmt.
+///
+/// See [`as_udisplay`] for more information.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct AsUDisplay<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized> Copy for AsUDisplay<'_, T> {}
+impl<T: ?Sized> Clone for AsUDisplay<'_, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T: fmt::Display + ?Sized> ufmt::uDisplay for AsUDisplay<'_, T> {
+	fn fmt<W: ufmt::uWrite + ?Sized>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+		let mut shim = FmtToUWrite { f, error: None };
+		let _ = fmt::write(&mut shim, format_args!("{}", self.0));
+		match shim.error {
+			Some(error) => Err(error),
+			None => Ok(()),
+		}
+	}
+}
+
+struct FmtToUWrite<'a, 'w, W: ufmt::uWrite + ?Sized> {
+	f: &'a mut ufmt::Formatter<'w, W>,
+	error: Option<W::Error>,
+}
+impl<W: ufmt::uWrite + ?Sized> fmt::Write for FmtToUWrite<'_, '_, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.f.write_str(s).map_err(|error| {
+			self.error = Some(error);
+			fmt::Error
+		})
+	}
+}
+
+/// Adapts a `ufmt::uDisplay` object to implement `Display`.
+///
+/// Requires the `ufmt` feature.
+///
+/// ```
+/// struct Celsius(i32);
+/// impl ufmt::uDisplay for Celsius {
+/// 	fn fmt<W: ufmt::uWrite + ?Sized>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+/// 		ufmt::uwrite!(f, "{}°C", self.0)
+/// 	}
+/// }
+///
+/// let temp = Celsius(21);
+/// let s = fmtools::format!("temp = "{fmtools::from_udisplay(&temp)});
+/// assert_eq!(s, "temp = 21°C");
+/// ```
+#[inline]
+pub fn from_udisplay<T: ufmt::uDisplay + ?Sized>(value: &T) -> FromUDisplay<'_, T> {
+	FromUDisplay(value)
+}
+
+/// ufmt object adapted for `Display`.
+///
+/// See [`from_udisplay`] for more information.
+#[repr(transparent)]
+pub struct FromUDisplay<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized> Copy for FromUDisplay<'_, T> {}
+impl<T: ?Sized> Clone for FromUDisplay<'_, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T: ufmt::uDisplay + ?Sized> fmt::Display for FromUDisplay<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		ufmt::uDisplay::fmt(self.0, &mut ufmt::Formatter::new(&mut UWriteToFmt(f)))
+	}
+}
+debug_as_display!([T: ufmt::uDisplay + ?Sized] FromUDisplay<'_, T>);
+
+struct UWriteToFmt<'a, 'f>(&'a mut fmt::Formatter<'f>);
+impl ufmt::uWrite for UWriteToFmt<'_, '_> {
+	type Error = fmt::Error;
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.0.write_str(s)
+	}
+}
+
+#[test]
+fn tests() {
+	struct Sink(String, usize);
+	impl ufmt::uWrite for Sink {
+		type Error = ();
+		fn write_str(&mut self, s: &str) -> Result<(), ()> {
+			if self.0.len() + s.len() > self.1 {
+				return Err(());
+			}
+			self.0.push_str(s);
+			Ok(())
+		}
+	}
+
+	struct Point(i32, i32);
+	impl ufmt::uDisplay for Point {
+		fn fmt<W: ufmt::uWrite + ?Sized>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+			ufmt::uwrite!(f, "({}, {})", self.0, self.1)
+		}
+	}
+
+	// Round trip through both adapters
+	let point = Point(1, -2);
+	let value = crate::fmt!("point = "{from_udisplay(&point)});
+	assert_eq!(value.to_string(), "point = (1, -2)");
+	let mut sink = Sink(String::new(), 100);
+	ufmt::uwrite!(sink, "[{}]", as_udisplay(&value)).unwrap();
+	assert_eq!(sink.0, "[point = (1, -2)]");
+
+	// Writer errors are passed through
+	let mut sink = Sink(String::new(), 10);
+	assert_eq!(ufmt::uwrite!(sink, "{}", as_udisplay(&value)), Err(()));
+
+	// Display errors end the output early
+	let fails = crate::fmt(|f| { f.write_str("abc")?; Err(fmt::Error) });
+	let mut sink = Sink(String::new(), 100);
+	assert_eq!(ufmt::uwrite!(sink, "{}", as_udisplay(&fails)), Ok(()));
+	assert_eq!(sink.0, "abc");
+}
@@ -0,0 +1,34 @@
+// Measures text for alignment.
+//
+// Counts chars by default, with the `unicode-width` feature counts display columns instead.
+// Wide chars (eg. CJK and emoji) take two columns, zero width chars (eg. combining marks and joiners) take none.
+// Control chars such as newlines have no display width, they count as one like they do without the feature.
+
+#[cfg(not(feature = "unicode-width"))]
+#[inline]
+pub(crate) fn char_width(_chr: char) -> usize {
+	1
+}
+#[cfg(feature = "unicode-width")]
+#[inline]
+pub(crate) fn char_width(chr: char) -> usize {
+	unicode_width::UnicodeWidthChar::width(chr).unwrap_or(1)
+}
+
+#[inline]
+pub(crate) fn str_width(s: &str) -> usize {
+	s.chars().map(char_width).sum()
+}
+
+#[test]
+fn tests() {
+	assert_eq!(str_width(""), 0);
+	assert_eq!(str_width("abc"), 3);
+	assert_eq!(str_width("a\n\tb\x07"), 5);
+	#[cfg(not(feature = "unicode-width"))]
+	assert_eq!(str_width("日本😀e\u{301}"), 5);
+	#[cfg(feature = "unicode-width")]
+	assert_eq!(str_width("日本😀e\u{301}"), 7);
+	#[cfg(feature = "unicode-width")]
+	assert_eq!(str_width("👩\u{200d}💻"), 4);
+}
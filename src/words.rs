@@ -0,0 +1,195 @@
+use core::fmt;
+
+/// Displays the number in English words.
+///
+/// ```
+/// assert_eq!(fmtools::words(123456).to_string(), "one hundred twenty-three thousand four hundred fifty-six");
+/// assert_eq!(fmtools::words(-40).to_string(), "negative forty");
+/// assert_eq!(fmtools::words(21).ordinal().to_string(), "twenty-first");
+///
+/// let retries = 3;
+/// let s = fmtools::format!("retried "{fmtools::words(retries)}" times");
+/// assert_eq!(s, "retried three times");
+/// ```
+///
+/// The numbers twenty-one to ninety-nine are hyphenated, the hundreds are written without `and`.
+/// The full range of `i64` is supported, up to quintillions.
+///
+/// The width, fill, alignment and precision apply to the whole output like they do for strings.
+#[inline]
+pub const fn words(n: i64) -> Words {
+	Words { n, ordinal: false }
+}
+
+/// Displayable object returned by [`words`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Words {
+	n: i64,
+	ordinal: bool,
+}
+impl Words {
+	/// Writes the ordinal words instead: `first`, `twenty-second`, `one hundredth`.
+	#[inline]
+	pub const fn ordinal(self) -> Words {
+		Words { ordinal: true, ..self }
+	}
+
+	fn write(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut words = WordList { words: [("", ""); 32], len: 0 };
+		if self.n < 0 {
+			words.push("", "negative");
+		}
+		let n = self.n.unsigned_abs();
+		if n == 0 {
+			words.push(" ", "zero");
+		}
+		let mut scale = SCALES.len();
+		while scale > 0 {
+			scale -= 1;
+			let group = n / 1000u64.pow(scale as u32) % 1000;
+			if group != 0 {
+				words.push_group(group);
+				if scale > 0 {
+					words.push(" ", SCALES[scale]);
+				}
+			}
+		}
+
+		let words = &words.words[..words.len];
+		for (i, &(sep, word)) in words.iter().enumerate() {
+			if i > 0 {
+				f.write_str(sep)?;
+			}
+			if self.ordinal && i + 1 == words.len() {
+				write_ordinal(f, word)?;
+			}
+			else {
+				f.write_str(word)?;
+			}
+		}
+		Ok(())
+	}
+}
+impl fmt::Display for Words {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if f.width().is_none() && f.precision().is_none() {
+			return self.write(f);
+		}
+		crate::pad::write_with_options(f, &|f: &mut fmt::Formatter| self.write(f))
+	}
+}
+debug_as_display!([] Words);
+
+static ONES: [&str; 20] = [
+	"zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+	"ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+static TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+static SCALES: [&str; 7] = ["", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion"];
+
+// The words with their separator from the previous word.
+// The quintillions group of an i64 is a single digit, so the longest number has the sign,
+// two words for the quintillions, five words for each of the five middle groups and four words for the last group.
+struct WordList {
+	words: [(&'static str, &'static str); 32],
+	len: usize,
+}
+impl WordList {
+	fn push(&mut self, sep: &'static str, word: &'static str) {
+		self.words[self.len] = (sep, word);
+		self.len += 1;
+	}
+	fn push_group(&mut self, group: u64) {
+		let (hundreds, rest) = ((group / 100) as usize, (group % 100) as usize);
+		if hundreds != 0 {
+			self.push(" ", ONES[hundreds]);
+			self.push(" ", "hundred");
+		}
+		if rest >= 20 {
+			self.push(" ", TENS[rest / 10]);
+			if rest % 10 != 0 {
+				self.push("-", ONES[rest % 10]);
+			}
+		}
+		else if rest != 0 {
+			self.push(" ", ONES[rest]);
+		}
+	}
+}
+
+fn write_ordinal(f: &mut fmt::Formatter, word: &str) -> fmt::Result {
+	let irregular = match word {
+		"one" => "first",
+		"two" => "second",
+		"three" => "third",
+		"five" => "fifth",
+		"eight" => "eighth",
+		"nine" => "ninth",
+		"twelve" => "twelfth",
+		_ => "",
+	};
+	if !irregular.is_empty() {
+		f.write_str(irregular)
+	}
+	else if let Some(stem) = word.strip_suffix('y') {
+		f.write_str(stem)?;
+		f.write_str("ieth")
+	}
+	else {
+		f.write_str(word)?;
+		f.write_str("th")
+	}
+}
+
+#[test]
+fn tests() {
+	const TESTS: &[(i64, &str, &str)] = &[
+		(0, "zero", "zeroth"),
+		(1, "one", "first"),
+		(2, "two", "second"),
+		(3, "three", "third"),
+		(4, "four", "fourth"),
+		(5, "five", "fifth"),
+		(8, "eight", "eighth"),
+		(9, "nine", "ninth"),
+		(10, "ten", "tenth"),
+		(11, "eleven", "eleventh"),
+		(12, "twelve", "twelfth"),
+		(13, "thirteen", "thirteenth"),
+		(19, "nineteen", "nineteenth"),
+		(20, "twenty", "twentieth"),
+		(21, "twenty-one", "twenty-first"),
+		(42, "forty-two", "forty-second"),
+		(90, "ninety", "ninetieth"),
+		(99, "ninety-nine", "ninety-ninth"),
+		(100, "one hundred", "one hundredth"),
+		(101, "one hundred one", "one hundred first"),
+		(110, "one hundred ten", "one hundred tenth"),
+		(115, "one hundred fifteen", "one hundred fifteenth"),
+		(999, "nine hundred ninety-nine", "nine hundred ninety-ninth"),
+		(1000, "one thousand", "one thousandth"),
+		(1001, "one thousand one", "one thousand first"),
+		(1_000_000, "one million", "one millionth"),
+		(2_000_003, "two million three", "two million third"),
+		(123_456, "one hundred twenty-three thousand four hundred fifty-six", "one hundred twenty-three thousand four hundred fifty-sixth"),
+		(1_000_000_000, "one billion", "one billionth"),
+		(3_000_070_000, "three billion seventy thousand", "three billion seventy thousandth"),
+		(-1, "negative one", "negative first"),
+		(-20, "negative twenty", "negative twentieth"),
+		(-305, "negative three hundred five", "negative three hundred fifth"),
+	];
+	for &(n, cardinal, ordinal) in TESTS {
+		assert_eq!(words(n).to_string(), cardinal, "{}", n);
+		assert_eq!(words(n).ordinal().to_string(), ordinal, "{}", n);
+	}
+
+	// Limits of the range
+	assert_eq!(words(i64::MAX).to_string(), "nine quintillion two hundred twenty-three quadrillion three hundred seventy-two trillion \
+		thirty-six billion eight hundred fifty-four million seven hundred seventy-five thousand eight hundred seven");
+	assert_eq!(words(i64::MIN).ordinal().to_string(), "negative nine quintillion two hundred twenty-three quadrillion three hundred seventy-two trillion \
+		thirty-six billion eight hundred fifty-four million seven hundred seventy-five thousand eight hundred eighth");
+
+	// Formatting options
+	assert_eq!(format!("[{:>8}|{:<6}|{:.5}]", words(7), words(3).ordinal(), words(40)), "[   seven|third |forty]");
+	assert_eq!(crate::format!("the "{words(2).ordinal()}" of "{words(12)}), "the second of twelve");
+}